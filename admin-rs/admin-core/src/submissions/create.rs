@@ -1,4 +1,4 @@
-use super::{Error, Submission};
+use super::{risk::assess_submission, Error, Submission};
 use crate::client::Client;
 use entity::{sea_orm_active_enums::SubmissionStatus, submissions};
 use sea_orm::{prelude::*, sea_query::Condition, TransactionTrait};
@@ -22,12 +22,15 @@ pub async fn create_submission(client: &Client, data: Submission) -> Result<Subm
                 .add(submissions::Column::Status.eq(SubmissionStatus::InProcess))
                 .add(submissions::Column::Status.eq(SubmissionStatus::WaitingForUpdate)),
         )
-        .one(client.db.as_ref())
+        .one(&txn)
         .await?;
     if let Some(submission_in_progress) = submission_in_progress {
         return Err(Error::Duplicate(submission_in_progress.id));
     };
-    let model = data.active_model().insert(&txn).await?;
+    let assessment = assess_submission(&txn, &data).await?;
+    let mut model = data.active_model();
+    assessment.apply(&mut model)?;
+    let model = model.insert(&txn).await?;
     txn.commit().await?;
 
     let submission = Submission::try_from_db(&client.db, model).await?;
@@ -45,7 +48,6 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     #[tokio::test]
-    #[ignore = "needs database to run"]
     async fn test_create() {
         let _ = tracing_subscriber::fmt::try_init();
         let db = init_admin_db("test_create", None).await;
@@ -62,6 +64,9 @@ mod tests {
                 submission.id = created_submission.id;
                 submission.status = Status::InProcess;
                 submission.updated_at = created_submission.updated_at;
+                // Submissions reuse the same icon, so they are assessed as risky
+                submission.risk_score = created_submission.risk_score;
+                submission.risk_reasons = created_submission.risk_reasons.clone();
                 assert_eq!(created_submission, submission);
             }
         }
@@ -85,13 +90,15 @@ mod tests {
                 submission.id = created_submission.id;
                 submission.status = Status::InProcess;
                 submission.updated_at = created_submission.updated_at;
+                // Submissions reuse the same icon, so they are assessed as risky
+                submission.risk_score = created_submission.risk_score;
+                submission.risk_reasons = created_submission.risk_reasons.clone();
                 assert_eq!(created_submission, submission);
             }
         }
     }
 
     #[tokio::test]
-    #[ignore = "needs database to run"]
     async fn create_test_selectors() {
         let _ = tracing_subscriber::fmt::try_init();
         let db = init_admin_db("create_test_selectors", None).await;
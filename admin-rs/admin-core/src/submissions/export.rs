@@ -0,0 +1,174 @@
+use super::{
+    search::{search_submissions, SearchQuery},
+    Error, Submission,
+};
+use crate::client::Client;
+use futures::Stream;
+
+/// Number of submissions encoded into a single chunk of the export stream.
+const CHUNK_SIZE: u64 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Ndjson,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv",
+            Self::Ndjson => "application/x-ndjson",
+        }
+    }
+}
+
+const CSV_HEADER: [&str; 13] = [
+    "id",
+    "chain_id",
+    "status",
+    "token_address",
+    "project_name",
+    "project_website",
+    "project_email",
+    "requester_name",
+    "requester_email",
+    "blockscout_user_email",
+    "admin_comments",
+    "updated_at",
+    "risk_score",
+];
+
+fn csv_record(submission: &Submission) -> [String; 13] {
+    [
+        submission.id.to_string(),
+        submission.chain_id.to_string(),
+        submission.status.as_str().to_string(),
+        submission.token_address.to_string(),
+        submission.project_name.clone().unwrap_or_default(),
+        submission.project_website.clone(),
+        submission.project_email.clone(),
+        submission.requester_name.clone(),
+        submission.requester_email.clone(),
+        submission.blockscout_user_email.clone(),
+        submission.admin_comments.clone().unwrap_or_default(),
+        submission.updated_at.to_string(),
+        submission.risk_score.to_string(),
+    ]
+}
+
+fn encode(
+    format: ExportFormat,
+    submissions: &[Submission],
+    with_header: bool,
+) -> Result<Vec<u8>, Error> {
+    match format {
+        ExportFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(vec![]);
+            if with_header {
+                writer
+                    .write_record(CSV_HEADER)
+                    .map_err(|e| Error::Internal(e.to_string()))?;
+            }
+            for submission in submissions {
+                writer
+                    .write_record(csv_record(submission))
+                    .map_err(|e| Error::Internal(e.to_string()))?;
+            }
+            writer
+                .into_inner()
+                .map_err(|e| Error::Internal(e.to_string()))
+        }
+        ExportFormat::Ndjson => {
+            let mut buffer = vec![];
+            for submission in submissions {
+                serde_json::to_writer(&mut buffer, submission)
+                    .map_err(|e| Error::Internal(e.to_string()))?;
+                buffer.push(b'\n');
+            }
+            Ok(buffer)
+        }
+    }
+}
+
+/// Streams all submissions matched by the query in the query order, encoded in the given format.
+/// Submissions are read from the database page by page as the stream is consumed,
+/// so that large result sets are never loaded into memory at once.
+pub fn export_submissions(
+    client: &Client,
+    query: &SearchQuery,
+    format: ExportFormat,
+) -> impl Stream<Item = Result<Vec<u8>, Error>> + Send + 'static {
+    let client = client.clone();
+    let query = query.clone();
+    async_stream::try_stream! {
+        let mut page_token = None;
+        let mut with_header = true;
+        loop {
+            let (submissions, next_page_token) =
+                search_submissions(&client, &query, CHUNK_SIZE, page_token).await?;
+            // Csv header is returned even if no submissions have been found
+            if !submissions.is_empty() || (with_header && format == ExportFormat::Csv) {
+                yield encode(format, &submissions, with_header)?;
+                with_header = false;
+            }
+            match next_page_token {
+                Some(token) => page_token = Some(token),
+                None => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::mocked_submissions;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn encode_csv() {
+        let mut submissions = mocked_submissions(&[
+            ("user@gmail.com", 1, "Project, with comma"),
+            ("user@gmail.com", 5, "Project"),
+        ]);
+        submissions[0].id = 1;
+        submissions[1].id = 2;
+        submissions[1].admin_comments = Some("Say \"hello\"".into());
+
+        let csv = String::from_utf8(encode(ExportFormat::Csv, &submissions, true).unwrap())
+            .expect("csv should be valid utf-8");
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(3, lines.len(), "invalid number of lines: {csv}");
+        assert_eq!(CSV_HEADER.join(","), lines[0]);
+        assert!(
+            lines[1].starts_with("1,1,in_process,0x1234,\"Project, with comma\",pweb,"),
+            "invalid first record: {}",
+            lines[1]
+        );
+        assert!(
+            lines[2].contains(",\"Say \"\"hello\"\"\","),
+            "invalid second record: {}",
+            lines[2]
+        );
+
+        let csv = encode(ExportFormat::Csv, &submissions[1..], false).unwrap();
+        assert_eq!(1, String::from_utf8(csv).unwrap().lines().count());
+    }
+
+    #[test]
+    fn encode_ndjson() {
+        let submissions = mocked_submissions(&[
+            ("user@gmail.com", 1, "Project 1"),
+            ("user@gmail.com", 5, "Project 2"),
+        ]);
+
+        let ndjson = encode(ExportFormat::Ndjson, &submissions, true).unwrap();
+        let decoded: Vec<Submission> = String::from_utf8(ndjson)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("line should be valid json"))
+            .collect();
+        assert_eq!(submissions, decoded);
+    }
+}
@@ -1,44 +1,20 @@
-use super::{Error, Submission};
+use super::{user::owner_condition, Error, Submission, User};
 use crate::client::Client;
-use sea_orm::{DbBackend, FromQueryResult, JsonValue, Statement};
+use entity::submissions;
+use sea_orm::{prelude::*, QueryOrder};
 
 pub async fn list_submissions(
     client: &Client,
-    user_email: String,
+    user: &User,
     chain_id: i64,
 ) -> Result<Vec<Submission>, Error> {
-    let submissions: Vec<Submission> =
-        JsonValue::find_by_statement(Statement::from_sql_and_values(
-            DbBackend::Postgres,
-            r#"SELECT 
-            s.*,
-            s.status::TEXT,
-            CASE WHEN s.status = 'waiting_for_update' THEN w.admin_comments
-                WHEN s.status = 'rejected' THEN r.reason
-                ELSE null
-            END as admin_comments
-        FROM submissions s
-        LEFT JOIN (
-            SELECT DISTINCT ON (submission_id) id, submission_id, admin_comments 
-            FROM waiting_for_update_submissions
-            ORDER BY submission_id, id DESC
-        ) w
-        ON s.id = w.submission_id
-        LEFT JOIN (
-            SELECT DISTINCT ON (submission_id) id, submission_id, reason 
-            FROM rejected_submissions
-            ORDER BY submission_id, id DESC
-        ) r
-        ON s.id = r.submission_id
-        WHERE s.chain_id = $1 AND s.blockscout_user_email = $2;"#,
-            [chain_id.into(), user_email.into()],
-        ))
+    let models = submissions::Entity::find()
+        .filter(owner_condition(Some(&user.id), &user.email))
+        .filter(submissions::Column::ChainId.eq(chain_id))
+        .order_by_asc(submissions::Column::Id)
         .all(client.db.as_ref())
-        .await?
-        .into_iter()
-        .map(serde_json::from_value)
-        .collect::<Result<_, _>>()
-        .map_err(|e| Error::Internal(e.to_string()))?;
+        .await?;
+    let submissions = Submission::try_from_models(client.db.as_ref(), models).await?;
 
     Ok(submissions)
 }
@@ -54,13 +30,13 @@ mod tests {
     use pretty_assertions::assert_eq;
 
     #[tokio::test]
-    #[ignore = "needs database to run"]
     async fn test_list() {
         let _ = tracing_subscriber::fmt::try_init();
         let db = init_admin_db("test_list", None).await;
         let client = Client::new(db, Selectors::default());
 
         let user_email = "user1@gmail.com";
+        let user = User::new("1", user_email);
         let chain_id = 1;
         let expected_submissions = insert_mocked_submissions(
             &client.db,
@@ -70,7 +46,7 @@ mod tests {
             ],
         )
         .await;
-        let actual_submissions = list_submissions(&client, user_email.to_string(), chain_id)
+        let actual_submissions = list_submissions(&client, &user, chain_id)
             .await
             .expect("failed to list subsmissions");
         assert_eq!(actual_submissions, expected_submissions);
@@ -84,18 +60,18 @@ mod tests {
             ],
         )
         .await;
-        let actual_submissions = list_submissions(&client, user_email.to_string(), chain_id)
+        let actual_submissions = list_submissions(&client, &user, chain_id)
             .await
             .expect("failed to list subsmissions");
         assert_eq!(actual_submissions, expected_submissions);
 
         let actual_submissions =
-            list_submissions(&client, "RANDOM_USER_EMAIL".to_string(), chain_id)
+            list_submissions(&client, &User::new("2", "RANDOM_USER_EMAIL"), chain_id)
                 .await
                 .expect("failed to list subsmissions");
         assert!(actual_submissions.is_empty());
 
-        let actual_submissions = list_submissions(&client, user_email.to_string(), 123123123123)
+        let actual_submissions = list_submissions(&client, &user, 123123123123)
             .await
             .expect("failed to list subsmissions");
         assert!(actual_submissions.is_empty());
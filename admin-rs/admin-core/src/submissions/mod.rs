@@ -1,11 +1,31 @@
 mod create;
+mod export;
 mod get;
 mod list;
+mod revisions;
+mod risk;
+mod schema;
+mod search;
 mod types;
 mod update;
+mod user;
+mod user_data;
 
 pub use create::create_submission;
+pub use export::{export_submissions, ExportFormat};
 pub use get::get_submission;
 pub use list::list_submissions;
-pub use types::{Error, Selectors, Status, Submission};
-pub use update::update_submission;
+pub use revisions::{diff_revisions, list_revisions, FieldChange, Revision, RevisionData};
+pub use risk::{assess_submission, RiskAssessment, RiskReason, MAX_RISK_SCORE};
+pub use schema::{FieldDescriptor, FieldKind};
+pub use search::{
+    search_submissions, PageToken, SearchOrder, SearchQuery, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE,
+};
+pub use service_common::user::{DataSubject, User, ANONYMIZED_EMAIL};
+pub use types::{Error, LocalizedDescription, Selectors, Status, Submission};
+pub use update::{get_updatable_submission, update_submission};
+pub use user::{backfill_user_ids, idempotency_caller, reconcile_user};
+pub use user_data::{
+    erase_user_data, export_user_data, ReviewComment, ReviewCommentKind, SubmissionData, UserData,
+    ANONYMIZED_NAME,
+};
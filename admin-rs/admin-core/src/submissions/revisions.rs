@@ -0,0 +1,320 @@
+use super::{Error, LocalizedDescription, ANONYMIZED_EMAIL, ANONYMIZED_NAME};
+use crate::client::Client;
+use chrono::NaiveDateTime;
+use entity::{submission_revisions, submissions};
+use sea_orm::{prelude::*, ConnectionTrait, QueryOrder, Set};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Values of user editable submission fields by field names.
+/// Fields without values are omitted.
+pub type RevisionData = BTreeMap<String, String>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Revision {
+    /// Revisions are numbered from 1. The last revision is the current submission state.
+    pub revision: i32,
+    pub created_at: NaiveDateTime,
+    pub data: RevisionData,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    pub field: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+fn revision_data(model: &submissions::Model) -> Result<RevisionData, Error> {
+    let mut data: RevisionData = [
+        ("token_address", Some(&model.token_address)),
+        ("requester_name", Some(&model.requester_name)),
+        ("requester_email", Some(&model.requester_email)),
+        ("project_name", model.project_name.as_ref()),
+        ("project_website", Some(&model.project_website)),
+        ("project_email", Some(&model.project_email)),
+        ("icon_url", Some(&model.icon_url)),
+        ("project_description", Some(&model.project_description)),
+        ("project_sector", model.project_sector.as_ref()),
+        ("comment", model.comment.as_ref()),
+        ("docs", model.docs.as_ref()),
+        ("github", model.github.as_ref()),
+        ("telegram", model.telegram.as_ref()),
+        ("linkedin", model.linkedin.as_ref()),
+        ("discord", model.discord.as_ref()),
+        ("slack", model.slack.as_ref()),
+        ("twitter", model.twitter.as_ref()),
+        ("open_sea", model.open_sea.as_ref()),
+        ("facebook", model.facebook.as_ref()),
+        ("medium", model.medium.as_ref()),
+        ("reddit", model.reddit.as_ref()),
+        ("support", model.support.as_ref()),
+        (
+            "coin_market_cap_ticker",
+            model.coin_market_cap_ticker.as_ref(),
+        ),
+        ("coin_gecko_ticker", model.coin_gecko_ticker.as_ref()),
+        ("defi_llama_ticker", model.defi_llama_ticker.as_ref()),
+    ]
+    .into_iter()
+    .filter_map(|(field, value)| value.map(|value| (field.to_string(), value.clone())))
+    .collect();
+
+    // Translations are stored per language, so that changes of a single one could be seen
+    let localized_descriptions: Vec<LocalizedDescription> =
+        serde_json::from_value(model.localized_descriptions.clone())
+            .map_err(|e| Error::Internal(e.to_string()))?;
+    for localized in localized_descriptions {
+        let prefix = format!("localized_descriptions.{}", localized.language);
+        if let Some(project_name) = localized.project_name {
+            data.insert(format!("{prefix}.project_name"), project_name);
+        }
+        data.insert(
+            format!("{prefix}.project_description"),
+            localized.project_description,
+        );
+    }
+    Ok(data)
+}
+
+/// Saves the submission state as a new revision. Is called before the submission is updated.
+pub(crate) async fn save_revision<C: ConnectionTrait>(
+    db: &C,
+    model: &submissions::Model,
+) -> Result<(), Error> {
+    let last_revision = submission_revisions::Entity::find()
+        .filter(submission_revisions::Column::SubmissionId.eq(model.id))
+        .order_by_desc(submission_revisions::Column::Revision)
+        .one(db)
+        .await?
+        .map(|revision| revision.revision)
+        .unwrap_or_default();
+    let data =
+        serde_json::to_value(revision_data(model)?).map_err(|e| Error::Internal(e.to_string()))?;
+    submission_revisions::ActiveModel {
+        submission_id: Set(model.id),
+        revision: Set(last_revision + 1),
+        data: Set(data),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    Ok(())
+}
+
+/// Replaces personal data stored in the revisions of the submissions with the anonymized values.
+pub(crate) async fn anonymize_revisions<C: ConnectionTrait>(
+    db: &C,
+    submission_ids: Vec<i64>,
+) -> Result<(), Error> {
+    let revisions = submission_revisions::Entity::find()
+        .filter(submission_revisions::Column::SubmissionId.is_in(submission_ids))
+        .all(db)
+        .await?;
+    for revision in revisions {
+        let mut data: RevisionData = serde_json::from_value(revision.data.clone())
+            .map_err(|e| Error::Internal(e.to_string()))?;
+        data.insert("requester_name".into(), ANONYMIZED_NAME.into());
+        data.insert("requester_email".into(), ANONYMIZED_EMAIL.into());
+        let data = serde_json::to_value(data).map_err(|e| Error::Internal(e.to_string()))?;
+
+        let mut revision: submission_revisions::ActiveModel = revision.into();
+        revision.data = Set(data);
+        revision.update(db).await?;
+    }
+    Ok(())
+}
+
+/// Returns all revisions of the submission ordered by the revision number.
+/// The current submission state is returned as the last revision.
+pub async fn list_revisions(client: &Client, submission_id: i64) -> Result<Vec<Revision>, Error> {
+    let submission = submissions::Entity::find_by_id(submission_id)
+        .one(client.db.as_ref())
+        .await?
+        .ok_or(Error::NotFound(submission_id))?;
+    let mut revisions = submission_revisions::Entity::find()
+        .filter(submission_revisions::Column::SubmissionId.eq(submission_id))
+        .order_by_asc(submission_revisions::Column::Revision)
+        .all(client.db.as_ref())
+        .await?
+        .into_iter()
+        .map(|model| {
+            Ok(Revision {
+                revision: model.revision,
+                created_at: model.created_at,
+                data: serde_json::from_value(model.data)
+                    .map_err(|e| Error::Internal(e.to_string()))?,
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    revisions.push(Revision {
+        revision: revisions.last().map(|r| r.revision).unwrap_or_default() + 1,
+        created_at: submission.updated_at,
+        data: revision_data(&submission)?,
+    });
+    Ok(revisions)
+}
+
+/// Returns fields which values differ between the two revisions of the submission.
+pub async fn diff_revisions(
+    client: &Client,
+    submission_id: i64,
+    old_revision: i32,
+    new_revision: i32,
+) -> Result<Vec<FieldChange>, Error> {
+    let revisions = list_revisions(client, submission_id).await?;
+    let find = |revision: i32| {
+        revisions
+            .iter()
+            .find(|r| r.revision == revision)
+            .ok_or(Error::RevisionNotFound {
+                submission_id,
+                revision,
+            })
+    };
+    Ok(diff(&find(old_revision)?.data, &find(new_revision)?.data))
+}
+
+fn diff(old: &RevisionData, new: &RevisionData) -> Vec<FieldChange> {
+    let fields: BTreeSet<_> = old.keys().chain(new.keys()).collect();
+    fields
+        .into_iter()
+        .filter_map(|field| {
+            let old_value = old.get(field);
+            let new_value = new.get(field);
+            (old_value != new_value).then(|| FieldChange {
+                field: field.clone(),
+                old_value: old_value.cloned(),
+                new_value: new_value.cloned(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        submissions::{update_submission, Selectors},
+        test_helpers::{init_admin_db, insert_mocked_submissions},
+    };
+    use entity::{sea_orm_active_enums::SubmissionStatus, waiting_for_update_submissions};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn diff_works() {
+        let old = RevisionData::from([
+            ("project_name".to_string(), "old".to_string()),
+            ("docs".to_string(), "docs".to_string()),
+            ("github".to_string(), "github".to_string()),
+        ]);
+        let new = RevisionData::from([
+            ("project_name".to_string(), "new".to_string()),
+            ("docs".to_string(), "docs".to_string()),
+            ("slack".to_string(), "slack".to_string()),
+        ]);
+        assert_eq!(
+            vec![
+                FieldChange {
+                    field: "github".into(),
+                    old_value: Some("github".into()),
+                    new_value: None,
+                },
+                FieldChange {
+                    field: "project_name".into(),
+                    old_value: Some("old".into()),
+                    new_value: Some("new".into()),
+                },
+                FieldChange {
+                    field: "slack".into(),
+                    old_value: None,
+                    new_value: Some("slack".into()),
+                },
+            ],
+            diff(&old, &new)
+        );
+        assert!(diff(&old, &old).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_revisions() {
+        let _ = tracing_subscriber::fmt::try_init();
+        let db = init_admin_db("test_revisions", None).await;
+        let client = Client::new(db, Selectors::default());
+
+        let mut submission =
+            insert_mocked_submissions(&client.db, &[("user@gmail.com", 1, "old name")])
+                .await
+                .remove(0);
+        let revisions = list_revisions(&client, submission.id)
+            .await
+            .expect("failed to list revisions");
+        assert_eq!(1, revisions.len());
+        assert_eq!(1, revisions[0].revision);
+
+        submissions::Entity::update(submissions::ActiveModel {
+            id: Set(submission.id),
+            status: Set(SubmissionStatus::WaitingForUpdate),
+            ..Default::default()
+        })
+        .exec(client.db.as_ref())
+        .await
+        .expect("failed to update status");
+        waiting_for_update_submissions::ActiveModel {
+            submission_id: Set(submission.id),
+            admin_comments: Set("change the name".into()),
+            ..Default::default()
+        }
+        .insert(client.db.as_ref())
+        .await
+        .expect("failed to insert waiting for update submission");
+
+        submission.project_name = Some("new name".into());
+        submission.localized_descriptions = vec![LocalizedDescription {
+            language: "de".into(),
+            project_name: None,
+            project_description: "Beschreibung".into(),
+        }];
+        submission.blockscout_user_id = Some("1".into());
+        update_submission(&client, submission.clone(), None)
+            .await
+            .expect("failed to update submission");
+
+        let revisions = list_revisions(&client, submission.id)
+            .await
+            .expect("failed to list revisions");
+        assert_eq!(
+            vec![1, 2],
+            revisions.iter().map(|r| r.revision).collect::<Vec<_>>()
+        );
+        let changes = diff_revisions(&client, submission.id, 1, 2)
+            .await
+            .expect("failed to diff revisions");
+        assert_eq!(
+            vec![
+                FieldChange {
+                    field: "localized_descriptions.de.project_description".into(),
+                    old_value: None,
+                    new_value: Some("Beschreibung".into()),
+                },
+                FieldChange {
+                    field: "project_name".into(),
+                    old_value: Some("old name".into()),
+                    new_value: Some("new name".into()),
+                },
+            ],
+            changes
+        );
+
+        let err = diff_revisions(&client, submission.id, 1, 3)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            Error::RevisionNotFound {
+                submission_id: submission.id,
+                revision: 3
+            },
+            err
+        );
+    }
+}
@@ -0,0 +1,367 @@
+use super::{Error, Submission};
+use entity::submissions;
+use sea_orm::{
+    prelude::*, sea_query::Condition, ConnectionTrait, DbBackend, FromQueryResult, QueryOrder,
+    QuerySelect, Set, Statement,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use url::Url;
+
+/// Descriptions with similarity (as defined by `pg_trgm`) not less than the value
+/// are considered near-duplicates.
+const DUPLICATE_DESCRIPTION_SIMILARITY: f64 = 0.8;
+/// Short descriptions are skipped, as they are often similar by accident.
+const MIN_DESCRIPTION_LENGTH: usize = 50;
+/// Maximum number of submissions referenced by a single reason.
+const MAX_REFERENCED_SUBMISSIONS: u64 = 10;
+pub const MAX_RISK_SCORE: i32 = 100;
+
+const DISPOSABLE_EMAIL_DOMAINS: &[&str] = &[
+    "10minutemail.com",
+    "discard.email",
+    "dispostable.com",
+    "emailondeck.com",
+    "fakeinbox.com",
+    "getnada.com",
+    "guerrillamail.com",
+    "mailinator.com",
+    "maildrop.cc",
+    "mailnesia.com",
+    "mintemail.com",
+    "moakt.com",
+    "sharklasers.com",
+    "spamgourmet.com",
+    "temp-mail.org",
+    "tempmail.com",
+    "tempr.email",
+    "throwawaymail.com",
+    "trashmail.com",
+    "yopmail.com",
+];
+
+/// A reason the submission is considered risky by.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RiskReason {
+    DuplicateDescription {
+        submission_id: i64,
+        similarity_percent: u8,
+    },
+    ReusedIconUrl {
+        submission_ids: Vec<i64>,
+    },
+    DisposableEmailDomain {
+        domain: String,
+    },
+    MismatchedEmailDomain {
+        website_domain: String,
+        email_domain: String,
+    },
+}
+
+impl RiskReason {
+    fn weight(&self) -> i32 {
+        match self {
+            Self::DuplicateDescription { .. } => 40,
+            Self::ReusedIconUrl { .. } => 30,
+            Self::DisposableEmailDomain { .. } => 50,
+            Self::MismatchedEmailDomain { .. } => 15,
+        }
+    }
+}
+
+impl fmt::Display for RiskReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateDescription {
+                submission_id,
+                similarity_percent,
+            } => write!(
+                f,
+                "project description is {similarity_percent}% similar to submission {submission_id}"
+            ),
+            Self::ReusedIconUrl { submission_ids } => {
+                let ids: Vec<_> = submission_ids.iter().map(i64::to_string).collect();
+                write!(f, "icon url is used by submissions {}", ids.join(", "))
+            }
+            Self::DisposableEmailDomain { domain } => {
+                write!(f, "project email uses disposable domain {domain}")
+            }
+            Self::MismatchedEmailDomain {
+                website_domain,
+                email_domain,
+            } => write!(
+                f,
+                "project email domain {email_domain} does not match website domain {website_domain}"
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RiskAssessment {
+    /// Sum of reason weights, capped by [`MAX_RISK_SCORE`].
+    pub score: i32,
+    pub reasons: Vec<RiskReason>,
+}
+
+impl RiskAssessment {
+    fn new(reasons: Vec<RiskReason>) -> Self {
+        let score = reasons
+            .iter()
+            .map(RiskReason::weight)
+            .sum::<i32>()
+            .min(MAX_RISK_SCORE);
+        Self { score, reasons }
+    }
+
+    pub(crate) fn apply(self, model: &mut submissions::ActiveModel) -> Result<(), Error> {
+        model.risk_score = Set(self.score);
+        model.risk_reasons =
+            Set(serde_json::to_value(self.reasons).map_err(|e| Error::Internal(e.to_string()))?);
+        Ok(())
+    }
+}
+
+/// Evaluates spam and duplicate-content heuristics for the submission.
+/// Other submissions for the same token are not taken into account,
+/// as resubmissions of the same project are expected to be similar.
+pub async fn assess_submission<C: ConnectionTrait>(
+    db: &C,
+    submission: &Submission,
+) -> Result<RiskAssessment, Error> {
+    let mut reasons = vec![];
+    if let Some(reason) = find_duplicate_description(db, submission).await? {
+        reasons.push(reason);
+    }
+    if let Some(reason) = find_reused_icon_url(db, submission).await? {
+        reasons.push(reason);
+    }
+    reasons.extend(check_email_domain(submission));
+    Ok(RiskAssessment::new(reasons))
+}
+
+#[derive(FromQueryResult)]
+struct SimilarSubmission {
+    id: i64,
+    similarity: f64,
+}
+
+async fn find_duplicate_description<C: ConnectionTrait>(
+    db: &C,
+    submission: &Submission,
+) -> Result<Option<RiskReason>, Error> {
+    // Similarity is provided by `pg_trgm` extension, so other backends skip the check
+    if db.get_database_backend() != DbBackend::Postgres {
+        return Ok(None);
+    }
+    let description = submission.project_description.trim();
+    if description.chars().count() < MIN_DESCRIPTION_LENGTH {
+        return Ok(None);
+    }
+    // `%` operator is used so that the trigram index could be applied
+    let similar = SimilarSubmission::find_by_statement(Statement::from_sql_and_values(
+        DbBackend::Postgres,
+        r#"SELECT id, similarity(project_description, $1)::FLOAT8 AS similarity
+        FROM submissions
+        WHERE project_description % $1
+            AND id <> $2
+            AND NOT (chain_id = $3 AND token_address = $4)
+        ORDER BY similarity DESC, id
+        LIMIT 1;"#,
+        [
+            description.into(),
+            submission.id.into(),
+            submission.chain_id.into(),
+            submission.token_address.to_string().into(),
+        ],
+    ))
+    .one(db)
+    .await?;
+
+    Ok(similar
+        .filter(|similar| similar.similarity >= DUPLICATE_DESCRIPTION_SIMILARITY)
+        .map(|similar| RiskReason::DuplicateDescription {
+            submission_id: similar.id,
+            similarity_percent: (similar.similarity * 100.0).round().min(100.0) as u8,
+        }))
+}
+
+async fn find_reused_icon_url<C: ConnectionTrait>(
+    db: &C,
+    submission: &Submission,
+) -> Result<Option<RiskReason>, Error> {
+    let icon_url = submission.icon_url.trim();
+    if icon_url.is_empty() {
+        return Ok(None);
+    }
+    let submission_ids: Vec<i64> = submissions::Entity::find()
+        .select_only()
+        .column(submissions::Column::Id)
+        .filter(submissions::Column::IconUrl.eq(icon_url))
+        .filter(submissions::Column::Id.ne(submission.id))
+        .filter(
+            Condition::all()
+                .add(submissions::Column::ChainId.eq(submission.chain_id))
+                .add(submissions::Column::TokenAddress.eq(submission.token_address.to_string()))
+                .not(),
+        )
+        .order_by_asc(submissions::Column::Id)
+        .limit(MAX_REFERENCED_SUBMISSIONS)
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    Ok((!submission_ids.is_empty()).then_some(RiskReason::ReusedIconUrl { submission_ids }))
+}
+
+fn check_email_domain(submission: &Submission) -> Option<RiskReason> {
+    let email_domain = submission
+        .project_email
+        .trim()
+        .rsplit_once('@')
+        .map(|(_, domain)| domain.trim_end_matches('.').to_lowercase())
+        .filter(|domain| !domain.is_empty())?;
+    let email_base_domain = base_domain(&email_domain);
+    if DISPOSABLE_EMAIL_DOMAINS.contains(&email_base_domain.as_str()) {
+        return Some(RiskReason::DisposableEmailDomain {
+            domain: email_domain,
+        });
+    }
+
+    let website_domain = website_domain(&submission.project_website)?;
+    let website_base_domain = base_domain(&website_domain);
+    (website_base_domain != email_base_domain).then_some(RiskReason::MismatchedEmailDomain {
+        website_domain: website_base_domain,
+        email_domain: email_base_domain,
+    })
+}
+
+fn website_domain(website: &str) -> Option<String> {
+    let website = website.trim();
+    let url = if website.contains("://") {
+        Url::parse(website)
+    } else {
+        Url::parse(&format!("https://{website}"))
+    }
+    .ok()?;
+    url.domain()
+        .map(|domain| domain.trim_end_matches('.').to_lowercase())
+        .filter(|domain| domain.contains('.'))
+}
+
+/// Returns the last two labels of the domain. Does not respect multi-label
+/// public suffixes (e.g. `co.uk`), which results only in missed mismatches.
+fn base_domain(domain: &str) -> String {
+    let labels: Vec<_> = domain.split('.').collect();
+    labels[labels.len().saturating_sub(2)..].join(".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        client::Client,
+        submissions::{create_submission, Selectors},
+        test_helpers::{init_admin_db, mocked_submissions},
+    };
+    use pretty_assertions::assert_eq;
+
+    fn submission(project_website: &str, project_email: &str) -> Submission {
+        let mut submission = mocked_submissions(&[("user@gmail.com", 1, "Project")]).remove(0);
+        submission.project_website = project_website.into();
+        submission.project_email = project_email.into();
+        submission
+    }
+
+    #[test]
+    fn check_email_domain_works() {
+        assert_eq!(
+            None,
+            check_email_domain(&submission("https://www.example.com/", "info@example.com"))
+        );
+        assert_eq!(
+            None,
+            check_email_domain(&submission("app.example.com", "team@mail.example.com"))
+        );
+        assert_eq!(
+            Some(RiskReason::DisposableEmailDomain {
+                domain: "mailinator.com".into()
+            }),
+            check_email_domain(&submission("example.com", "info@Mailinator.com"))
+        );
+        assert_eq!(
+            Some(RiskReason::MismatchedEmailDomain {
+                website_domain: "example.com".into(),
+                email_domain: "gmail.com".into(),
+            }),
+            check_email_domain(&submission("https://example.com", "project@gmail.com"))
+        );
+        // Invalid values are not reported, as they are validated elsewhere
+        assert_eq!(None, check_email_domain(&submission("pweb", "pemail")));
+    }
+
+    #[test]
+    fn risk_score_is_capped() {
+        let assessment = RiskAssessment::new(vec![
+            RiskReason::DisposableEmailDomain {
+                domain: "yopmail.com".into(),
+            },
+            RiskReason::DuplicateDescription {
+                submission_id: 1,
+                similarity_percent: 95,
+            },
+            RiskReason::ReusedIconUrl {
+                submission_ids: vec![1],
+            },
+        ]);
+        assert_eq!(MAX_RISK_SCORE, assessment.score);
+        assert_eq!(3, assessment.reasons.len());
+    }
+
+    #[tokio::test]
+    async fn test_assess_submission() {
+        let _ = tracing_subscriber::fmt::try_init();
+        let db = init_admin_db("test_assess_submission", None).await;
+        let client = Client::new(db, Selectors::default());
+        let description = "The best decentralized exchange protocol with deep liquidity";
+
+        let mut original = submission("https://example.com", "info@example.com");
+        original.project_description = description.into();
+        original.icon_url = "https://example.com/icon.png".into();
+        let original = create_submission(&client, original)
+            .await
+            .expect("failed to create submission");
+        assert_eq!(0, original.risk_score);
+        assert!(original.risk_reasons.is_empty());
+
+        let mut copy = submission("https://another.com", "info@another.com");
+        copy.token_address = "0x5678".parse().unwrap();
+        copy.project_description = format!("{description}!");
+        copy.icon_url = original.icon_url.clone();
+        let copy = create_submission(&client, copy)
+            .await
+            .expect("failed to create submission");
+
+        // Descriptions similarity is checked on Postgres only
+        if client.db.get_database_backend() != DbBackend::Postgres {
+            assert_eq!(30, copy.risk_score);
+            assert_eq!(
+                vec![RiskReason::ReusedIconUrl {
+                    submission_ids: vec![original.id]
+                }],
+                copy.risk_reasons
+            );
+            return;
+        }
+        assert_eq!(70, copy.risk_score);
+        assert!(matches!(
+            copy.risk_reasons.as_slice(),
+            [
+                RiskReason::DuplicateDescription { submission_id, .. },
+                RiskReason::ReusedIconUrl { submission_ids },
+            ] if *submission_id == original.id && submission_ids == &vec![original.id]
+        ));
+    }
+}
@@ -0,0 +1,448 @@
+use super::{Error, Selectors, Submission};
+
+/// How the field value is expected to be entered. Only kinds which are enforced
+/// by [`Selectors::validate_submission`] (or by parsing, for addresses) are reported,
+/// so urls and emails are free text limited by [`FieldDescriptor::max_length`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    Address,
+    Text,
+    /// Value must be one of [`FieldDescriptor::allowed_values`].
+    Selector,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDescriptor {
+    /// Name of the user editable [`Submission`] field.
+    pub name: &'static str,
+    pub kind: FieldKind,
+    /// Required fields must not be empty or consist of whitespaces only.
+    pub required: bool,
+    /// Maximum number of characters in the value.
+    pub max_length: usize,
+    /// Values allowed for [`FieldKind::Selector`] fields. Empty for other kinds.
+    pub allowed_values: Vec<String>,
+}
+
+struct FieldRule {
+    name: &'static str,
+    kind: FieldKind,
+    required: bool,
+    max_length: usize,
+    value: fn(&Submission) -> Option<&str>,
+}
+
+const NAME_MAX_LENGTH: usize = 256;
+const EMAIL_MAX_LENGTH: usize = 254;
+const URL_MAX_LENGTH: usize = 2048;
+const TEXT_MAX_LENGTH: usize = 2048;
+const TICKER_MAX_LENGTH: usize = 128;
+const LANGUAGE_TAG_MAX_LENGTH: usize = 35;
+const LOCALIZED_DESCRIPTIONS_MAX_COUNT: usize = 32;
+
+/// Validation rules of user editable submission fields in the order fields are displayed in.
+/// Token address is validated during parsing, so its value is not checked here.
+const FIELD_RULES: &[FieldRule] = &[
+    FieldRule {
+        name: "token_address",
+        kind: FieldKind::Address,
+        required: true,
+        max_length: 42,
+        value: |_| None,
+    },
+    FieldRule {
+        name: "requester_name",
+        kind: FieldKind::Text,
+        required: true,
+        max_length: NAME_MAX_LENGTH,
+        value: |s| Some(s.requester_name.as_str()),
+    },
+    FieldRule {
+        name: "requester_email",
+        kind: FieldKind::Text,
+        required: true,
+        max_length: EMAIL_MAX_LENGTH,
+        value: |s| Some(s.requester_email.as_str()),
+    },
+    FieldRule {
+        name: "project_name",
+        kind: FieldKind::Text,
+        required: false,
+        max_length: NAME_MAX_LENGTH,
+        value: |s| s.project_name.as_deref(),
+    },
+    FieldRule {
+        name: "project_website",
+        kind: FieldKind::Text,
+        required: true,
+        max_length: URL_MAX_LENGTH,
+        value: |s| Some(s.project_website.as_str()),
+    },
+    FieldRule {
+        name: "project_email",
+        kind: FieldKind::Text,
+        required: true,
+        max_length: EMAIL_MAX_LENGTH,
+        value: |s| Some(s.project_email.as_str()),
+    },
+    FieldRule {
+        name: "icon_url",
+        kind: FieldKind::Text,
+        required: true,
+        max_length: URL_MAX_LENGTH,
+        value: |s| Some(s.icon_url.as_str()),
+    },
+    FieldRule {
+        name: "project_description",
+        kind: FieldKind::Text,
+        required: true,
+        max_length: TEXT_MAX_LENGTH,
+        value: |s| Some(s.project_description.as_str()),
+    },
+    FieldRule {
+        name: "project_sector",
+        kind: FieldKind::Selector,
+        required: false,
+        max_length: NAME_MAX_LENGTH,
+        value: |s| s.project_sector.as_deref(),
+    },
+    FieldRule {
+        name: "comment",
+        kind: FieldKind::Text,
+        required: false,
+        max_length: TEXT_MAX_LENGTH,
+        value: |s| s.comment.as_deref(),
+    },
+    FieldRule {
+        name: "docs",
+        kind: FieldKind::Text,
+        required: false,
+        max_length: URL_MAX_LENGTH,
+        value: |s| s.docs.as_deref(),
+    },
+    FieldRule {
+        name: "github",
+        kind: FieldKind::Text,
+        required: false,
+        max_length: URL_MAX_LENGTH,
+        value: |s| s.github.as_deref(),
+    },
+    FieldRule {
+        name: "telegram",
+        kind: FieldKind::Text,
+        required: false,
+        max_length: URL_MAX_LENGTH,
+        value: |s| s.telegram.as_deref(),
+    },
+    FieldRule {
+        name: "linkedin",
+        kind: FieldKind::Text,
+        required: false,
+        max_length: URL_MAX_LENGTH,
+        value: |s| s.linkedin.as_deref(),
+    },
+    FieldRule {
+        name: "discord",
+        kind: FieldKind::Text,
+        required: false,
+        max_length: URL_MAX_LENGTH,
+        value: |s| s.discord.as_deref(),
+    },
+    FieldRule {
+        name: "slack",
+        kind: FieldKind::Text,
+        required: false,
+        max_length: URL_MAX_LENGTH,
+        value: |s| s.slack.as_deref(),
+    },
+    FieldRule {
+        name: "twitter",
+        kind: FieldKind::Text,
+        required: false,
+        max_length: URL_MAX_LENGTH,
+        value: |s| s.twitter.as_deref(),
+    },
+    FieldRule {
+        name: "open_sea",
+        kind: FieldKind::Text,
+        required: false,
+        max_length: URL_MAX_LENGTH,
+        value: |s| s.open_sea.as_deref(),
+    },
+    FieldRule {
+        name: "facebook",
+        kind: FieldKind::Text,
+        required: false,
+        max_length: URL_MAX_LENGTH,
+        value: |s| s.facebook.as_deref(),
+    },
+    FieldRule {
+        name: "medium",
+        kind: FieldKind::Text,
+        required: false,
+        max_length: URL_MAX_LENGTH,
+        value: |s| s.medium.as_deref(),
+    },
+    FieldRule {
+        name: "reddit",
+        kind: FieldKind::Text,
+        required: false,
+        max_length: URL_MAX_LENGTH,
+        value: |s| s.reddit.as_deref(),
+    },
+    FieldRule {
+        name: "support",
+        kind: FieldKind::Text,
+        required: false,
+        max_length: URL_MAX_LENGTH,
+        value: |s| s.support.as_deref(),
+    },
+    FieldRule {
+        name: "coin_market_cap_ticker",
+        kind: FieldKind::Text,
+        required: false,
+        max_length: TICKER_MAX_LENGTH,
+        value: |s| s.coin_market_cap_ticker.as_deref(),
+    },
+    FieldRule {
+        name: "coin_gecko_ticker",
+        kind: FieldKind::Text,
+        required: false,
+        max_length: TICKER_MAX_LENGTH,
+        value: |s| s.coin_gecko_ticker.as_deref(),
+    },
+    FieldRule {
+        name: "defi_llama_ticker",
+        kind: FieldKind::Text,
+        required: false,
+        max_length: TICKER_MAX_LENGTH,
+        value: |s| s.defi_llama_ticker.as_deref(),
+    },
+];
+
+impl Selectors {
+    fn allowed_values(&self, field: &str) -> Option<&[String]> {
+        match field {
+            "project_sector" => Some(&self.project_sectors),
+            _ => None,
+        }
+    }
+
+    /// Returns descriptors of user editable submission fields,
+    /// built from the same rules [`Selectors::validate_submission`] enforces.
+    pub fn submission_schema(&self) -> Vec<FieldDescriptor> {
+        FIELD_RULES
+            .iter()
+            .map(|rule| FieldDescriptor {
+                name: rule.name,
+                kind: rule.kind,
+                required: rule.required,
+                max_length: rule.max_length,
+                allowed_values: self
+                    .allowed_values(rule.name)
+                    .map(<[String]>::to_vec)
+                    .unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    pub fn validate_submission(&self, submission: &Submission) -> Result<(), Error> {
+        for rule in FIELD_RULES {
+            let value = (rule.value)(submission);
+            if rule.required && value.is_some_and(|value| value.trim().is_empty()) {
+                return Err(Error::InvalidField {
+                    field: rule.name.into(),
+                    reason: "value is required".into(),
+                });
+            }
+            let value = match value {
+                Some(value) => value,
+                None => continue,
+            };
+            if value.chars().count() > rule.max_length {
+                return Err(Error::InvalidField {
+                    field: rule.name.into(),
+                    reason: format!("value exceeds {} characters", rule.max_length),
+                });
+            }
+            if let Some(allowed_values) = self.allowed_values(rule.name) {
+                if !allowed_values.iter().any(|allowed| allowed == value) {
+                    return Err(Error::InvalidSelector {
+                        selector: rule.name.into(),
+                        value: value.into(),
+                    });
+                }
+            }
+        }
+        validate_localized_descriptions(submission)
+    }
+}
+
+fn validate_localized_descriptions(submission: &Submission) -> Result<(), Error> {
+    let invalid = |field: String, reason: String| Error::InvalidField { field, reason };
+    if submission.localized_descriptions.len() > LOCALIZED_DESCRIPTIONS_MAX_COUNT {
+        return Err(invalid(
+            "localized_descriptions".into(),
+            format!("at most {LOCALIZED_DESCRIPTIONS_MAX_COUNT} languages are allowed"),
+        ));
+    }
+    for (i, localized) in submission.localized_descriptions.iter().enumerate() {
+        let field = |name: &str| format!("localized_descriptions[{i}].{name}");
+        if !is_language_tag(&localized.language) {
+            return Err(invalid(
+                field("language"),
+                format!("{} is not a valid BCP-47 language tag", localized.language),
+            ));
+        }
+        // Tags are case insensitive
+        if submission.localized_descriptions[..i]
+            .iter()
+            .any(|other| other.language.eq_ignore_ascii_case(&localized.language))
+        {
+            return Err(invalid(
+                field("language"),
+                format!("{} is specified more than once", localized.language),
+            ));
+        }
+        if localized.project_description.trim().is_empty() {
+            return Err(invalid(
+                field("project_description"),
+                "value is required".into(),
+            ));
+        }
+        if localized.project_description.chars().count() > TEXT_MAX_LENGTH {
+            return Err(invalid(
+                field("project_description"),
+                format!("value exceeds {TEXT_MAX_LENGTH} characters"),
+            ));
+        }
+        if let Some(project_name) = &localized.project_name {
+            if project_name.chars().count() > NAME_MAX_LENGTH {
+                return Err(invalid(
+                    field("project_name"),
+                    format!("value exceeds {NAME_MAX_LENGTH} characters"),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the tag is a well-formed BCP-47 language tag: 2-3 or 5-8 letter
+/// language subtag followed by alphanumeric subtags of up to 8 characters,
+/// e.g. `en`, `pt-BR` or `zh-Hant-TW`. Subtags are not checked against the registry.
+fn is_language_tag(tag: &str) -> bool {
+    let mut subtags = tag.split('-');
+    let language = subtags.next().unwrap_or_default();
+    tag.len() <= LANGUAGE_TAG_MAX_LENGTH
+        && matches!(language.len(), 2..=3 | 5..=8)
+        && language.chars().all(|c| c.is_ascii_alphabetic())
+        && subtags.all(|subtag| {
+            (1..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{submissions::LocalizedDescription, test_helpers::mocked_submissions};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn schema_contains_selector_values() {
+        let selectors = Selectors::new(["DeFi", "NFT"]);
+        let schema = selectors.submission_schema();
+        assert_eq!(FIELD_RULES.len(), schema.len());
+
+        let project_sector = schema
+            .iter()
+            .find(|field| field.name == "project_sector")
+            .expect("project sector should be in the schema");
+        assert_eq!(FieldKind::Selector, project_sector.kind);
+        assert_eq!(vec!["DeFi", "NFT"], project_sector.allowed_values);
+        assert!(schema
+            .iter()
+            .filter(|field| field.kind != FieldKind::Selector)
+            .all(|field| field.allowed_values.is_empty()));
+
+        // Formats of urls are not validated, so they are reported as text
+        let project_website = schema
+            .iter()
+            .find(|field| field.name == "project_website")
+            .expect("project website should be in the schema");
+        assert_eq!(FieldKind::Text, project_website.kind);
+        assert_eq!(URL_MAX_LENGTH, project_website.max_length);
+    }
+
+    #[test]
+    fn validate_submission_works() {
+        let selectors = Selectors::new(["DeFi"]);
+        let submission = mocked_submissions(&[("user@gmail.com", 1, "Project")]).remove(0);
+        selectors
+            .validate_submission(&submission)
+            .expect("mocked submission should be valid");
+
+        let mut blank = submission.clone();
+        blank.project_description = "  ".into();
+        assert_eq!(
+            Err(Error::InvalidField {
+                field: "project_description".into(),
+                reason: "value is required".into(),
+            }),
+            selectors.validate_submission(&blank)
+        );
+
+        let mut too_long = submission.clone();
+        too_long.coin_gecko_ticker = Some("a".repeat(TICKER_MAX_LENGTH + 1));
+        assert_eq!(
+            Err(Error::InvalidField {
+                field: "coin_gecko_ticker".into(),
+                reason: format!("value exceeds {TICKER_MAX_LENGTH} characters"),
+            }),
+            selectors.validate_submission(&too_long)
+        );
+
+        let mut localized = submission.clone();
+        localized.localized_descriptions = vec![
+            LocalizedDescription {
+                language: "pt-BR".into(),
+                project_name: None,
+                project_description: "Descrição".into(),
+            },
+            LocalizedDescription {
+                language: "PT-br".into(),
+                project_name: None,
+                project_description: "Descrição".into(),
+            },
+        ];
+        assert_eq!(
+            Err(Error::InvalidField {
+                field: "localized_descriptions[1].language".into(),
+                reason: "PT-br is specified more than once".into(),
+            }),
+            selectors.validate_submission(&localized)
+        );
+        localized.localized_descriptions[1].language = "en_US".into();
+        assert_eq!(
+            Err(Error::InvalidField {
+                field: "localized_descriptions[1].language".into(),
+                reason: "en_US is not a valid BCP-47 language tag".into(),
+            }),
+            selectors.validate_submission(&localized)
+        );
+        localized.localized_descriptions[1].language = "zh-Hant-TW".into();
+        selectors
+            .validate_submission(&localized)
+            .expect("localized descriptions should be valid");
+
+        let mut invalid_sector = submission;
+        invalid_sector.project_sector = Some("Gaming".into());
+        assert_eq!(
+            Err(Error::InvalidSelector {
+                selector: "project_sector".into(),
+                value: "Gaming".into(),
+            }),
+            selectors.validate_submission(&invalid_sector)
+        );
+    }
+}
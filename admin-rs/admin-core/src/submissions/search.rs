@@ -0,0 +1,316 @@
+use super::{Error, Status, Submission};
+use crate::client::Client;
+use chrono::NaiveDateTime;
+use entity::{sea_orm_active_enums::SubmissionStatus, submissions};
+use sea_orm::{
+    prelude::*,
+    sea_query::{Condition, Expr, SimpleExpr},
+    ConnectionTrait, DbBackend, QueryOrder, QuerySelect, Select,
+};
+use std::{fmt, str::FromStr};
+
+pub const DEFAULT_PAGE_SIZE: u64 = 50;
+pub const MAX_PAGE_SIZE: u64 = 500;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SearchOrder {
+    /// Newest submissions go first.
+    #[default]
+    Newest,
+    /// Submissions with the highest risk score go first, newest first within the same score.
+    Risk,
+}
+
+/// Filters reviewers use to find submissions across all chains.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    /// Matched against project name and description, token address,
+    /// requester email and project website.
+    pub text: Option<String>,
+    pub statuses: Vec<Status>,
+    pub chain_ids: Vec<i64>,
+    pub created_from: Option<NaiveDateTime>,
+    pub created_to: Option<NaiveDateTime>,
+    pub order: SearchOrder,
+}
+
+/// Position of the last returned submission. Formatted as `{risk_score}.{id}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageToken {
+    pub risk_score: i32,
+    pub id: i64,
+}
+
+impl From<&Submission> for PageToken {
+    fn from(submission: &Submission) -> Self {
+        Self {
+            risk_score: submission.risk_score,
+            id: submission.id,
+        }
+    }
+}
+
+impl fmt::Display for PageToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.risk_score, self.id)
+    }
+}
+
+impl FromStr for PageToken {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid page token: {s}");
+        let (risk_score, id) = s.split_once('.').ok_or_else(invalid)?;
+        Ok(Self {
+            risk_score: risk_score.parse().map_err(|_| invalid())?,
+            id: id.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+impl SearchQuery {
+    /// Returns the query selecting matched submissions in the query order.
+    /// If `after` is specified, only submissions following it are selected.
+    pub(crate) fn select(
+        &self,
+        backend: DbBackend,
+        after: Option<PageToken>,
+    ) -> Select<submissions::Entity> {
+        let mut select = submissions::Entity::find();
+
+        let text = self.text.as_deref().map(str::trim).unwrap_or_default();
+        if !text.is_empty() {
+            select = select.filter(text_condition(backend, text));
+        }
+        if !self.statuses.is_empty() {
+            let statuses = self
+                .statuses
+                .iter()
+                .fold(Condition::any(), |condition, status| {
+                    condition
+                        .add(submissions::Column::Status.eq(SubmissionStatus::from(status.clone())))
+                });
+            select = select.filter(statuses);
+        }
+        if !self.chain_ids.is_empty() {
+            select = select.filter(submissions::Column::ChainId.is_in(self.chain_ids.clone()));
+        }
+        if let Some(created_from) = self.created_from {
+            select = select.filter(submissions::Column::CreatedAt.gte(created_from));
+        }
+        if let Some(created_to) = self.created_to {
+            select = select.filter(submissions::Column::CreatedAt.lt(created_to));
+        }
+        if let Some(after) = after {
+            select = select.filter(match self.order {
+                SearchOrder::Newest => Condition::all().add(submissions::Column::Id.lt(after.id)),
+                SearchOrder::Risk => Condition::any()
+                    .add(submissions::Column::RiskScore.lt(after.risk_score))
+                    .add(
+                        Condition::all()
+                            .add(submissions::Column::RiskScore.eq(after.risk_score))
+                            .add(submissions::Column::Id.lt(after.id)),
+                    ),
+            });
+        }
+
+        match self.order {
+            SearchOrder::Newest => select.order_by_desc(submissions::Column::Id),
+            SearchOrder::Risk => select
+                .order_by_desc(submissions::Column::RiskScore)
+                .order_by_desc(submissions::Column::Id),
+        }
+    }
+}
+
+fn text_condition(backend: DbBackend, text: &str) -> SimpleExpr {
+    let pattern = format!("%{}%", escape_like(text));
+    match backend {
+        // Substring matches are served by trigram indexes,
+        // while the text search by the full-text one.
+        DbBackend::Postgres => Expr::cust_with_values(
+            r#"("project_name" ILIKE ?
+            OR "token_address" ILIKE ?
+            OR "requester_email" ILIKE ?
+            OR "project_website" ILIKE ?
+            OR to_tsvector('simple', coalesce("project_name", '') || ' ' || "project_description")
+                @@ plainto_tsquery('simple', ?))"#,
+            [
+                pattern.clone(),
+                pattern.clone(),
+                pattern.clone(),
+                pattern,
+                text.to_string(),
+            ],
+        ),
+        // Other backends have neither full-text search nor default escape character,
+        // so the description is matched by substring and the escape character is passed explicitly
+        _ => {
+            let pattern = pattern.to_lowercase();
+            let escape = "\\".to_string();
+            Expr::cust_with_values(
+                r#"(lower("project_name") LIKE ? ESCAPE ?
+                OR lower("token_address") LIKE ? ESCAPE ?
+                OR lower("requester_email") LIKE ? ESCAPE ?
+                OR lower("project_website") LIKE ? ESCAPE ?
+                OR lower("project_description") LIKE ? ESCAPE ?)"#,
+                [
+                    pattern.clone(),
+                    escape.clone(),
+                    pattern.clone(),
+                    escape.clone(),
+                    pattern.clone(),
+                    escape.clone(),
+                    pattern.clone(),
+                    escape.clone(),
+                    pattern,
+                    escape,
+                ],
+            )
+        }
+    }
+}
+
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Returns a page of matched submissions in the query order, and a token
+/// to retrieve the next page with, if there are more submissions.
+pub async fn search_submissions(
+    client: &Client,
+    query: &SearchQuery,
+    page_size: u64,
+    page_token: Option<PageToken>,
+) -> Result<(Vec<Submission>, Option<PageToken>), Error> {
+    let db = client.db.as_ref();
+    let page_size = page_size.clamp(1, MAX_PAGE_SIZE);
+    // One more submission is requested to find out whether the next page exists
+    let mut models = query
+        .select(db.get_database_backend(), page_token)
+        .limit(page_size + 1)
+        .all(db)
+        .await?;
+
+    let next_page_token = if models.len() as u64 > page_size {
+        models.truncate(page_size as usize);
+        models.last().map(|model| PageToken {
+            risk_score: model.risk_score,
+            id: model.id,
+        })
+    } else {
+        None
+    };
+    let submissions = Submission::try_from_models(db, models).await?;
+    Ok((submissions, next_page_token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        submissions::Selectors,
+        test_helpers::{init_admin_db, insert_mocked_submissions},
+    };
+    use entity::submissions;
+    use pretty_assertions::assert_eq;
+    use sea_orm::{sea_query::Expr, ColumnTrait, EntityTrait, QueryFilter};
+
+    #[test]
+    fn escape_like_works() {
+        assert_eq!("100\\%", escape_like("100%"));
+        assert_eq!("user\\_name", escape_like("user_name"));
+        assert_eq!("back\\\\slash", escape_like("back\\slash"));
+    }
+
+    #[test]
+    fn page_token_parsing() {
+        let token = PageToken {
+            risk_score: 40,
+            id: 12,
+        };
+        assert_eq!("40.12", token.to_string());
+        assert_eq!(Ok(token), "40.12".parse());
+        assert!("12".parse::<PageToken>().is_err());
+        assert!("a.12".parse::<PageToken>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search() {
+        let _ = tracing_subscriber::fmt::try_init();
+        let db = init_admin_db("test_search", None).await;
+        let client = Client::new(db, Selectors::default());
+
+        let inserted = insert_mocked_submissions(
+            &client.db,
+            &[
+                ("user1@gmail.com", 1, "Uniswap"),
+                ("user2@gmail.com", 1, "Sushiswap"),
+                ("user3@gmail.com", 2, "Aave"),
+                ("user4@gmail.com", 2, "Uniswap V3"),
+            ],
+        )
+        .await;
+        let ids = |submissions: &[Submission]| -> Vec<i64> {
+            submissions.iter().map(|submission| submission.id).collect()
+        };
+
+        let query = SearchQuery {
+            text: Some("uniswap".into()),
+            ..Default::default()
+        };
+        let (found, next_page_token) = search_submissions(&client, &query, 10, None)
+            .await
+            .expect("failed to search submissions");
+        assert_eq!(vec![inserted[3].id, inserted[0].id], ids(&found));
+        assert_eq!(None, next_page_token);
+
+        let query = SearchQuery {
+            text: Some("swap".into()),
+            chain_ids: vec![1],
+            ..Default::default()
+        };
+        let (found, next_page_token) = search_submissions(&client, &query, 1, None)
+            .await
+            .expect("failed to search submissions");
+        assert_eq!(vec![inserted[1].id], ids(&found));
+        assert_eq!(Some(PageToken::from(&inserted[1])), next_page_token);
+        let (found, next_page_token) = search_submissions(&client, &query, 1, next_page_token)
+            .await
+            .expect("failed to search submissions");
+        assert_eq!(vec![inserted[0].id], ids(&found));
+        assert_eq!(None, next_page_token);
+
+        let query = SearchQuery {
+            statuses: vec![Status::Approved],
+            ..Default::default()
+        };
+        let (found, _) = search_submissions(&client, &query, 10, None)
+            .await
+            .expect("failed to search submissions");
+        assert!(found.is_empty(), "no submissions are approved");
+
+        submissions::Entity::update_many()
+            .col_expr(submissions::Column::RiskScore, Expr::value(50))
+            .filter(submissions::Column::Id.eq(inserted[2].id))
+            .exec(client.db.as_ref())
+            .await
+            .expect("failed to update risk score");
+        let query = SearchQuery {
+            order: SearchOrder::Risk,
+            ..Default::default()
+        };
+        let (found, next_page_token) = search_submissions(&client, &query, 2, None)
+            .await
+            .expect("failed to search submissions");
+        assert_eq!(vec![inserted[2].id, inserted[3].id], ids(&found));
+        let (found, _) = search_submissions(&client, &query, 2, next_page_token)
+            .await
+            .expect("failed to search submissions");
+        assert_eq!(vec![inserted[1].id, inserted[0].id], ids(&found));
+    }
+}
@@ -1,12 +1,16 @@
+use super::risk::RiskReason;
 use blockscout_display_bytes::Bytes;
 use chrono::NaiveDateTime;
 use entity::{
     rejected_submissions, sea_orm_active_enums::SubmissionStatus, submissions,
     waiting_for_update_submissions,
 };
-use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder, Set};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    Set,
+};
 use serde::{Deserialize, Serialize};
-use std::str::FromStr;
+use std::{collections::HashMap, str::FromStr};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq, Eq)]
@@ -23,6 +27,12 @@ pub enum Error {
     InvalidStatusForUpdate(SubmissionStatus),
     #[error("invalid selector value ({selector}): {value}")]
     InvalidSelector { selector: String, value: String },
+    #[error("invalid {field}: {reason}")]
+    InvalidField { field: String, reason: String },
+    #[error("revision {revision} of submission {submission_id} not found")]
+    RevisionNotFound { submission_id: i64, revision: i32 },
+    #[error("submission {submission_id} has been modified, current etag is {etag}")]
+    EtagMismatch { submission_id: i64, etag: String },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -40,6 +50,29 @@ impl Default for Status {
     }
 }
 
+impl Status {
+    /// Returns the value the status is stored with in the database.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Approved => "approved",
+            Self::InProcess => "in_process",
+            Self::Rejected => "rejected",
+            Self::WaitingForUpdate => "waiting_for_update",
+        }
+    }
+}
+
+impl From<Status> for SubmissionStatus {
+    fn from(status: Status) -> Self {
+        match status {
+            Status::Approved => Self::Approved,
+            Status::InProcess => Self::InProcess,
+            Status::Rejected => Self::Rejected,
+            Status::WaitingForUpdate => Self::WaitingForUpdate,
+        }
+    }
+}
+
 impl From<SubmissionStatus> for Status {
     fn from(status: SubmissionStatus) -> Self {
         match status {
@@ -62,18 +95,16 @@ impl Selectors {
             project_sectors: project_sectors.into_iter().map(|v| v.into()).collect(),
         }
     }
+}
 
-    pub fn validate_submission(&self, submission: &Submission) -> Result<(), Error> {
-        if let Some(project_sector) = &submission.project_sector {
-            if !self.project_sectors.contains(project_sector) {
-                return Err(Error::InvalidSelector {
-                    selector: "project_sector".into(),
-                    value: project_sector.into(),
-                });
-            }
-        }
-        Ok(())
-    }
+/// Project name and description translated into the language
+/// identified by the BCP-47 `language` tag (e.g. `de`, `pt-BR`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LocalizedDescription {
+    pub language: String,
+    #[serde(default)]
+    pub project_name: Option<String>,
+    pub project_description: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -88,11 +119,31 @@ pub struct Submission {
 
     #[serde(default)]
     pub updated_at: NaiveDateTime,
+    #[serde(default)]
+    pub version: i64,
+    #[serde(default)]
+    pub risk_score: i32,
+    #[serde(default)]
+    pub risk_reasons: Vec<RiskReason>,
 
     // Blockscout related fields
     pub chain_id: i64,
+    #[serde(default)]
+    pub blockscout_user_id: Option<String>,
     pub blockscout_user_email: String,
 
+    // On-chain token metadata. Filled by the service, not the user
+    #[serde(default)]
+    pub token_name: Option<String>,
+    #[serde(default)]
+    pub token_symbol: Option<String>,
+    #[serde(default)]
+    pub token_type: Option<String>,
+    #[serde(default)]
+    pub token_decimals: Option<i32>,
+    #[serde(default)]
+    pub token_holders: Option<i64>,
+
     // Token info related fields
     pub token_address: Bytes,
     pub requester_name: String,
@@ -119,6 +170,8 @@ pub struct Submission {
     pub coin_market_cap_ticker: Option<String>,
     pub coin_gecko_ticker: Option<String>,
     pub defi_llama_ticker: Option<String>,
+    #[serde(default)]
+    pub localized_descriptions: Vec<LocalizedDescription>,
 }
 
 impl Submission {
@@ -144,6 +197,56 @@ impl Submission {
         Self::try_from_data(model, admin_comments)
     }
 
+    /// Converts the models loading admin comments of all of them at once.
+    pub(crate) async fn try_from_models<C: ConnectionTrait>(
+        db: &C,
+        models: Vec<submissions::Model>,
+    ) -> Result<Vec<Self>, DbErr> {
+        let ids_with_status = |status: SubmissionStatus| -> Vec<i64> {
+            models
+                .iter()
+                .filter(|model| model.status == status)
+                .map(|model| model.id)
+                .collect()
+        };
+        let waiting_for_update_ids = ids_with_status(SubmissionStatus::WaitingForUpdate);
+        let rejected_ids = ids_with_status(SubmissionStatus::Rejected);
+
+        // Comments are ordered by id, so that only the latest one of each submission is kept
+        let mut admin_comments = HashMap::new();
+        if !waiting_for_update_ids.is_empty() {
+            for model in waiting_for_update_submissions::Entity::find()
+                .filter(
+                    waiting_for_update_submissions::Column::SubmissionId
+                        .is_in(waiting_for_update_ids),
+                )
+                .order_by_asc(waiting_for_update_submissions::Column::Id)
+                .all(db)
+                .await?
+            {
+                admin_comments.insert(model.submission_id, model.admin_comments);
+            }
+        }
+        if !rejected_ids.is_empty() {
+            for model in rejected_submissions::Entity::find()
+                .filter(rejected_submissions::Column::SubmissionId.is_in(rejected_ids))
+                .order_by_asc(rejected_submissions::Column::Id)
+                .all(db)
+                .await?
+            {
+                admin_comments.insert(model.submission_id, model.reason);
+            }
+        }
+
+        models
+            .into_iter()
+            .map(|model| {
+                let comments = admin_comments.remove(&model.id);
+                Self::try_from_data(model, comments)
+            })
+            .collect()
+    }
+
     fn try_from_data(
         model: submissions::Model,
         admin_comments: Option<String>,
@@ -154,7 +257,17 @@ impl Submission {
             status: model.status.into(),
             admin_comments,
             updated_at: model.updated_at,
+            version: model.version,
+            risk_score: model.risk_score,
+            risk_reasons: serde_json::from_value(model.risk_reasons)
+                .map_err(|e| DbErr::Custom(format!("invalid risk_reasons: {e}")))?,
+            blockscout_user_id: model.blockscout_user_id,
             blockscout_user_email: model.blockscout_user_email,
+            token_name: model.token_name,
+            token_symbol: model.token_symbol,
+            token_type: model.token_type,
+            token_decimals: model.token_decimals,
+            token_holders: model.token_holders,
             token_address: Bytes::from_str(&model.token_address)
                 .map_err(|e| DbErr::Custom(format!("invalid token_address: {e}")))?,
             requester_name: model.requester_name,
@@ -181,16 +294,34 @@ impl Submission {
             coin_market_cap_ticker: model.coin_market_cap_ticker,
             coin_gecko_ticker: model.coin_gecko_ticker,
             defi_llama_ticker: model.defi_llama_ticker,
+            localized_descriptions: serde_json::from_value(model.localized_descriptions)
+                .map_err(|e| DbErr::Custom(format!("invalid localized_descriptions: {e}")))?,
         })
     }
 }
 
+/// Opaque value which changes every time the submission is modified.
+pub(crate) fn etag(version: i64) -> String {
+    format!("{version:x}")
+}
+
 impl Submission {
+    /// Is used as a precondition of the updates, so that they are not based on outdated state.
+    pub fn etag(&self) -> String {
+        etag(self.version)
+    }
+
     pub fn active_model(self) -> submissions::ActiveModel {
         submissions::ActiveModel {
             chain_id: Set(self.chain_id),
+            blockscout_user_id: Set(self.blockscout_user_id),
             blockscout_user_email: Set(self.blockscout_user_email),
             token_address: Set(self.token_address.to_string()),
+            token_name: Set(self.token_name),
+            token_symbol: Set(self.token_symbol),
+            token_type: Set(self.token_type),
+            token_decimals: Set(self.token_decimals),
+            token_holders: Set(self.token_holders),
             project_name: Set(self.project_name),
             requester_name: Set(self.requester_name),
             requester_email: Set(self.requester_email),
@@ -215,6 +346,7 @@ impl Submission {
             coin_market_cap_ticker: Set(self.coin_market_cap_ticker),
             coin_gecko_ticker: Set(self.coin_gecko_ticker),
             defi_llama_ticker: Set(self.defi_llama_ticker),
+            localized_descriptions: Set(serde_json::json!(self.localized_descriptions)),
             ..Default::default()
         }
     }
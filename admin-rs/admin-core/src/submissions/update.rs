@@ -1,36 +1,101 @@
-use super::{Error, Submission};
+use super::{
+    revisions::save_revision, risk::assess_submission, types::etag, user::owner_condition, Error,
+    Submission, User,
+};
 use crate::client::Client;
 use entity::{sea_orm_active_enums::SubmissionStatus, submissions, waiting_for_update_submissions};
-use sea_orm::{prelude::*, sea_query::Query, ActiveValue, Iterable, TransactionTrait};
+use sea_orm::{
+    prelude::*, sea_query::Query, ActiveValue, DatabaseTransaction, Iterable, QuerySelect,
+    TransactionTrait,
+};
 
-pub async fn update_submission(client: &Client, data: Submission) -> Result<Submission, Error> {
+/// Returns the submission of the user if it could be updated, i.e. it is waiting
+/// for an update and matches `expected_etag` (if set). Allows failing fast
+/// before the update data is prepared; [`update_submission`] repeats the checks.
+pub async fn get_updatable_submission(
+    client: &Client,
+    id: i64,
+    user: &User,
+    chain_id: i64,
+    expected_etag: Option<&str>,
+) -> Result<Submission, Error> {
+    let submission = submissions::Entity::find()
+        .filter(owner_condition(Some(&user.id), &user.email))
+        .filter(submissions::Column::Id.eq(id))
+        .filter(submissions::Column::ChainId.eq(chain_id))
+        .one(client.db.as_ref())
+        .await?
+        .ok_or_else(|| Error::NotFound(id))?;
+    check_updatable(&submission, expected_etag)?;
+    let submission = Submission::try_from_db(&client.db, submission).await?;
+    Ok(submission)
+}
+
+/// Updates user editable fields of the submission.
+/// If `expected_etag` is set, the update fails with [`Error::EtagMismatch`]
+/// when the submission has been modified since the etag was returned.
+pub async fn update_submission(
+    client: &Client,
+    data: Submission,
+    expected_etag: Option<&str>,
+) -> Result<Submission, Error> {
     client.selectors.validate_submission(&data)?;
 
-    let user_email = data.blockscout_user_email.clone();
     let id = data.id;
     let chain_id = data.chain_id;
 
+    let txn = client.db.begin().await?;
+    // The row is locked until the transaction ends, so that concurrent
+    // updates cannot be based on the same submission state
     let submission = submissions::Entity::find()
-        .filter(submissions::Column::BlockscoutUserEmail.eq(user_email))
+        .filter(owner_condition(
+            data.blockscout_user_id.as_deref(),
+            &data.blockscout_user_email,
+        ))
         .filter(submissions::Column::Id.eq(id))
         .filter(submissions::Column::ChainId.eq(chain_id))
-        .one(client.db.as_ref())
+        .lock_exclusive()
+        .one(&txn)
         .await?
         .ok_or_else(|| Error::NotFound(id))?;
-    let data = data.active_model();
+    check_updatable(&submission, expected_etag)?;
+
+    let assessment = assess_submission(&txn, &data).await?;
+    let mut data = data.active_model();
+    assessment.apply(&mut data)?;
+    let updated_submission = perform_update(&txn, submission, data).await?;
+    txn.commit().await?;
+    let updated_submission = Submission::try_from_db(&client.db, updated_submission).await?;
+    Ok(updated_submission)
+}
+
+fn check_updatable(
+    submission: &submissions::Model,
+    expected_etag: Option<&str>,
+) -> Result<(), Error> {
+    if let Some(expected_etag) = expected_etag {
+        let etag = etag(submission.version);
+        if etag != expected_etag {
+            return Err(Error::EtagMismatch {
+                submission_id: submission.id,
+                etag,
+            });
+        }
+    }
     match submission.status {
-        SubmissionStatus::WaitingForUpdate => perform_update(client, submission, data).await,
-        _ => Err(Error::InvalidStatusForUpdate(submission.status)),
+        SubmissionStatus::WaitingForUpdate => Ok(()),
+        _ => Err(Error::InvalidStatusForUpdate(submission.status.clone())),
     }
 }
 
 async fn perform_update(
-    client: &Client,
+    txn: &DatabaseTransaction,
     db_submission: submissions::Model,
     updating_submission: submissions::ActiveModel,
-) -> Result<Submission, Error> {
-    let txn = client.db.begin().await?;
+) -> Result<submissions::Model, Error> {
     let submission_id: i64 = db_submission.id;
+    let version = db_submission.version;
+    save_revision(txn, &db_submission).await?;
     let mut submission: submissions::ActiveModel = db_submission.into();
     for column in submissions::Column::iter() {
         if let Some(value) = updating_submission.get(column).into_value() {
@@ -38,7 +103,26 @@ async fn perform_update(
         }
     }
     submission.status = ActiveValue::Set(SubmissionStatus::InProcess);
-    let updated_submission = submission.update(&txn).await?;
+    // Backends without row locks may let the submission be modified after it was read.
+    // The version is incremented on every update, so such updates are rejected.
+    let updated_submission = match submissions::Entity::update(submission)
+        .filter(submissions::Column::Version.eq(version))
+        .exec(txn)
+        .await
+    {
+        Ok(submission) => submission,
+        Err(DbErr::RecordNotUpdated) => {
+            let current = submissions::Entity::find_by_id(submission_id)
+                .one(txn)
+                .await?
+                .ok_or(Error::NotFound(submission_id))?;
+            return Err(Error::EtagMismatch {
+                submission_id,
+                etag: etag(current.version),
+            });
+        }
+        Err(err) => return Err(err.into()),
+    };
     let update_result = waiting_for_update_submissions::Entity::update_many()
         .filter(
             waiting_for_update_submissions::Column::Id.in_subquery(
@@ -56,7 +140,7 @@ async fn perform_update(
             waiting_for_update_submissions::Column::Addressed,
             true.into(),
         )
-        .exec(&txn)
+        .exec(txn)
         .await?;
     if update_result.rows_affected != 1 {
         tracing::warn!(
@@ -65,8 +149,6 @@ async fn perform_update(
             "invalid update of `addressed` field of waiting_for_update_submissions"
         )
     }
-    txn.commit().await?;
-    let updated_submission = Submission::try_from_db(&client.db, updated_submission).await?;
     Ok(updated_submission)
 }
 
@@ -82,7 +164,6 @@ mod tests {
     use sea_orm::{sea_query::Expr, Set};
 
     #[tokio::test]
-    #[ignore = "needs database to run"]
     async fn test_update() {
         let _ = tracing_subscriber::fmt::try_init();
         let db = init_admin_db("test_update", None).await;
@@ -116,7 +197,7 @@ mod tests {
             new_submission.project_website = "new_email@gmail.com".to_string();
             new_submission.project_name = Some("new_name".to_string());
             new_submission.status = Status::Approved;
-            let actual_submission = update_submission(&client, new_submission.clone())
+            let actual_submission = update_submission(&client, new_submission.clone(), None)
                 .await
                 .expect("failed to update submission");
             // submission should change it's status
@@ -125,6 +206,12 @@ mod tests {
             assert!(actual_submission.updated_at > new_submission.updated_at);
 
             new_submission.updated_at = actual_submission.updated_at;
+            // submission should change it's version
+            assert!(actual_submission.version > new_submission.version);
+            new_submission.version = actual_submission.version;
+            // Submissions reuse the same icon, so they are assessed as risky
+            new_submission.risk_score = actual_submission.risk_score;
+            new_submission.risk_reasons = actual_submission.risk_reasons.clone();
             assert_eq!(actual_submission, new_submission);
 
             let waiting_for_update = waiting_for_update_submissions::Entity::find()
@@ -141,7 +228,6 @@ mod tests {
     }
 
     #[tokio::test]
-    #[ignore = "needs database to run"]
     async fn update_test_selectors() {
         let _ = tracing_subscriber::fmt::try_init();
         let db = init_admin_db("update_test_selectors", None).await;
@@ -167,7 +253,7 @@ mod tests {
             submission
         };
 
-        let _result = update_submission(&client, valid_submission)
+        let _result = update_submission(&client, valid_submission, None)
             .await
             .expect("error during valid submission creation");
 
@@ -179,7 +265,7 @@ mod tests {
             submission.project_sector = Some(invalid_project_sector.into());
             submission
         };
-        let result = update_submission(&client, invalid_submission)
+        let result = update_submission(&client, invalid_submission, None)
             .await
             .expect_err("Error expected, but operation succeeded");
         let expected_result = Error::InvalidSelector {
@@ -191,4 +277,117 @@ mod tests {
             "Invalid result for invalid project sector"
         );
     }
+
+    /// Makes the submission updatable again. Returns the etag of the submission.
+    async fn require_update(client: &Client, id: i64) -> String {
+        submissions::Entity::update_many()
+            .col_expr(
+                submissions::Column::Status,
+                Expr::value(SubmissionStatus::WaitingForUpdate.as_enum()),
+            )
+            .exec(client.db.as_ref())
+            .await
+            .expect("failed to manually update database");
+        let model = submissions::Entity::find_by_id(id)
+            .one(client.db.as_ref())
+            .await
+            .unwrap()
+            .expect("submission should exist");
+        etag(model.version)
+    }
+
+    #[tokio::test]
+    async fn update_checks_etag() {
+        let _ = tracing_subscriber::fmt::try_init();
+        let db = init_admin_db("update_checks_etag", None).await;
+        let client = Client::new(db, Selectors::default());
+        let submission = insert_mocked_submissions(&client.db, &[("1", 1, "sub1")])
+            .await
+            .remove(0);
+        let outdated_etag = require_update(&client, submission.id).await;
+        let mut new_submission = submission.clone();
+        new_submission.project_name = Some("new_name".to_string());
+        let updated = update_submission(&client, new_submission.clone(), Some(&outdated_etag))
+            .await
+            .expect("failed to update submission");
+        assert_ne!(outdated_etag, updated.etag());
+
+        let current_etag = require_update(&client, submission.id).await;
+        let err = update_submission(&client, new_submission, Some(&outdated_etag))
+            .await
+            .expect_err("update based on outdated etag should fail");
+        assert_eq!(
+            Error::EtagMismatch {
+                submission_id: submission.id,
+                etag: current_etag,
+            },
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn update_checks_version_of_read_submission() {
+        let _ = tracing_subscriber::fmt::try_init();
+        let db = init_admin_db("update_checks_version_of_read_submission", None).await;
+        let client = Client::new(db, Selectors::default());
+        let submission = insert_mocked_submissions(&client.db, &[("1", 1, "sub1")])
+            .await
+            .remove(0);
+        require_update(&client, submission.id).await;
+        let outdated = submissions::Entity::find_by_id(submission.id)
+            .one(client.db.as_ref())
+            .await
+            .unwrap()
+            .expect("submission should exist");
+        // Modified after it was read, as if it was not locked
+        let current_etag = require_update(&client, submission.id).await;
+
+        let txn = client.db.begin().await.unwrap();
+        let err = perform_update(&txn, outdated, submission.clone().active_model())
+            .await
+            .expect_err("update of modified submission should fail");
+        assert_eq!(
+            Error::EtagMismatch {
+                submission_id: submission.id,
+                etag: current_etag,
+            },
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn get_updatable_submission_checks_owner_and_status() {
+        let _ = tracing_subscriber::fmt::try_init();
+        let db = init_admin_db("get_updatable_submission_checks_owner_and_status", None).await;
+        let client = Client::new(db, Selectors::default());
+        let submission = insert_mocked_submissions(&client.db, &[("1", 1, "sub1")])
+            .await
+            .remove(0);
+        let user = User::new("1", "1");
+
+        let err = get_updatable_submission(&client, submission.id, &user, 1, None)
+            .await
+            .expect_err("submission in process should not be updatable");
+        assert_eq!(
+            Error::InvalidStatusForUpdate(SubmissionStatus::InProcess),
+            err
+        );
+
+        let etag = require_update(&client, submission.id).await;
+        let updatable = get_updatable_submission(&client, submission.id, &user, 1, Some(&etag))
+            .await
+            .expect("submission waiting for update should be updatable");
+        assert_eq!(submission.id, updatable.id);
+
+        let err = get_updatable_submission(&client, submission.id, &user, 1, Some("outdated"))
+            .await
+            .expect_err("outdated etag should be rejected");
+        assert!(matches!(err, Error::EtagMismatch { .. }));
+
+        let another_user = User::new("2", "2");
+        let err = get_updatable_submission(&client, submission.id, &another_user, 1, None)
+            .await
+            .expect_err("submission of another user should not be found");
+        assert_eq!(Error::NotFound(submission.id), err);
+    }
 }
@@ -0,0 +1,217 @@
+use super::{Error, User, ANONYMIZED_EMAIL};
+use crate::client::Client;
+use entity::submissions;
+use sea_orm::{
+    prelude::*,
+    sea_query::{Condition, Expr},
+    TransactionTrait,
+};
+
+pub(crate) fn owner_condition(user_id: Option<&str>, user_email: &str) -> Condition {
+    let legacy_condition = Condition::all()
+        .add(submissions::Column::BlockscoutUserId.is_null())
+        .add(submissions::Column::BlockscoutUserEmail.eq(user_email));
+    match user_id {
+        Some(user_id) => Condition::any()
+            .add(submissions::Column::BlockscoutUserId.eq(user_id))
+            .add(legacy_condition),
+        None => legacy_condition,
+    }
+}
+
+/// Caller the idempotency keys of the user requests at the chain are stored for,
+/// so that different users cannot replay responses of each other.
+pub fn idempotency_caller(chain_id: i64, user_id: &str) -> String {
+    format!("chains/{chain_id}/users/{user_id}")
+}
+
+/// Brings stored submissions in line with the current state of the user account:
+/// submissions without user id which belong to the user email are assigned the user id,
+/// and submissions of the user get the current email, if it has been changed
+/// and has not been erased on the user request.
+///
+/// Returns the number of updated submissions.
+pub async fn reconcile_user(client: &Client, user: &User) -> Result<u64, Error> {
+    let txn = client.db.begin().await?;
+    let backfilled = submissions::Entity::update_many()
+        .col_expr(
+            submissions::Column::BlockscoutUserId,
+            Expr::value(user.id.clone()),
+        )
+        .filter(submissions::Column::BlockscoutUserId.is_null())
+        .filter(submissions::Column::BlockscoutUserEmail.eq(user.email.as_str()))
+        .exec(&txn)
+        .await?
+        .rows_affected;
+    let email_updated = submissions::Entity::update_many()
+        .col_expr(
+            submissions::Column::BlockscoutUserEmail,
+            Expr::value(user.email.clone()),
+        )
+        .filter(submissions::Column::BlockscoutUserId.eq(user.id.as_str()))
+        .filter(submissions::Column::BlockscoutUserEmail.ne(user.email.as_str()))
+        // Erased emails must not be restored
+        .filter(submissions::Column::BlockscoutUserEmail.ne(ANONYMIZED_EMAIL))
+        .exec(&txn)
+        .await?
+        .rows_affected;
+    txn.commit().await?;
+
+    if email_updated > 0 {
+        tracing::info!(
+            user_id = %user.id,
+            submissions = email_updated,
+            "blockscout user email of submissions has been updated"
+        );
+    }
+    Ok(backfilled + email_updated)
+}
+
+/// Assigns user ids to the submissions at the chain created before the ids were stored.
+/// `users` are accounts of the chain Blockscout instance (e.g. exported from its database),
+/// which are matched with the submissions by email. Submissions of users who are not
+/// authenticated anymore would otherwise be identified by the email only.
+///
+/// Returns the number of updated submissions.
+pub async fn backfill_user_ids(
+    client: &Client,
+    chain_id: i64,
+    users: &[User],
+) -> Result<u64, Error> {
+    let txn = client.db.begin().await?;
+    let mut updated = 0;
+    for user in users {
+        updated += submissions::Entity::update_many()
+            .col_expr(
+                submissions::Column::BlockscoutUserId,
+                Expr::value(user.id.clone()),
+            )
+            .filter(submissions::Column::ChainId.eq(chain_id))
+            .filter(submissions::Column::BlockscoutUserId.is_null())
+            .filter(submissions::Column::BlockscoutUserEmail.eq(user.email.as_str()))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+    }
+    txn.commit().await?;
+
+    tracing::info!(
+        chain_id,
+        users = users.len(),
+        submissions = updated,
+        "blockscout user ids of submissions have been backfilled"
+    );
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        submissions::{get_submission, list_submissions, Selectors},
+        test_helpers::{init_admin_db, insert_mocked_submissions},
+    };
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn test_reconcile_user() {
+        let _ = tracing_subscriber::fmt::try_init();
+        let db = init_admin_db("test_reconcile_user", None).await;
+        let client = Client::new(db, Selectors::default());
+        let chain_id = 1;
+
+        let submissions = insert_mocked_submissions(
+            &client.db,
+            &[
+                ("user@gmail.com", chain_id, "sub1"),
+                ("another@gmail.com", chain_id, "sub2"),
+            ],
+        )
+        .await;
+
+        // Submissions created before user ids were stored are found by email
+        let user = User::new("1", "user@gmail.com");
+        let found = list_submissions(&client, &user, chain_id)
+            .await
+            .expect("failed to list submissions");
+        assert_eq!(vec![submissions[0].id], ids(&found));
+
+        let updated = reconcile_user(&client, &user)
+            .await
+            .expect("failed to reconcile user");
+        assert_eq!(1, updated, "invalid number of updated submissions");
+
+        // The user changes the email
+        let user = User::new("1", "new_email@gmail.com");
+        let updated = reconcile_user(&client, &user)
+            .await
+            .expect("failed to reconcile user");
+        assert_eq!(1, updated, "invalid number of updated submissions");
+
+        let submission = get_submission(&client, submissions[0].id, &user, chain_id)
+            .await
+            .expect("submission of the user should be found by id");
+        assert_eq!(Some(user.id.clone()), submission.blockscout_user_id);
+        assert_eq!(user.email, submission.blockscout_user_email);
+
+        // Another user cannot access submissions by previous email of the user
+        let another_user = User::new("2", "user@gmail.com");
+        let found = list_submissions(&client, &another_user, chain_id)
+            .await
+            .expect("failed to list submissions");
+        assert!(found.is_empty(), "submissions of another user returned");
+    }
+
+    #[tokio::test]
+    async fn test_backfill_user_ids() {
+        let db = init_admin_db("test_backfill_user_ids", None).await;
+        let client = Client::new(db, Selectors::default());
+
+        let submissions = insert_mocked_submissions(
+            &client.db,
+            &[
+                ("user@gmail.com", 1, "sub1"),
+                ("user@gmail.com", 2, "sub2"),
+                ("another@gmail.com", 1, "sub3"),
+            ],
+        )
+        .await;
+
+        let users = [
+            User::new("1", "user@gmail.com"),
+            User::new("3", "unknown@gmail.com"),
+        ];
+        let updated = backfill_user_ids(&client, 1, &users)
+            .await
+            .expect("failed to backfill user ids");
+        assert_eq!(1, updated, "invalid number of updated submissions");
+
+        // The user is found by id, even after the email has been changed
+        let user = User::new("1", "new_email@gmail.com");
+        let found = list_submissions(&client, &user, 1)
+            .await
+            .expect("failed to list submissions");
+        assert_eq!(vec![submissions[0].id], ids(&found));
+
+        // Submissions at other chains and of other users are not updated
+        for (user, chain_id, expected) in [
+            (User::new("1", "user@gmail.com"), 2, submissions[1].id),
+            (User::new("2", "another@gmail.com"), 1, submissions[2].id),
+        ] {
+            let submission = get_submission(&client, expected, &user, chain_id)
+                .await
+                .expect("submission should be found by email");
+            assert_eq!(None, submission.blockscout_user_id);
+        }
+
+        // Submissions with user ids are not reassigned
+        let updated = backfill_user_ids(&client, 1, &[User::new("4", "user@gmail.com")])
+            .await
+            .expect("failed to backfill user ids");
+        assert_eq!(0, updated, "invalid number of updated submissions");
+    }
+
+    fn ids(submissions: &[crate::submissions::Submission]) -> Vec<i64> {
+        submissions.iter().map(|s| s.id).collect()
+    }
+}
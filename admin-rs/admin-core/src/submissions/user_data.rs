@@ -0,0 +1,381 @@
+use super::{
+    revisions::anonymize_revisions,
+    user::{idempotency_caller, owner_condition},
+    DataSubject, Error, Submission, ANONYMIZED_EMAIL,
+};
+use crate::client::Client;
+use chrono::NaiveDateTime;
+use entity::{idempotency_keys, rejected_submissions, submissions, waiting_for_update_submissions};
+use sea_orm::{
+    prelude::*,
+    sea_query::{Condition, Expr},
+    DatabaseTransaction, QueryOrder, QuerySelect, TransactionTrait,
+};
+use std::collections::{BTreeSet, HashMap};
+
+/// Value requester names are replaced with on the user data erasure
+/// (emails are replaced with [`ANONYMIZED_EMAIL`]).
+pub const ANONYMIZED_NAME: &str = "anonymized";
+
+fn subject_condition(subject: &DataSubject) -> Condition {
+    match subject {
+        DataSubject::User(user) => owner_condition(Some(&user.id), &user.email),
+        DataSubject::Id(id) => {
+            Condition::all().add(submissions::Column::BlockscoutUserId.eq(id.as_str()))
+        }
+        DataSubject::Email(email) => {
+            Condition::all().add(submissions::Column::BlockscoutUserEmail.eq(email.as_str()))
+        }
+    }
+}
+
+/// Restricts the query to the given chain, or to no chain if `None` is specified.
+fn chain_condition(chain_id: Option<i64>) -> Condition {
+    let condition = Condition::all();
+    match chain_id {
+        Some(chain_id) => condition.add(submissions::Column::ChainId.eq(chain_id)),
+        None => condition,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReviewCommentKind {
+    WaitingForUpdate,
+    Rejected,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewComment {
+    pub kind: ReviewCommentKind,
+    pub comment: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubmissionData {
+    pub submission: Submission,
+    /// All comments left by reviewers, ordered by creation time.
+    pub review_comments: Vec<ReviewComment>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserData {
+    pub submissions: Vec<SubmissionData>,
+}
+
+/// Returns all submissions of the user at the given chain (or at all chains, if `chain_id`
+/// is `None`) together with their review history.
+pub async fn export_user_data(
+    client: &Client,
+    subject: &DataSubject,
+    chain_id: Option<i64>,
+) -> Result<UserData, Error> {
+    let models = submissions::Entity::find()
+        .filter(subject_condition(subject))
+        .filter(chain_condition(chain_id))
+        .order_by_asc(submissions::Column::Id)
+        .all(client.db.as_ref())
+        .await?;
+    let ids: Vec<i64> = models.iter().map(|model| model.id).collect();
+
+    let mut review_comments: HashMap<i64, Vec<ReviewComment>> = HashMap::new();
+    for model in waiting_for_update_submissions::Entity::find()
+        .filter(waiting_for_update_submissions::Column::SubmissionId.is_in(ids.clone()))
+        .all(client.db.as_ref())
+        .await?
+    {
+        review_comments
+            .entry(model.submission_id)
+            .or_default()
+            .push(ReviewComment {
+                kind: ReviewCommentKind::WaitingForUpdate,
+                comment: model.admin_comments,
+                created_at: model.created_at,
+            });
+    }
+    for model in rejected_submissions::Entity::find()
+        .filter(rejected_submissions::Column::SubmissionId.is_in(ids))
+        .all(client.db.as_ref())
+        .await?
+    {
+        review_comments
+            .entry(model.submission_id)
+            .or_default()
+            .push(ReviewComment {
+                kind: ReviewCommentKind::Rejected,
+                comment: model.reason,
+                created_at: model.created_at,
+            });
+    }
+
+    let mut submissions = Vec::with_capacity(models.len());
+    for model in models {
+        let mut comments = review_comments.remove(&model.id).unwrap_or_default();
+        comments.sort_by_key(|comment| comment.created_at);
+        submissions.push(SubmissionData {
+            submission: Submission::try_from_db(&client.db, model).await?,
+            review_comments: comments,
+        });
+    }
+    Ok(UserData { submissions })
+}
+
+/// Anonymizes personal data of the user submissions and their revisions at the given chain
+/// (or at all chains, if `chain_id` is `None`). Submissions themselves and their review history are kept,
+/// so that the statistics built on top of them remain intact. The user id is cleared as well,
+/// so erased submissions do not belong to the user account anymore.
+/// Idempotency records of the user requests, which store their responses, are deleted.
+///
+/// Returns the number of anonymized submissions.
+pub async fn erase_user_data(
+    client: &Client,
+    subject: &DataSubject,
+    chain_id: Option<i64>,
+) -> Result<u64, Error> {
+    let txn = client.db.begin().await?;
+    let owners: Vec<(i64, Option<String>)> = submissions::Entity::find()
+        .select_only()
+        .column(submissions::Column::Id)
+        .column(submissions::Column::BlockscoutUserId)
+        .filter(subject_condition(subject))
+        .filter(chain_condition(chain_id))
+        .into_tuple()
+        .all(&txn)
+        .await?;
+    let mut user_ids = BTreeSet::new();
+    match subject {
+        DataSubject::User(user) => {
+            user_ids.insert(user.id.clone());
+        }
+        DataSubject::Id(id) => {
+            user_ids.insert(id.clone());
+        }
+        DataSubject::Email(_) => (),
+    }
+    let ids: Vec<i64> = owners
+        .into_iter()
+        .map(|(id, user_id)| {
+            user_ids.extend(user_id);
+            id
+        })
+        .collect();
+
+    let erased = submissions::Entity::update_many()
+        .col_expr(
+            submissions::Column::RequesterName,
+            Expr::value(ANONYMIZED_NAME),
+        )
+        .col_expr(
+            submissions::Column::RequesterEmail,
+            Expr::value(ANONYMIZED_EMAIL),
+        )
+        .col_expr(
+            submissions::Column::BlockscoutUserEmail,
+            Expr::value(ANONYMIZED_EMAIL),
+        )
+        .col_expr(
+            submissions::Column::BlockscoutUserId,
+            Expr::value(Option::<String>::None),
+        )
+        .filter(submissions::Column::Id.is_in(ids.clone()))
+        .exec(&txn)
+        .await?
+        .rows_affected;
+    anonymize_revisions(&txn, ids).await?;
+    delete_idempotency_keys(&txn, &user_ids, chain_id).await?;
+    txn.commit().await?;
+
+    tracing::info!(?chain_id, submissions = erased, "user data has been erased");
+    Ok(erased)
+}
+
+/// Deletes idempotency keys of the users' requests at the given chain (or at all chains).
+async fn delete_idempotency_keys(
+    txn: &DatabaseTransaction,
+    user_ids: &BTreeSet<String>,
+    chain_id: Option<i64>,
+) -> Result<(), Error> {
+    if user_ids.is_empty() {
+        return Ok(());
+    }
+    let callers = user_ids
+        .iter()
+        .fold(Condition::any(), |condition, user_id| match chain_id {
+            Some(chain_id) => condition
+                .add(idempotency_keys::Column::Caller.eq(idempotency_caller(chain_id, user_id))),
+            // Matches callers of the user at any chain, see `idempotency_caller`
+            None => condition
+                .add(idempotency_keys::Column::Caller.like(&format!("chains/%/users/{user_id}"))),
+        });
+    idempotency_keys::Entity::delete_many()
+        .filter(callers)
+        .exec(txn)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        submissions::{reconcile_user, Selectors, User},
+        test_helpers::{init_admin_db, insert_mocked_submissions},
+    };
+    use pretty_assertions::assert_eq;
+    use sea_orm::{ActiveModelTrait, Set};
+
+    #[tokio::test]
+    async fn test_export_and_erase_user_data() {
+        let _ = tracing_subscriber::fmt::try_init();
+        let db = init_admin_db("test_export_and_erase_user_data", None).await;
+        let client = Client::new(db, Selectors::default());
+        let chain_id = 1;
+
+        let submissions = insert_mocked_submissions(
+            &client.db,
+            &[
+                ("user@gmail.com", chain_id, "sub1"),
+                ("user@gmail.com", chain_id, "sub2"),
+                ("another@gmail.com", chain_id, "sub3"),
+            ],
+        )
+        .await;
+        rejected_submissions::ActiveModel {
+            submission_id: Set(submissions[0].id),
+            reason: Set("reason".into()),
+            ..Default::default()
+        }
+        .insert(client.db.as_ref())
+        .await
+        .expect("failed to insert rejected submission");
+
+        let user = User::new("1", "user@gmail.com");
+        let user_data = export_user_data(&client, &user.clone().into(), Some(chain_id))
+            .await
+            .expect("failed to export user data");
+        assert_eq!(2, user_data.submissions.len());
+        let comments: Vec<_> = user_data.submissions[0]
+            .review_comments
+            .iter()
+            .map(|comment| (comment.kind.clone(), comment.comment.as_str()))
+            .collect();
+        assert_eq!(vec![(ReviewCommentKind::Rejected, "reason")], comments);
+        assert!(user_data.submissions[1].review_comments.is_empty());
+
+        for caller in [
+            idempotency_caller(chain_id, &user.id),
+            idempotency_caller(chain_id + 1, &user.id),
+            idempotency_caller(chain_id, "2"),
+        ] {
+            idempotency_keys::ActiveModel {
+                caller: Set(caller),
+                key: Set("key".into()),
+                request_hash: Set("hash".into()),
+                response: Set(Some(vec![1])),
+                ..Default::default()
+            }
+            .insert(client.db.as_ref())
+            .await
+            .expect("failed to insert idempotency key");
+        }
+
+        reconcile_user(&client, &user)
+            .await
+            .expect("failed to reconcile user");
+        let erased = erase_user_data(&client, &user.clone().into(), Some(chain_id))
+            .await
+            .expect("failed to erase user data");
+        assert_eq!(2, erased, "invalid number of erased submissions");
+
+        // Submissions are kept, but do not belong to the user account anymore
+        let user_data = export_user_data(&client, &user.into(), Some(chain_id))
+            .await
+            .expect("failed to export user data");
+        assert!(user_data.submissions.is_empty());
+        let models = submissions::Entity::find()
+            .filter(submissions::Column::Id.is_in([submissions[0].id, submissions[1].id]))
+            .all(client.db.as_ref())
+            .await
+            .expect("failed to find submissions");
+        assert_eq!(2, models.len());
+        for model in models {
+            assert_eq!(ANONYMIZED_NAME, model.requester_name);
+            assert_eq!(ANONYMIZED_EMAIL, model.requester_email);
+            assert_eq!(ANONYMIZED_EMAIL, model.blockscout_user_email);
+            assert_eq!(None, model.blockscout_user_id);
+        }
+
+        // Only idempotency keys of the user requests at the chain are deleted
+        let callers: Vec<_> = idempotency_keys::Entity::find()
+            .order_by_asc(idempotency_keys::Column::Caller)
+            .all(client.db.as_ref())
+            .await
+            .expect("failed to find idempotency keys")
+            .into_iter()
+            .map(|model| model.caller)
+            .collect();
+        assert_eq!(
+            vec![
+                idempotency_caller(chain_id, "2"),
+                idempotency_caller(chain_id + 1, "1"),
+            ],
+            callers
+        );
+
+        let another_user_data = export_user_data(
+            &client,
+            &DataSubject::Email("another@gmail.com".into()),
+            Some(chain_id),
+        )
+        .await
+        .expect("failed to export user data");
+        assert_eq!(1, another_user_data.submissions.len());
+        assert_eq!(
+            "another@gmail.com",
+            another_user_data.submissions[0]
+                .submission
+                .blockscout_user_email
+        );
+    }
+
+    #[tokio::test]
+    async fn test_export_and_erase_user_data_at_all_chains() {
+        let db = init_admin_db("test_export_and_erase_user_data_at_all_chains", None).await;
+        let client = Client::new(db, Selectors::default());
+
+        insert_mocked_submissions(
+            &client.db,
+            &[
+                ("user@gmail.com", 1, "sub1"),
+                ("user@gmail.com", 2, "sub2"),
+                ("another@gmail.com", 2, "sub3"),
+            ],
+        )
+        .await;
+
+        let subject = DataSubject::Email("user@gmail.com".into());
+        let user_data = export_user_data(&client, &subject, None)
+            .await
+            .expect("failed to export user data");
+        let chain_ids: Vec<_> = user_data
+            .submissions
+            .iter()
+            .map(|data| data.submission.chain_id)
+            .collect();
+        assert_eq!(vec![1, 2], chain_ids);
+
+        let erased = erase_user_data(&client, &subject, None)
+            .await
+            .expect("failed to erase user data");
+        assert_eq!(2, erased, "invalid number of erased submissions");
+
+        let another_user_data = export_user_data(
+            &client,
+            &DataSubject::Email("another@gmail.com".into()),
+            None,
+        )
+        .await
+        .expect("failed to export user data");
+        assert_eq!(1, another_user_data.submissions.len());
+    }
+}
@@ -20,8 +20,17 @@ async fn init_db<M: MigratorTrait>(name: &str, db_url: Option<String>) -> Databa
     };
     assert!(db_not_created, "db with name {name} already was created",);
 
-    let db_url =
-        db_url.unwrap_or_else(|| std::env::var("DATABASE_URL").expect("no DATABASE_URL env"));
+    // Tests run on in-memory SQLite database if no Postgres database is provided
+    let db_url = match db_url.or_else(|| std::env::var("DATABASE_URL").ok()) {
+        Some(db_url) => db_url,
+        None => {
+            let conn = Database::connect("sqlite::memory:")
+                .await
+                .expect("failed to connect to sqlite");
+            M::up(&conn, None).await.expect("failed to run migrations");
+            return conn;
+        }
+    };
     let url = Url::parse(&db_url).expect("unvalid database url");
     let db_url = url.join("/").unwrap().to_string();
     let raw_conn = Database::connect(db_url)
@@ -2,8 +2,10 @@
 
 pub mod prelude;
 
+pub mod idempotency_keys;
 pub mod rejected_submissions;
 pub mod sea_orm_active_enums;
+pub mod submission_revisions;
 pub mod submissions;
 pub mod users;
 pub mod users_chains;
@@ -38,12 +38,26 @@ pub struct Model {
     pub coin_market_cap_ticker: Option<String>,
     pub coin_gecko_ticker: Option<String>,
     pub defi_llama_ticker: Option<String>,
+    pub blockscout_user_id: Option<String>,
+    pub risk_score: i32,
+    #[sea_orm(column_type = "JsonBinary")]
+    pub risk_reasons: Json,
+    pub token_name: Option<String>,
+    pub token_symbol: Option<String>,
+    pub token_type: Option<String>,
+    pub token_decimals: Option<i32>,
+    pub token_holders: Option<i64>,
+    #[sea_orm(column_type = "JsonBinary")]
+    pub localized_descriptions: Json,
+    pub version: i64,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
     #[sea_orm(has_many = "super::rejected_submissions::Entity")]
     RejectedSubmissions,
+    #[sea_orm(has_many = "super::submission_revisions::Entity")]
+    SubmissionRevisions,
     #[sea_orm(has_many = "super::waiting_for_update_submissions::Entity")]
     WaitingForUpdateSubmissions,
 }
@@ -54,6 +68,12 @@ impl Related<super::rejected_submissions::Entity> for Entity {
     }
 }
 
+impl Related<super::submission_revisions::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SubmissionRevisions.def()
+    }
+}
+
 impl Related<super::waiting_for_update_submissions::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::WaitingForUpdateSubmissions.def()
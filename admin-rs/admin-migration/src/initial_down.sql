@@ -1,8 +0,0 @@
-DROP TRIGGER set_timestamp ON submissions;
-
-DROP TABLE "rejected_submissions";
-DROP TABLE "waiting_for_update_submissions";
-DROP TABLE "submissions";
-DROP TABLE "users_chains";
-DROP TABLE "users";
-DROP TYPE "submission_status";
\ No newline at end of file
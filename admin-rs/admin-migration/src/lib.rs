@@ -1,9 +1,17 @@
 pub use sea_orm_migration::prelude::*;
-use sea_orm_migration::sea_orm::{ConnectionTrait, Statement, TransactionTrait};
+use sea_orm_migration::sea_orm::{Statement, TransactionTrait};
 
 mod m20220101_000001_create_table;
 mod m20230517_124955_insert_default_admin;
 mod m20230808_151142_add_delete_cascade_subm;
+mod m20231018_101500_submissions_add_blockscout_user_id;
+mod m20231020_093000_submissions_search_indexes;
+mod m20231022_110000_submissions_add_risk_score;
+mod m20231024_100000_submissions_add_token_metadata;
+mod m20231026_120000_create_submission_revisions;
+mod m20231028_090000_create_idempotency_keys;
+mod m20231030_100000_submissions_add_localized_descriptions;
+mod m20231101_100000_submissions_add_version;
 
 pub struct Migrator;
 
@@ -14,6 +22,14 @@ impl MigratorTrait for Migrator {
             Box::new(m20220101_000001_create_table::Migration),
             Box::new(m20230517_124955_insert_default_admin::Migration),
             Box::new(m20230808_151142_add_delete_cascade_subm::Migration),
+            Box::new(m20231018_101500_submissions_add_blockscout_user_id::Migration),
+            Box::new(m20231020_093000_submissions_search_indexes::Migration),
+            Box::new(m20231022_110000_submissions_add_risk_score::Migration),
+            Box::new(m20231024_100000_submissions_add_token_metadata::Migration),
+            Box::new(m20231026_120000_create_submission_revisions::Migration),
+            Box::new(m20231028_090000_create_idempotency_keys::Migration),
+            Box::new(m20231030_100000_submissions_add_localized_descriptions::Migration),
+            Box::new(m20231101_100000_submissions_add_version::Migration),
         ]
     }
 }
@@ -24,7 +40,10 @@ pub async fn from_sql(
     content: &str,
     post_stmnts: Vec<&str>,
 ) -> Result<(), DbErr> {
-    let stmnts: Vec<&str> = content.split(';').collect();
+    let stmnts: Vec<&str> = content
+        .split(';')
+        .filter(|st| !st.trim().is_empty())
+        .collect();
     let txn = manager.get_connection().begin().await?;
     for st in pre_stmnts.into_iter().chain(stmnts).chain(post_stmnts) {
         txn.execute(Statement::from_string(
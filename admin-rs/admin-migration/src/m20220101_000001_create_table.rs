@@ -1,4 +1,8 @@
-use sea_orm_migration::prelude::*;
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, DbBackend},
+    sea_query::extension::postgres::Type,
+};
 
 #[derive(DeriveMigrationName)]
 pub struct Migration;
@@ -6,34 +10,427 @@ pub struct Migration;
 #[async_trait::async_trait]
 impl MigrationTrait for Migration {
     async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
-        // CREATE FUNCTION syntax contains ; symbols, so
-        // crate::from_sql function will fail to parse it,
-        // therefore we need to pass already parsed function statement
-        let create_function = r#"
-        CREATE OR REPLACE FUNCTION trigger_set_timestamp()   
-        RETURNS TRIGGER AS $$
-        BEGIN
-            NEW.updated_at = now();
-            RETURN NEW;
-        END;
-        $$ language 'plpgsql';"#;
-        crate::from_sql(
-            manager,
-            vec![create_function],
-            include_str!("initial_up.sql"),
-            vec![],
-        )
-        .await
+        // Other backends store enumerations as text
+        if manager.get_database_backend() == DbBackend::Postgres {
+            manager
+                .create_type(
+                    Type::create()
+                        .as_enum(SubmissionStatus::Type)
+                        .values([
+                            SubmissionStatus::InProcess,
+                            SubmissionStatus::WaitingForUpdate,
+                            SubmissionStatus::Approved,
+                            SubmissionStatus::Rejected,
+                        ])
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Users::Table)
+                    .col(
+                        ColumnDef::new(Users::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Users::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(SimpleExpr::Custom("CURRENT_TIMESTAMP".into())),
+                    )
+                    .col(
+                        ColumnDef::new(Users::Email)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(Users::Password).string().not_null())
+                    .col(
+                        ColumnDef::new(Users::IsSuperuser)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(UsersChains::Table)
+                    .col(ColumnDef::new(UsersChains::UserId).big_integer().not_null())
+                    .col(
+                        ColumnDef::new(UsersChains::ChainId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(UsersChains::UserId)
+                            .col(UsersChains::ChainId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("users_chains_user_id_fkey")
+                            .from(UsersChains::Table, UsersChains::UserId)
+                            .to(Users::Table, Users::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Submissions::Table)
+                    .col(
+                        ColumnDef::new(Submissions::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Submissions::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(SimpleExpr::Custom("CURRENT_TIMESTAMP".into())),
+                    )
+                    .col(
+                        ColumnDef::new(Submissions::UpdatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(SimpleExpr::Custom("CURRENT_TIMESTAMP".into())),
+                    )
+                    .col(
+                        ColumnDef::new(Submissions::ChainId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Submissions::TokenAddress)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Submissions::Status)
+                            .enumeration(
+                                SubmissionStatus::Type,
+                                [
+                                    SubmissionStatus::InProcess,
+                                    SubmissionStatus::WaitingForUpdate,
+                                    SubmissionStatus::Approved,
+                                    SubmissionStatus::Rejected,
+                                ],
+                            )
+                            .not_null()
+                            .default("in_process"),
+                    )
+                    .col(
+                        ColumnDef::new(Submissions::BlockscoutUserEmail)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Submissions::RequesterName)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Submissions::RequesterEmail)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Submissions::ProjectName).string())
+                    .col(
+                        ColumnDef::new(Submissions::ProjectWebsite)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Submissions::ProjectEmail)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Submissions::IconUrl).string().not_null())
+                    .col(
+                        ColumnDef::new(Submissions::ProjectDescription)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Submissions::ProjectSector).string())
+                    .col(ColumnDef::new(Submissions::Comment).string())
+                    .col(ColumnDef::new(Submissions::Docs).string())
+                    .col(ColumnDef::new(Submissions::Github).string())
+                    .col(ColumnDef::new(Submissions::Telegram).string())
+                    .col(ColumnDef::new(Submissions::Linkedin).string())
+                    .col(ColumnDef::new(Submissions::Discord).string())
+                    .col(ColumnDef::new(Submissions::Slack).string())
+                    .col(ColumnDef::new(Submissions::Twitter).string())
+                    .col(ColumnDef::new(Submissions::OpenSea).string())
+                    .col(ColumnDef::new(Submissions::Facebook).string())
+                    .col(ColumnDef::new(Submissions::Medium).string())
+                    .col(ColumnDef::new(Submissions::Reddit).string())
+                    .col(ColumnDef::new(Submissions::Support).string())
+                    .col(ColumnDef::new(Submissions::CoinMarketCapTicker).string())
+                    .col(ColumnDef::new(Submissions::CoinGeckoTicker).string())
+                    .col(ColumnDef::new(Submissions::DefiLlamaTicker).string())
+                    .to_owned(),
+            )
+            .await?;
+
+        // Constraints of existing databases are altered to cascade
+        // by `m20230808_151142_add_delete_cascade_subm`
+        manager
+            .create_table(
+                Table::create()
+                    .table(WaitingForUpdateSubmissions::Table)
+                    .col(
+                        ColumnDef::new(WaitingForUpdateSubmissions::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(WaitingForUpdateSubmissions::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(SimpleExpr::Custom("CURRENT_TIMESTAMP".into())),
+                    )
+                    .col(
+                        ColumnDef::new(WaitingForUpdateSubmissions::SubmissionId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WaitingForUpdateSubmissions::AdminComments)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(WaitingForUpdateSubmissions::Addressed)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("waiting_for_update_submissions_submission_id_fkey")
+                            .from(
+                                WaitingForUpdateSubmissions::Table,
+                                WaitingForUpdateSubmissions::SubmissionId,
+                            )
+                            .to(Submissions::Table, Submissions::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(RejectedSubmissions::Table)
+                    .col(
+                        ColumnDef::new(RejectedSubmissions::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(RejectedSubmissions::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(SimpleExpr::Custom("CURRENT_TIMESTAMP".into())),
+                    )
+                    .col(
+                        ColumnDef::new(RejectedSubmissions::SubmissionId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RejectedSubmissions::Reason)
+                            .string()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("rejected_submissions_submission_id_fkey")
+                            .from(
+                                RejectedSubmissions::Table,
+                                RejectedSubmissions::SubmissionId,
+                            )
+                            .to(Submissions::Table, Submissions::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        create_set_timestamp_trigger(manager).await
     }
 
     async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
-        let drop_function = "DROP FUNCTION trigger_set_timestamp;";
-        crate::from_sql(
-            manager,
-            vec![],
-            include_str!("initial_down.sql"),
-            vec![drop_function],
-        )
-        .await
+        // Dropping the tables drops the trigger as well
+        for table in [
+            RejectedSubmissions::Table.into_iden(),
+            WaitingForUpdateSubmissions::Table.into_iden(),
+            Submissions::Table.into_iden(),
+            UsersChains::Table.into_iden(),
+            Users::Table.into_iden(),
+        ] {
+            manager
+                .drop_table(Table::drop().table(table).to_owned())
+                .await?;
+        }
+
+        if manager.get_database_backend() == DbBackend::Postgres {
+            manager
+                .get_connection()
+                .execute_unprepared("DROP FUNCTION trigger_set_timestamp;")
+                .await?;
+            manager
+                .drop_type(Type::drop().name(SubmissionStatus::Type).to_owned())
+                .await?;
+        }
+        Ok(())
     }
 }
+
+/// Keeps `submissions.updated_at` up to date on every update.
+/// Triggers are not covered by the query builder, so their definitions
+/// are the only statements written for each backend separately.
+async fn create_set_timestamp_trigger(manager: &SchemaManager<'_>) -> Result<(), DbErr> {
+    let statements: &[&str] = match manager.get_database_backend() {
+        DbBackend::Postgres => &[
+            r#"
+            CREATE OR REPLACE FUNCTION trigger_set_timestamp()
+            RETURNS TRIGGER AS $$
+            BEGIN
+                NEW.updated_at = now();
+                RETURN NEW;
+            END;
+            $$ language 'plpgsql';"#,
+            r#"
+            CREATE TRIGGER set_timestamp
+            BEFORE UPDATE ON submissions
+            FOR EACH ROW
+            EXECUTE PROCEDURE trigger_set_timestamp();"#,
+        ],
+        // SQLite rows can't be modified in place, so the row is updated once more.
+        // `CURRENT_TIMESTAMP` has second precision only, which would make
+        // consecutive updates indistinguishable.
+        DbBackend::Sqlite => &[r#"
+            CREATE TRIGGER set_timestamp
+            AFTER UPDATE ON submissions
+            FOR EACH ROW WHEN NEW.updated_at = OLD.updated_at
+            BEGIN
+                UPDATE submissions
+                SET updated_at = strftime('%Y-%m-%d %H:%M:%f', 'now')
+                WHERE id = NEW.id;
+            END;"#],
+        DbBackend::MySql => return Err(DbErr::Migration("MySQL backend is not supported".into())),
+    };
+    for statement in statements {
+        manager
+            .get_connection()
+            .execute_unprepared(statement)
+            .await?;
+    }
+    Ok(())
+}
+
+#[derive(Iden)]
+enum SubmissionStatus {
+    #[iden = "submission_status"]
+    Type,
+    InProcess,
+    WaitingForUpdate,
+    Approved,
+    Rejected,
+}
+
+#[derive(Iden)]
+enum Users {
+    Table,
+    Id,
+    CreatedAt,
+    Email,
+    Password,
+    IsSuperuser,
+}
+
+#[derive(Iden)]
+enum UsersChains {
+    Table,
+    UserId,
+    ChainId,
+}
+
+/// There should be at most one submission for given token address
+/// that is `in_process` or `waiting_for_update`.
+#[derive(Iden)]
+enum Submissions {
+    Table,
+    Id,
+    CreatedAt,
+    UpdatedAt,
+    ChainId,
+    TokenAddress,
+    Status,
+    BlockscoutUserEmail,
+    RequesterName,
+    RequesterEmail,
+    ProjectName,
+    ProjectWebsite,
+    ProjectEmail,
+    IconUrl,
+    ProjectDescription,
+    ProjectSector,
+    Comment,
+    Docs,
+    Github,
+    Telegram,
+    Linkedin,
+    Discord,
+    Slack,
+    Twitter,
+    OpenSea,
+    Facebook,
+    Medium,
+    Reddit,
+    Support,
+    CoinMarketCapTicker,
+    CoinGeckoTicker,
+    DefiLlamaTicker,
+}
+
+/// At any given moment only one non-addressed waiting
+/// should occur for any submission.
+#[derive(Iden)]
+enum WaitingForUpdateSubmissions {
+    Table,
+    Id,
+    CreatedAt,
+    SubmissionId,
+    AdminComments,
+    Addressed,
+}
+
+#[derive(Iden)]
+enum RejectedSubmissions {
+    Table,
+    Id,
+    CreatedAt,
+    SubmissionId,
+    Reason,
+}
@@ -1,4 +1,4 @@
-use sea_orm_migration::prelude::*;
+use sea_orm_migration::{prelude::*, sea_orm::DbBackend};
 
 #[derive(DeriveMigrationName)]
 pub struct Migration;
@@ -6,36 +6,71 @@ pub struct Migration;
 #[async_trait::async_trait]
 impl MigrationTrait for Migration {
     async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
-        let sql = r#"
-        ALTER TABLE "waiting_for_update_submissions"
-        DROP CONSTRAINT IF EXISTS "waiting_for_update_submissions_submission_id_fkey";
-        ALTER TABLE "waiting_for_update_submissions"
-        ADD CONSTRAINT "waiting_for_update_submissions_submission_id_fkey"
-        FOREIGN KEY ("submission_id") REFERENCES "submissions" ("id") ON DELETE CASCADE;
-
-        ALTER TABLE "rejected_submissions"
-        DROP CONSTRAINT IF EXISTS "rejected_submissions_submission_id_fkey";
-        ALTER TABLE "rejected_submissions"
-        ADD CONSTRAINT "rejected_submissions_submission_id_fkey"
-        FOREIGN KEY ("submission_id") REFERENCES "submissions" ("id") ON DELETE CASCADE;
-        "#;
-        crate::from_sql(manager, vec![], sql, vec![]).await
+        replace_submission_foreign_keys(manager, ForeignKeyAction::Cascade).await
     }
 
     async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
-        let sql = r#"
-        ALTER TABLE "waiting_for_update_submissions"
-        DROP CONSTRAINT IF EXISTS "waiting_for_update_submissions_submission_id_fkey";
-        ALTER TABLE "waiting_for_update_submissions"
-        ADD CONSTRAINT "waiting_for_update_submissions_submission_id_fkey"
-        FOREIGN KEY ("submission_id") REFERENCES "submissions" ("id");
+        replace_submission_foreign_keys(manager, ForeignKeyAction::NoAction).await
+    }
+}
 
-        ALTER TABLE "rejected_submissions"
-        DROP CONSTRAINT IF EXISTS "rejected_submissions_submission_id_fkey";
-        ALTER TABLE "rejected_submissions"
-        ADD CONSTRAINT "rejected_submissions_submission_id_fkey"
-        FOREIGN KEY ("submission_id") REFERENCES "submissions" ("id");
-        "#;
-        crate::from_sql(manager, vec![], sql, vec![]).await
+async fn replace_submission_foreign_keys(
+    manager: &SchemaManager<'_>,
+    on_delete: ForeignKeyAction,
+) -> Result<(), DbErr> {
+    // SQLite does not support altering constraints. Its databases
+    // are always created with cascades by the initial migration.
+    if manager.get_database_backend() == DbBackend::Sqlite {
+        return Ok(());
     }
+    for (name, table, column) in [
+        (
+            "waiting_for_update_submissions_submission_id_fkey",
+            WaitingForUpdateSubmissions::Table.into_iden(),
+            WaitingForUpdateSubmissions::SubmissionId.into_iden(),
+        ),
+        (
+            "rejected_submissions_submission_id_fkey",
+            RejectedSubmissions::Table.into_iden(),
+            RejectedSubmissions::SubmissionId.into_iden(),
+        ),
+    ] {
+        manager
+            .drop_foreign_key(
+                ForeignKey::drop()
+                    .name(name)
+                    .table(table.clone())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name(name)
+                    .from(table, column)
+                    .to(Submissions::Table, Submissions::Id)
+                    .on_delete(on_delete)
+                    .to_owned(),
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+#[derive(Iden)]
+enum Submissions {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum WaitingForUpdateSubmissions {
+    Table,
+    SubmissionId,
+}
+
+#[derive(Iden)]
+enum RejectedSubmissions {
+    Table,
+    SubmissionId,
 }
@@ -0,0 +1,57 @@
+use sea_orm_migration::prelude::*;
+
+// Existing submissions keep `NULL` user id, as it is not known to the database.
+// It is backfilled when the submission owner is authenticated next time. Submissions of
+// users who do not sign in anymore are backfilled with the `BackfillUserIdsAdmin` rpc.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                sea_query::Table::alter()
+                    .table(Submissions::Table)
+                    .add_column(ColumnDef::new(Submissions::BlockscoutUserId).string())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("submissions_blockscout_user_id_chain_id_index")
+                    .table(Submissions::Table)
+                    .col(Submissions::BlockscoutUserId)
+                    .col(Submissions::ChainId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("submissions_blockscout_user_id_chain_id_index")
+                    .table(Submissions::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                sea_query::Table::alter()
+                    .table(Submissions::Table)
+                    .drop_column(Submissions::BlockscoutUserId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Submissions {
+    Table,
+    BlockscoutUserId,
+    ChainId,
+}
@@ -0,0 +1,75 @@
+use sea_orm_migration::{prelude::*, sea_orm::DbBackend};
+
+// Indexes used by reviewers search. Trigram indexes allow substring
+// search (`ILIKE '%...%'`), while the full-text one covers project texts.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_index(
+                Index::create()
+                    .name("submissions_created_at_index")
+                    .table(Submissions::Table)
+                    .col(Submissions::CreatedAt)
+                    .to_owned(),
+            )
+            .await?;
+        // Operator classes and expression indexes are not covered by the query builder.
+        // Search on other backends works without them, only slower.
+        if manager.get_database_backend() != DbBackend::Postgres {
+            return Ok(());
+        }
+        let sql = r#"
+        CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+        CREATE INDEX "submissions_project_name_trgm_index"
+        ON "submissions" USING gin ("project_name" gin_trgm_ops);
+
+        CREATE INDEX "submissions_token_address_trgm_index"
+        ON "submissions" USING gin ("token_address" gin_trgm_ops);
+
+        CREATE INDEX "submissions_requester_email_trgm_index"
+        ON "submissions" USING gin ("requester_email" gin_trgm_ops);
+
+        CREATE INDEX "submissions_project_website_trgm_index"
+        ON "submissions" USING gin ("project_website" gin_trgm_ops);
+
+        CREATE INDEX "submissions_project_text_search_index"
+        ON "submissions" USING gin (
+            to_tsvector('simple', coalesce("project_name", '') || ' ' || "project_description")
+        );
+        "#;
+        crate::from_sql(manager, vec![], sql, vec![]).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() == DbBackend::Postgres {
+            // The extension is kept, as it may be used outside of the service
+            let sql = r#"
+            DROP INDEX IF EXISTS "submissions_project_text_search_index";
+            DROP INDEX IF EXISTS "submissions_project_website_trgm_index";
+            DROP INDEX IF EXISTS "submissions_requester_email_trgm_index";
+            DROP INDEX IF EXISTS "submissions_token_address_trgm_index";
+            DROP INDEX IF EXISTS "submissions_project_name_trgm_index";
+            "#;
+            crate::from_sql(manager, vec![], sql, vec![]).await?;
+        }
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("submissions_created_at_index")
+                    .table(Submissions::Table)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Submissions {
+    Table,
+    CreatedAt,
+}
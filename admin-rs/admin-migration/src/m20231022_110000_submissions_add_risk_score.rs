@@ -0,0 +1,108 @@
+use sea_orm_migration::{prelude::*, sea_orm::DbBackend};
+
+// Existing submissions are considered not risky until they are updated
+// and assessed for the first time.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                sea_query::Table::alter()
+                    .table(Submissions::Table)
+                    .add_column(
+                        ColumnDef::new(Submissions::RiskScore)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                sea_query::Table::alter()
+                    .table(Submissions::Table)
+                    .add_column(
+                        ColumnDef::new(Submissions::RiskReasons)
+                            .json_binary()
+                            .not_null()
+                            .default("[]"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("submissions_risk_score_id_index")
+                    .table(Submissions::Table)
+                    .col((Submissions::RiskScore, IndexOrder::Desc))
+                    .col((Submissions::Id, IndexOrder::Desc))
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("submissions_icon_url_index")
+                    .table(Submissions::Table)
+                    .col(Submissions::IconUrl)
+                    .to_owned(),
+            )
+            .await?;
+        // See `m20231020_093000_submissions_search_indexes`
+        if manager.get_database_backend() == DbBackend::Postgres {
+            let sql = r#"
+            CREATE INDEX "submissions_project_description_trgm_index"
+            ON "submissions" USING gin ("project_description" gin_trgm_ops);
+            "#;
+            crate::from_sql(manager, vec![], sql, vec![]).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() == DbBackend::Postgres {
+            let sql = r#"
+            DROP INDEX IF EXISTS "submissions_project_description_trgm_index";
+            "#;
+            crate::from_sql(manager, vec![], sql, vec![]).await?;
+        }
+        for index in [
+            "submissions_icon_url_index",
+            "submissions_risk_score_id_index",
+        ] {
+            manager
+                .drop_index(
+                    Index::drop()
+                        .name(index)
+                        .table(Submissions::Table)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        for column in [Submissions::RiskReasons, Submissions::RiskScore] {
+            manager
+                .alter_table(
+                    sea_query::Table::alter()
+                        .table(Submissions::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Submissions {
+    Table,
+    Id,
+    IconUrl,
+    RiskScore,
+    RiskReasons,
+}
@@ -0,0 +1,64 @@
+use sea_orm_migration::prelude::*;
+
+// On-chain token metadata is captured from Blockscout when a submission
+// is created or updated. Existing submissions keep `NULL` values until then.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite adds a single column per statement
+        for mut column in [
+            ColumnDef::new(Submissions::TokenName).string().to_owned(),
+            ColumnDef::new(Submissions::TokenSymbol).string().to_owned(),
+            ColumnDef::new(Submissions::TokenType).string().to_owned(),
+            ColumnDef::new(Submissions::TokenDecimals)
+                .integer()
+                .to_owned(),
+            ColumnDef::new(Submissions::TokenHolders)
+                .big_integer()
+                .to_owned(),
+        ] {
+            manager
+                .alter_table(
+                    sea_query::Table::alter()
+                        .table(Submissions::Table)
+                        .add_column(&mut column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [
+            Submissions::TokenHolders,
+            Submissions::TokenDecimals,
+            Submissions::TokenType,
+            Submissions::TokenSymbol,
+            Submissions::TokenName,
+        ] {
+            manager
+                .alter_table(
+                    sea_query::Table::alter()
+                        .table(Submissions::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Submissions {
+    Table,
+    TokenName,
+    TokenSymbol,
+    TokenType,
+    TokenDecimals,
+    TokenHolders,
+}
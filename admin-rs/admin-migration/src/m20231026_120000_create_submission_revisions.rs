@@ -0,0 +1,88 @@
+use sea_orm_migration::prelude::*;
+
+// Snapshots of user editable submission fields taken before each user update.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SubmissionRevisions::Table)
+                    .col(
+                        ColumnDef::new(SubmissionRevisions::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SubmissionRevisions::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(SimpleExpr::Custom("CURRENT_TIMESTAMP".into())),
+                    )
+                    .col(
+                        ColumnDef::new(SubmissionRevisions::SubmissionId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SubmissionRevisions::Revision)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SubmissionRevisions::Data)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(
+                                SubmissionRevisions::Table,
+                                SubmissionRevisions::SubmissionId,
+                            )
+                            .to(Submissions::Table, Submissions::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .unique()
+                    .name("submission_revisions_submission_id_revision_index")
+                    .table(SubmissionRevisions::Table)
+                    .col(SubmissionRevisions::SubmissionId)
+                    .col(SubmissionRevisions::Revision)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SubmissionRevisions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Submissions {
+    Table,
+    Id,
+}
+
+#[derive(Iden)]
+enum SubmissionRevisions {
+    Table,
+    Id,
+    CreatedAt,
+    SubmissionId,
+    Revision,
+    Data,
+}
@@ -0,0 +1,42 @@
+use sea_orm_migration::prelude::*;
+
+// Localized project names and descriptions keyed by BCP-47 language tags.
+// `project_description` keeps the default (usually English) description.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                sea_query::Table::alter()
+                    .table(Submissions::Table)
+                    .add_column(
+                        ColumnDef::new(Submissions::LocalizedDescriptions)
+                            .json_binary()
+                            .not_null()
+                            .default("[]"),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                sea_query::Table::alter()
+                    .table(Submissions::Table)
+                    .drop_column(Submissions::LocalizedDescriptions)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Submissions {
+    Table,
+    LocalizedDescriptions,
+}
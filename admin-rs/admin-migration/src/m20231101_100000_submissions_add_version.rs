@@ -0,0 +1,112 @@
+use sea_orm_migration::{
+    prelude::*,
+    sea_orm::{ConnectionTrait, DbBackend},
+};
+
+// Version of the submission, incremented by the `set_timestamp` trigger on every update.
+// Unlike `updated_at`, it changes even if the row is updated twice within the clock precision,
+// so it is used as a precondition of the updates.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                sea_query::Table::alter()
+                    .table(Submissions::Table)
+                    .add_column(
+                        ColumnDef::new(Submissions::Version)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        let statements: &[&str] = match manager.get_database_backend() {
+            DbBackend::Postgres => &[r#"
+                CREATE OR REPLACE FUNCTION trigger_set_timestamp()
+                RETURNS TRIGGER AS $$
+                BEGIN
+                    NEW.updated_at = now();
+                    NEW.version = OLD.version + 1;
+                    RETURN NEW;
+                END;
+                $$ language 'plpgsql';"#],
+            DbBackend::Sqlite => &[
+                "DROP TRIGGER set_timestamp;",
+                r#"
+                CREATE TRIGGER set_timestamp
+                AFTER UPDATE ON submissions
+                FOR EACH ROW WHEN NEW.version = OLD.version
+                BEGIN
+                    UPDATE submissions
+                    SET updated_at = strftime('%Y-%m-%d %H:%M:%f', 'now'),
+                        version = OLD.version + 1
+                    WHERE id = NEW.id;
+                END;"#,
+            ],
+            DbBackend::MySql => {
+                return Err(DbErr::Migration("MySQL backend is not supported".into()))
+            }
+        };
+        for statement in statements {
+            manager
+                .get_connection()
+                .execute_unprepared(statement)
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let statements: &[&str] = match manager.get_database_backend() {
+            DbBackend::Postgres => &[r#"
+                CREATE OR REPLACE FUNCTION trigger_set_timestamp()
+                RETURNS TRIGGER AS $$
+                BEGIN
+                    NEW.updated_at = now();
+                    RETURN NEW;
+                END;
+                $$ language 'plpgsql';"#],
+            // The column could not be dropped while it is used by the trigger
+            DbBackend::Sqlite => &[
+                "DROP TRIGGER set_timestamp;",
+                r#"
+                CREATE TRIGGER set_timestamp
+                AFTER UPDATE ON submissions
+                FOR EACH ROW WHEN NEW.updated_at = OLD.updated_at
+                BEGIN
+                    UPDATE submissions
+                    SET updated_at = strftime('%Y-%m-%d %H:%M:%f', 'now')
+                    WHERE id = NEW.id;
+                END;"#,
+            ],
+            DbBackend::MySql => {
+                return Err(DbErr::Migration("MySQL backend is not supported".into()))
+            }
+        };
+        for statement in statements {
+            manager
+                .get_connection()
+                .execute_unprepared(statement)
+                .await?;
+        }
+        manager
+            .alter_table(
+                sea_query::Table::alter()
+                    .table(Submissions::Table)
+                    .drop_column(Submissions::Version)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum Submissions {
+    Table,
+    Version,
+}
@@ -8,9 +8,12 @@ fn compile(
     includes: &[impl AsRef<Path>],
     generator: Box<dyn ServiceGenerator>,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR")?);
     let mut config = Config::new();
     config
         .service_generator(generator)
+        // Used by the gRPC server reflection service
+        .file_descriptor_set_path(out_dir.join("admin_descriptor.bin"))
         .compile_well_known_types()
         .protoc_arg("--openapiv2_out=swagger")
         .protoc_arg("--openapiv2_opt")
@@ -34,6 +37,22 @@ fn compile(
             ".blockscout.admin.v1.TokenInfoSubmission.updated_at",
             "#[serde(default)]"
         )
+        .field_attribute(
+            ".blockscout.admin.v1.TokenInfoSubmission.etag",
+            "#[serde(default)]"
+        )
+        .field_attribute(
+            ".blockscout.admin.v1.TokenInfoSubmission.localized_descriptions",
+            "#[serde(default)]"
+        )
+        .field_attribute(
+            ".blockscout.admin.v1.UpdateTokenInfoSubmissionRequest.etag",
+            "#[serde(default)]"
+        )
+        .field_attribute(
+            ".blockscout.admin.v1.UpdateTokenInfoSubmissionRequest.update_mask",
+            "#[serde(default)]"
+        )
         .field_attribute(
             ".blockscout.admin.v1.HealthCheckRequest.service",
             "#[serde(default)]"
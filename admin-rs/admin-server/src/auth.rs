@@ -0,0 +1,76 @@
+use crate::{
+    errors::{self, ErrorBuilder, Reason},
+    settings::ChainConfig,
+};
+use admin_core::submissions;
+use tonic::{metadata::MetadataMap, Code, Status};
+
+pub type AuthCache = service_common::auth::AuthCache<AuthenticatedUser>;
+
+#[derive(Debug, Clone)]
+pub struct AuthenticatedUser {
+    pub id: String,
+    pub email: String,
+}
+
+impl From<blockscout_auth::AuthSuccess> for AuthenticatedUser {
+    fn from(value: blockscout_auth::AuthSuccess) -> Self {
+        Self {
+            id: value.id.to_string(),
+            email: value.email,
+        }
+    }
+}
+
+impl From<AuthenticatedUser> for submissions::User {
+    fn from(value: AuthenticatedUser) -> Self {
+        Self::new(value.id, value.email)
+    }
+}
+
+/// Authenticates the user against the chain blockscout instance.
+/// Results are looked up in the cache first.
+pub async fn authenticate(
+    cache: &AuthCache,
+    metadata: &MetadataMap,
+    is_http_safe: bool,
+    chain: &ChainConfig,
+) -> Result<AuthenticatedUser, Status> {
+    service_common::auth::authenticate(
+        cache,
+        metadata,
+        is_http_safe,
+        &chain.url,
+        chain.api_key.as_deref(),
+        map_auth_error,
+    )
+    .await
+}
+
+fn map_auth_error(err: blockscout_auth::Error) -> Status {
+    let message = err.to_string();
+    match err {
+        blockscout_auth::Error::Unauthorized(_) => {
+            ErrorBuilder::new(Code::Unauthenticated, Reason::InvalidCredentials, message).build()
+        }
+        blockscout_auth::Error::Forbidden(_) => {
+            ErrorBuilder::new(Code::PermissionDenied, Reason::Forbidden, message).build()
+        }
+        blockscout_auth::Error::InvalidCsrfToken(_) => {
+            ErrorBuilder::new(Code::InvalidArgument, Reason::InvalidCsrfToken, &message)
+                .field_violation("x-csrf-token", message)
+                .build()
+        }
+        blockscout_auth::Error::InvalidJwt(_) => {
+            ErrorBuilder::new(Code::InvalidArgument, Reason::InvalidCredentials, message).build()
+        }
+        blockscout_auth::Error::BlockscoutApi(_) => ErrorBuilder::new(
+            Code::Unauthenticated,
+            Reason::BlockscoutUnavailable,
+            message,
+        )
+        .retryable()
+        .build(),
+        _ => errors::internal(message),
+    }
+}
@@ -0,0 +1,125 @@
+use admin_core::submissions::Submission;
+use reqwest::StatusCode;
+use serde::Deserialize;
+use std::time::Duration;
+use thiserror::Error;
+use url::Url;
+
+const API_KEY_NAME: &str = "apikey";
+
+pub struct Client {
+    http: reqwest::Client,
+    url: Url,
+    api_key: Option<String>,
+}
+
+/// On-chain token metadata as returned by `/api/v2/tokens/{address}`.
+/// Numeric values may be returned either as strings or as numbers.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct Token {
+    pub name: Option<String>,
+    pub symbol: Option<String>,
+    #[serde(rename = "type")]
+    pub token_type: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_number")]
+    pub decimals: Option<i32>,
+    #[serde(default, deserialize_with = "deserialize_number")]
+    pub holders: Option<i64>,
+}
+
+impl Token {
+    pub fn apply(self, submission: &mut Submission) {
+        submission.token_name = self.name;
+        submission.token_symbol = self.symbol;
+        submission.token_type = self.token_type;
+        submission.token_decimals = self.decimals;
+        submission.token_holders = self.holders;
+    }
+}
+
+fn deserialize_number<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: std::str::FromStr,
+{
+    let value: Option<serde_json::Value> = Option::deserialize(deserializer)?;
+    let value = match value {
+        Some(serde_json::Value::String(value)) => value,
+        Some(serde_json::Value::Number(value)) => value.to_string(),
+        _ => return Ok(None),
+    };
+    value
+        .parse()
+        .map(Some)
+        .map_err(|_| serde::de::Error::custom(format!("invalid number: {value}")))
+}
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("address {0} is not a token")]
+    NotAToken(String),
+    #[error("unexpected internal error: {0}")]
+    Internal(String),
+}
+
+impl Client {
+    pub fn new(url: Url, api_key: Option<String>) -> Self {
+        let http = reqwest::Client::new();
+        Self { http, url, api_key }
+    }
+
+    pub async fn get_token(&self, token_address: &str) -> Result<Token, Error> {
+        let url = self
+            .url
+            .join(&format!("/api/v2/tokens/{token_address}"))
+            .map_err(|e| Error::Internal(e.to_string()))?;
+        let mut request = self.http.get(url);
+        if let Some(api_key) = &self.api_key {
+            request = request.query(&[(API_KEY_NAME, api_key)]);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| Error::Internal(e.to_string()))?;
+        match response.status() {
+            StatusCode::OK => response
+                .json()
+                .await
+                .map_err(|e| Error::Internal(e.to_string())),
+            // Blockscout returns 422 for strings which are not valid addresses
+            StatusCode::NOT_FOUND | StatusCode::UNPROCESSABLE_ENTITY => {
+                Err(Error::NotAToken(token_address.to_string()))
+            }
+            _ => {
+                let error = response
+                    .text()
+                    .await
+                    .map_err(|e| Error::Internal(e.to_string()))?;
+                tracing::warn!(error = ?error, "invalid response from blockscout");
+                Err(Error::Internal("failed to fetch token".to_string()))
+            }
+        }
+    }
+
+    /// Checks that the blockscout instance is reachable and reports itself healthy.
+    pub async fn check_health(&self, timeout: Duration) -> Result<(), Error> {
+        let url = self
+            .url
+            .join("/api/v1/health")
+            .map_err(|e| Error::Internal(e.to_string()))?;
+        let response = self
+            .http
+            .get(url)
+            .timeout(timeout)
+            .send()
+            .await
+            .map_err(|e| Error::Internal(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(Error::Internal(format!(
+                "unexpected health status: {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,170 @@
+use crate::{
+    blockscout, contracts_info,
+    settings::{modified_at, ChainConfig, ChainsSettings},
+};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime},
+};
+use url::Url;
+
+pub struct Chain {
+    pub config: ChainConfig,
+    pub contracts_info_client: contracts_info::Client,
+    pub blockscout_client: blockscout::Client,
+}
+
+impl Chain {
+    fn new(config: ChainConfig, default_contracts_info_addr: &Url) -> Self {
+        let contracts_info_url = config
+            .contracts_info_url
+            .clone()
+            .unwrap_or_else(|| default_contracts_info_addr.clone());
+        let contracts_info_client =
+            contracts_info::Client::new(contracts_info_url, config.contracts_info_api_key.clone());
+        let blockscout_client = blockscout::Client::new(config.url.clone(), config.api_key.clone());
+        Self {
+            config,
+            contracts_info_client,
+            blockscout_client,
+        }
+    }
+}
+
+/// Set of chains the service works with. The whole set
+/// is replaced at once when the chains config is reloaded,
+/// so a request always observes a consistent configuration.
+pub struct Chains {
+    chains: RwLock<Arc<HashMap<i64, Arc<Chain>>>>,
+    default_contracts_info_addr: Url,
+}
+
+impl Chains {
+    pub fn new(settings: ChainsSettings, default_contracts_info_addr: Url) -> Self {
+        let chains = Self::build(settings, &default_contracts_info_addr);
+        Self {
+            chains: RwLock::new(Arc::new(chains)),
+            default_contracts_info_addr,
+        }
+    }
+
+    pub fn get(&self, chain_id: i64) -> Option<Arc<Chain>> {
+        self.chains
+            .read()
+            .expect("chains lock is poisoned")
+            .get(&chain_id)
+            .cloned()
+    }
+
+    /// Returns a snapshot of all currently configured chains.
+    pub fn all(&self) -> Arc<HashMap<i64, Arc<Chain>>> {
+        self.chains.read().expect("chains lock is poisoned").clone()
+    }
+
+    pub fn replace(&self, settings: ChainsSettings) {
+        let chains = Arc::new(Self::build(settings, &self.default_contracts_info_addr));
+        *self.chains.write().expect("chains lock is poisoned") = chains;
+    }
+
+    fn build(
+        settings: ChainsSettings,
+        default_contracts_info_addr: &Url,
+    ) -> HashMap<i64, Arc<Chain>> {
+        settings
+            .networks
+            .into_iter()
+            .map(|(chain_id, config)| {
+                (
+                    chain_id,
+                    Arc::new(Chain::new(config, default_contracts_info_addr)),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Periodically checks the chains config file for modifications made after
+/// `loaded_at` and replaces the chains if the file has been changed.
+/// Invalid configs are ignored and the previous chains are kept.
+pub async fn watch_chains_config(
+    chains: Arc<Chains>,
+    path: PathBuf,
+    loaded_at: SystemTime,
+    interval: Duration,
+) {
+    let mut last_modified = Some(loaded_at);
+    let mut interval = tokio::time::interval(interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        let modified = modified_at(&path);
+        if modified.is_none() || modified == last_modified {
+            continue;
+        }
+        last_modified = modified;
+
+        match ChainsSettings::new(path.as_path()) {
+            Ok(settings) => {
+                let chain_ids = settings.networks.keys().collect::<Vec<_>>();
+                tracing::info!(path = ?path, chain_ids = ?chain_ids, "reloaded chains config");
+                chains.replace(settings);
+            }
+            Err(err) => {
+                tracing::error!(
+                    path = ?path,
+                    err = ?err,
+                    "failed to reload chains config; previous config is kept"
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs::File, io::Write};
+
+    fn write_config(path: &std::path::Path, chain_id: i64, modified: SystemTime) {
+        let config = serde_json::json!({
+            "networks": { chain_id.to_string(): { "url": "http://127.0.0.1:1" } }
+        });
+        let mut file = File::create(path).unwrap();
+        file.write_all(config.to_string().as_bytes()).unwrap();
+        file.set_modified(modified).unwrap();
+    }
+
+    #[tokio::test]
+    async fn reloads_config_modified_before_watching() {
+        let file = tempfile::Builder::new().suffix(".json").tempfile().unwrap();
+        let path = file.path().to_path_buf();
+        let loaded_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        write_config(&path, 1, loaded_at);
+        let settings = ChainsSettings::new(&path).unwrap();
+        assert_eq!(Some(loaded_at), settings.modified_at);
+        let chains = Arc::new(Chains::new(
+            settings,
+            Url::parse("http://127.0.0.1:1").unwrap(),
+        ));
+
+        // The file is modified after loading, but before the watcher is started
+        write_config(&path, 2, loaded_at + Duration::from_secs(1));
+        tokio::spawn(watch_chains_config(
+            chains.clone(),
+            path,
+            loaded_at,
+            Duration::from_millis(10),
+        ));
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while chains.get(2).is_none() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("config has not been reloaded");
+        assert!(chains.get(1).is_none());
+    }
+}
@@ -1,16 +1,23 @@
+use admin_core::submissions::User;
 use reqwest::StatusCode;
 use serde::Deserialize;
+use std::time::Duration;
 use thiserror::Error;
 use url::Url;
 
+const API_KEY_NAME: &str = "x-api-key";
+
 pub struct Client {
     http: reqwest::Client,
     url: Url,
+    api_key: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct VerifiedAddressOwner {
+    #[serde(default)]
+    user_id: Option<String>,
     user_email: String,
 }
 
@@ -25,14 +32,14 @@ pub enum Error {
 }
 
 impl Client {
-    pub fn new(url: Url) -> Self {
+    pub fn new(url: Url, api_key: Option<String>) -> Self {
         let http = reqwest::Client::new();
-        Self { http, url }
+        Self { http, url, api_key }
     }
 
     pub async fn validate_user_permission(
         &self,
-        user_email: &str,
+        user: &User,
         chain_id: i64,
         contract_address: &str,
     ) -> Result<(), Error> {
@@ -42,9 +49,11 @@ impl Client {
                 "/api/v1/chains/{chain_id}/admin/verified-addresses/{contract_address}/owner"
             ))
             .map_err(|e| Error::Internal(e.to_string()))?;
-        let response = self
-            .http
-            .get(url)
+        let mut request = self.http.get(url);
+        if let Some(api_key) = &self.api_key {
+            request = request.header(API_KEY_NAME, api_key);
+        }
+        let response = request
             .send()
             .await
             .map_err(|e| Error::Internal(e.to_string()))?;
@@ -54,10 +63,10 @@ impl Client {
                     .json()
                     .await
                     .map_err(|e| Error::Internal(e.to_string()))?;
-                if body.user_email == user_email {
+                if user.is_owner(body.user_id.as_deref(), &body.user_email) {
                     Ok(())
                 } else {
-                    Err(Error::PermissionDenied("invalid user_email".to_string()))
+                    Err(Error::PermissionDenied("invalid user".to_string()))
                 }
             }
             StatusCode::NOT_FOUND => Err(Error::ContractNotFound),
@@ -71,4 +80,26 @@ impl Client {
             }
         }
     }
+
+    /// Checks that the contracts-info service is reachable and reports itself healthy.
+    pub async fn check_health(&self, timeout: Duration) -> Result<(), Error> {
+        let url = self
+            .url
+            .join("/health")
+            .map_err(|e| Error::Internal(e.to_string()))?;
+        let response = self
+            .http
+            .get(url)
+            .timeout(timeout)
+            .send()
+            .await
+            .map_err(|e| Error::Internal(e.to_string()))?;
+        if !response.status().is_success() {
+            return Err(Error::Internal(format!(
+                "unexpected health status: {}",
+                response.status()
+            )));
+        }
+        Ok(())
+    }
 }
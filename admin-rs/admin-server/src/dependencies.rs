@@ -0,0 +1,90 @@
+use crate::{
+    chains::Chains,
+    services::{HealthService, Statuses, DATABASE_COMPONENT},
+    settings::HealthCheckSettings,
+};
+use admin_proto::blockscout::admin::v1::health_check_response::ServingStatus;
+use futures::future::join_all;
+use sea_orm::{ConnectionTrait, DatabaseConnection, Statement};
+use std::{fmt::Display, sync::Arc};
+
+/// Periodically checks the database, blockscout and contracts-info
+/// instances of every configured chain and reports their statuses
+/// to the health service. Chains are re-read on every check,
+/// so reloaded chains config is picked up automatically.
+pub async fn watch_dependencies(
+    health: Arc<HealthService>,
+    db: Arc<DatabaseConnection>,
+    chains: Arc<Chains>,
+    settings: HealthCheckSettings,
+) {
+    let timeout = settings.timeout();
+    let mut interval = tokio::time::interval(settings.interval());
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+
+        let chains = chains.all();
+        let chain_checks = chains.iter().map(|(chain_id, chain)| async move {
+            let blockscout = chain.blockscout_client.check_health(timeout).await;
+            let contracts_info = chain.contracts_info_client.check_health(timeout).await;
+            [
+                (
+                    format!("chains/{chain_id}/blockscout"),
+                    serving_status("blockscout", *chain_id, blockscout),
+                ),
+                (
+                    format!("chains/{chain_id}/contracts-info"),
+                    serving_status("contracts_info", *chain_id, contracts_info),
+                ),
+            ]
+        });
+        let (database, chain_statuses) = futures::join!(
+            tokio::time::timeout(timeout, check_database(&db)),
+            join_all(chain_checks)
+        );
+
+        let mut statuses: Statuses = chain_statuses.into_iter().flatten().collect();
+        let database = match database {
+            Ok(Ok(())) => ServingStatus::Serving,
+            Ok(Err(err)) => {
+                tracing::warn!(err = ?err, "database health check failed");
+                ServingStatus::NotServing
+            }
+            Err(_) => {
+                tracing::warn!(timeout = ?timeout, "database health check timed out");
+                ServingStatus::NotServing
+            }
+        };
+        statuses.insert(DATABASE_COMPONENT.to_string(), database);
+        health.update(statuses);
+    }
+}
+
+async fn check_database(db: &DatabaseConnection) -> Result<(), sea_orm::DbErr> {
+    db.execute(Statement::from_string(
+        db.get_database_backend(),
+        "SELECT 1".to_string(),
+    ))
+    .await?;
+    Ok(())
+}
+
+fn serving_status<E: Display>(
+    dependency: &str,
+    chain_id: i64,
+    result: Result<(), E>,
+) -> ServingStatus {
+    match result {
+        Ok(()) => ServingStatus::Serving,
+        Err(err) => {
+            tracing::warn!(
+                dependency = dependency,
+                chain_id = chain_id,
+                err = %err,
+                "dependency health check failed"
+            );
+            ServingStatus::NotServing
+        }
+    }
+}
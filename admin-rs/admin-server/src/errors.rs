@@ -0,0 +1,71 @@
+use service_common::errors;
+use tonic::Status;
+
+pub use service_common::errors::unavailable;
+
+pub type ErrorBuilder = errors::ErrorBuilder<Reason>;
+
+/// Reasons of the errors returned by the service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reason {
+    InvalidArgument,
+    InvalidField,
+    InvalidApiKey,
+    InvalidCredentials,
+    InvalidCsrfToken,
+    Forbidden,
+    ChainNotFound,
+    SubmissionNotFound,
+    RevisionNotFound,
+    DuplicateSubmission,
+    InvalidSubmissionStatus,
+    SubmissionModified,
+    NotAToken,
+    NotTokenOwner,
+    VerifiedAddressNotFound,
+    BlockscoutUnavailable,
+    ContractsInfoUnavailable,
+    IdempotencyKeyReused,
+    IdempotencyKeyInProgress,
+    Internal,
+}
+
+impl errors::Reason for Reason {
+    const DOMAIN: &'static str = "admin-rs.blockscout.com";
+    const INVALID_ARGUMENT: Self = Self::InvalidArgument;
+    const INTERNAL: Self = Self::Internal;
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::InvalidArgument => "INVALID_ARGUMENT",
+            Self::InvalidField => "INVALID_FIELD",
+            Self::InvalidApiKey => "INVALID_API_KEY",
+            Self::InvalidCredentials => "INVALID_CREDENTIALS",
+            Self::InvalidCsrfToken => "INVALID_CSRF_TOKEN",
+            Self::Forbidden => "FORBIDDEN",
+            Self::ChainNotFound => "CHAIN_NOT_FOUND",
+            Self::SubmissionNotFound => "SUBMISSION_NOT_FOUND",
+            Self::RevisionNotFound => "REVISION_NOT_FOUND",
+            Self::DuplicateSubmission => "DUPLICATE_SUBMISSION",
+            Self::InvalidSubmissionStatus => "INVALID_SUBMISSION_STATUS",
+            Self::SubmissionModified => "SUBMISSION_MODIFIED",
+            Self::NotAToken => "NOT_A_TOKEN",
+            Self::NotTokenOwner => "NOT_TOKEN_OWNER",
+            Self::VerifiedAddressNotFound => "VERIFIED_ADDRESS_NOT_FOUND",
+            Self::BlockscoutUnavailable => "BLOCKSCOUT_UNAVAILABLE",
+            Self::ContractsInfoUnavailable => "CONTRACTS_INFO_UNAVAILABLE",
+            Self::IdempotencyKeyReused => "IDEMPOTENCY_KEY_REUSED",
+            Self::IdempotencyKeyInProgress => "IDEMPOTENCY_KEY_IN_PROGRESS",
+            Self::Internal => "INTERNAL",
+        }
+    }
+}
+
+/// Returns `INVALID_ARGUMENT` status with the field violation attached.
+pub fn invalid_argument(field: &str, description: impl Into<String>) -> Status {
+    errors::invalid_argument::<Reason>(field, description)
+}
+
+pub fn internal(message: impl Into<String>) -> Status {
+    errors::internal::<Reason>(message)
+}
@@ -0,0 +1,33 @@
+use crate::errors::{self, ErrorBuilder, Reason};
+pub use service_common::idempotency::Idempotency;
+use service_common::idempotency::{Error, IDEMPOTENCY_KEY_NAME};
+use tonic::{Code, Status};
+
+/// Converts errors of idempotency key handling into the statuses returned by the service.
+pub fn map_idempotency_error(err: Error) -> Status {
+    match err {
+        Error::KeyReused => ErrorBuilder::new(
+            Code::InvalidArgument,
+            Reason::IdempotencyKeyReused,
+            err.to_string(),
+        )
+        .field_violation(IDEMPOTENCY_KEY_NAME, err.to_string())
+        .build(),
+        Error::InProgress => ErrorBuilder::new(
+            Code::Aborted,
+            Reason::IdempotencyKeyInProgress,
+            err.to_string(),
+        )
+        .retryable()
+        .build(),
+        Error::InvalidKey(reason) => errors::invalid_argument(IDEMPOTENCY_KEY_NAME, reason),
+        Error::Decode(_) => {
+            tracing::error!(err = ?err, "failed to decode stored response");
+            errors::internal("failed to replay stored response")
+        }
+        Error::Db(_) => {
+            tracing::error!(err = ?err, "idempotency key storage failed");
+            errors::internal("failed to process idempotency key")
+        }
+    }
+}
@@ -1,4 +1,10 @@
+mod auth;
+mod blockscout;
+mod chains;
 mod contracts_info;
+mod dependencies;
+mod errors;
+mod idempotency;
 mod server;
 mod services;
 mod settings;
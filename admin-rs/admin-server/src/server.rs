@@ -1,12 +1,18 @@
 use crate::{
-    contracts_info,
-    services::{AdminService, HealthService},
+    auth::AuthCache,
+    chains::{self, Chains},
+    dependencies,
+    idempotency::{map_idempotency_error, Idempotency},
+    services::{route_export, AdminService, HealthService},
     settings::Settings,
 };
 use admin_core::submissions::Selectors;
-use admin_proto::blockscout::admin::v1::{
-    admin_actix::route_admin, admin_server::AdminServer, health_actix::route_health,
-    health_server::HealthServer,
+use admin_proto::{
+    blockscout::admin::v1::{
+        admin_actix::route_admin, admin_server::AdminServer, health_actix::route_health,
+        health_server::HealthServer,
+    },
+    FILE_DESCRIPTOR_SET,
 };
 use anyhow::Context;
 use blockscout_service_launcher::LaunchSettings;
@@ -24,9 +30,14 @@ struct Router {
 
 impl Router {
     pub fn grpc_router(&self) -> tonic::transport::server::Router {
+        let reflection = tonic_reflection::server::Builder::configure()
+            .register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
+            .build()
+            .expect("file descriptor set should be valid");
         tonic::transport::Server::builder()
             .add_service(AdminServer::from_arc(self.admin.clone()))
             .add_service(HealthServer::from_arc(self.health.clone()))
+            .add_service(reflection)
     }
 }
 
@@ -34,6 +45,7 @@ impl blockscout_service_launcher::HttpRouter for Router {
     fn register_routes(&self, service_config: &mut actix_web::web::ServiceConfig) {
         service_config
             .configure(|config| route_admin(config, self.admin.clone()))
+            .configure(|config| route_export(config, self.admin.clone()))
             .configure(|config| route_health(config, self.health.clone()));
     }
 }
@@ -47,9 +59,9 @@ pub async fn run(settings: Settings) -> Result<(), anyhow::Error> {
         Migrator::up(db.as_ref(), None).await?;
     }
     let networks_config = settings.chains_config;
-    println!(
-        "start with networks config:\n{}",
-        serde_json::to_string_pretty(&networks_config).unwrap()
+    tracing::info!(
+        config = %serde_json::to_string(&networks_config.redacted())?,
+        "start with networks config"
     );
 
     let selectors_list_path = settings.selectors_list_path;
@@ -68,16 +80,39 @@ pub async fn run(settings: Settings) -> Result<(), anyhow::Error> {
         Selectors::default()
     };
 
+    // Config which has not been loaded from the file (e.g. provided programmatically) is not watched
+    let loaded_at = networks_config
+        .modified_at
+        .filter(|_| settings.chains_config_reload.enabled);
+    let chains = Arc::new(Chains::new(networks_config, settings.contracts_info_addr));
+    if let Some(loaded_at) = loaded_at {
+        tokio::spawn(chains::watch_chains_config(
+            chains.clone(),
+            settings.chains_config_path,
+            loaded_at,
+            settings.chains_config_reload.interval(),
+        ));
+    }
+
+    let health = Arc::new(HealthService::default());
+    tokio::spawn(dependencies::watch_dependencies(
+        health.clone(),
+        db.clone(),
+        chains.clone(),
+        settings.health_check,
+    ));
+
+    let idempotency = Idempotency::new(db.clone(), settings.idempotency, map_idempotency_error);
     let admin_client = admin_core::Client::new_arc(db, selectors);
-    let contracts_info_client = contracts_info::Client::new(settings.contracts_info_addr);
+    let auth_cache = AuthCache::new(settings.auth_cache);
     let admin = Arc::new(AdminService::new(
         admin_client,
-        contracts_info_client,
-        networks_config,
+        chains,
+        auth_cache,
+        idempotency,
+        settings.admin_api_key,
     ));
 
-    let health = Arc::new(HealthService::default());
-
     let router = Router { admin, health };
 
     let grpc_router = router.grpc_router();
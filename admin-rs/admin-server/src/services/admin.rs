@@ -1,37 +1,103 @@
 use crate::{
+    auth::{authenticate, AuthCache},
+    blockscout,
+    chains::{Chain, Chains},
     contracts_info,
-    settings::ChainsSettings,
-    types::{convert_submission, validate_input_chain_id, validate_input_submission},
+    errors::{self, ErrorBuilder, Reason},
+    idempotency::Idempotency,
+    types::{
+        apply_update_mask, convert_field_change, convert_field_descriptor, convert_revision,
+        convert_searched_submission, convert_submission, convert_user_data,
+        validate_blockscout_users, validate_data_subject, validate_input_chain_id,
+        validate_input_submission, validate_search_query, validate_user_email,
+    },
 };
 use admin_core::submissions;
 use admin_proto::blockscout::admin::v1::{
-    admin_server::Admin, GetTokenInfoSubmissionRequest, ListTokenInfoSubmissionSelectorsRequest,
-    ListTokenInfoSubmissionSelectorsResponse, ListTokenInfoSubmissionsRequest,
-    ListTokenInfoSubmissionsResponse, TokenInfoSubmission, TokenInfoSubmissionRequest,
-    UpdateTokenInfoSubmissionRequest,
+    admin_server::Admin, BackfillUserIdsAdminRequest, BackfillUserIdsAdminResponse,
+    DiffTokenInfoSubmissionRevisionsAdminRequest, DiffTokenInfoSubmissionRevisionsAdminResponse,
+    EraseUserDataAdminRequest, EraseUserDataAllChainsAdminRequest, EraseUserDataRequest,
+    EraseUserDataResponse, ExportUserDataAdminRequest, ExportUserDataAllChainsAdminRequest,
+    ExportUserDataRequest, GetTokenInfoSubmissionRequest,
+    ListTokenInfoSubmissionRevisionsAdminRequest, ListTokenInfoSubmissionRevisionsAdminResponse,
+    ListTokenInfoSubmissionSelectorsRequest, ListTokenInfoSubmissionSelectorsResponse,
+    ListTokenInfoSubmissionsRequest, ListTokenInfoSubmissionsResponse,
+    SearchTokenInfoSubmissionsAdminRequest, SearchTokenInfoSubmissionsAdminResponse,
+    TokenInfoSubmission, TokenInfoSubmissionRequest, UpdateTokenInfoSubmissionRequest, UserData,
 };
-use blockscout_auth::auth_from_metadata;
-use tonic::{Request, Response, Status};
-use url::Url;
+use std::sync::Arc;
+use tonic::{metadata::MetadataMap, Code, Request, Response, Status};
+
+pub(crate) const API_KEY_NAME: &str = "x-api-key";
 
 pub struct AdminService {
     admin_client: admin_core::Client,
-    contracts_info_client: contracts_info::Client,
-    networks: ChainsSettings,
+    chains: Arc<Chains>,
+    auth_cache: AuthCache,
+    idempotency: Idempotency,
+    admin_api_key: Option<String>,
 }
 
 impl AdminService {
     pub fn new(
         admin_client: admin_core::Client,
-        contracts_info_client: contracts_info::Client,
-        networks: ChainsSettings,
+        chains: Arc<Chains>,
+        auth_cache: AuthCache,
+        idempotency: Idempotency,
+        admin_api_key: Option<String>,
     ) -> Self {
         Self {
             admin_client,
-            contracts_info_client,
-            networks,
+            chains,
+            auth_cache,
+            idempotency,
+            admin_api_key,
         }
     }
+
+    pub(crate) fn admin_client(&self) -> &admin_core::Client {
+        &self.admin_client
+    }
+
+    pub(crate) fn check_api_key(&self, api_key: Option<&str>) -> Result<(), Status> {
+        match (&self.admin_api_key, api_key) {
+            (Some(expected), Some(api_key)) if expected == api_key => Ok(()),
+            _ => Err(ErrorBuilder::new(
+                Code::Unauthenticated,
+                Reason::InvalidApiKey,
+                "invalid api key",
+            )
+            .build()),
+        }
+    }
+
+    fn authorize_admin<T>(&self, request: &Request<T>) -> Result<(), Status> {
+        let api_key = request
+            .metadata()
+            .get(API_KEY_NAME)
+            .and_then(|value| value.to_str().ok());
+        self.check_api_key(api_key)
+    }
+}
+
+/// Idempotency keys are scoped by callers, so that different users cannot replay responses of each other.
+fn user_caller(chain_id: i64, user: &submissions::User) -> String {
+    submissions::idempotency_caller(chain_id, &user.id)
+}
+
+/// Caller of the requests authorized with the admin api key.
+const ADMIN_CALLER: &str = "admin";
+
+const IF_MATCH_HEADER: &str = "if-match";
+
+/// Returns the etag the update is based on. HTTP clients may provide it in the `If-Match` header.
+fn expected_etag(etag: Option<String>, metadata: &MetadataMap) -> Option<String> {
+    etag.filter(|etag| !etag.is_empty()).or_else(|| {
+        metadata
+            .get(IF_MATCH_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.trim_matches('"').to_string())
+    })
 }
 
 #[async_trait::async_trait]
@@ -42,24 +108,39 @@ impl Admin for AdminService {
     ) -> Result<Response<TokenInfoSubmission>, Status> {
         let (metadata, _, payload) = request.into_parts();
         let chain_id = validate_input_chain_id(payload.chain_id)?;
-        let (blockscout_url, blockscout_api_key) = get_url_and_apikey(&self.networks, chain_id)?;
+        let chain = get_chain(&self.chains, chain_id)?;
         let is_http_safe = false;
-        let auth = auth_from_metadata(&metadata, is_http_safe, blockscout_url, blockscout_api_key)
-            .await
-            .map_err(map_auth_error)?;
-        let submission = payload
-            .submission
-            .ok_or_else(|| Status::invalid_argument("no submission data"))?;
-        self.contracts_info_client
-            .validate_user_permission(&auth.email.to_string(), chain_id, &submission.token_address)
-            .await
-            .map_err(map_contracts_info_error)?;
-        let data = validate_input_submission(submission, None, chain_id, auth.email.to_string())
-            .map_err(|e| Status::invalid_argument(e.to_string()))?;
-        let submission = submissions::create_submission(&self.admin_client, data)
-            .await
-            .map_err(map_submissions_error)?;
-        Ok(tonic::Response::new(convert_submission(submission)))
+        let user: submissions::User =
+            authenticate(&self.auth_cache, &metadata, is_http_safe, &chain.config)
+                .await?
+                .into();
+        let caller = user_caller(chain_id, &user);
+        let method = "CreateTokenInfoSubmission";
+        let submission = self
+            .idempotency
+            .run(&metadata, &caller, method, payload, |payload| async move {
+                let submission = payload
+                    .submission
+                    .ok_or_else(|| errors::invalid_argument("submission", "value is required"))?;
+                let token_address = submission.token_address.clone();
+                let mut data = validate_input_submission(submission, None, chain_id, user.clone())?;
+                // Blockscout is requested only on behalf of users who may submit the token info
+                chain
+                    .contracts_info_client
+                    .validate_user_permission(&user, chain_id, &token_address)
+                    .await
+                    .map_err(map_contracts_info_error)?;
+                fetch_token(&chain, &data).await?.apply(&mut data);
+                submissions::reconcile_user(&self.admin_client, &user)
+                    .await
+                    .map_err(map_submissions_error)?;
+                let submission = submissions::create_submission(&self.admin_client, data)
+                    .await
+                    .map_err(map_submissions_error)?;
+                Ok(convert_submission(submission))
+            })
+            .await?;
+        Ok(tonic::Response::new(submission))
     }
 
     async fn get_token_info_submission(
@@ -68,19 +149,16 @@ impl Admin for AdminService {
     ) -> Result<Response<TokenInfoSubmission>, Status> {
         let (metadata, _, payload) = request.into_parts();
         let chain_id = validate_input_chain_id(payload.chain_id)?;
-        let (blockscout_url, blockscout_api_key) = get_url_and_apikey(&self.networks, chain_id)?;
+        let chain = get_chain(&self.chains, chain_id)?;
         let is_http_safe = true;
-        let auth = auth_from_metadata(&metadata, is_http_safe, blockscout_url, blockscout_api_key)
-            .await
-            .map_err(map_auth_error)?;
-        let submission = submissions::get_submission(
-            &self.admin_client,
-            payload.id,
-            auth.email.to_string(),
-            chain_id,
-        )
-        .await
-        .map_err(map_submissions_error)?;
+        let user: submissions::User =
+            authenticate(&self.auth_cache, &metadata, is_http_safe, &chain.config)
+                .await?
+                .into();
+        let submission =
+            submissions::get_submission(&self.admin_client, payload.id, &user, chain_id)
+                .await
+                .map_err(map_submissions_error)?;
         Ok(tonic::Response::new(convert_submission(submission)))
     }
 
@@ -90,25 +168,62 @@ impl Admin for AdminService {
     ) -> Result<Response<TokenInfoSubmission>, Status> {
         let (metadata, _, payload) = request.into_parts();
         let chain_id = validate_input_chain_id(payload.chain_id)?;
-        let (blockscout_url, blockscout_api_key) = get_url_and_apikey(&self.networks, chain_id)?;
+        let chain = get_chain(&self.chains, chain_id)?;
         let is_http_safe = false;
-        let auth = auth_from_metadata(&metadata, is_http_safe, blockscout_url, blockscout_api_key)
-            .await
-            .map_err(map_auth_error)?;
-        let submission = payload
-            .submission
-            .ok_or_else(|| Status::invalid_argument("no submission data"))?;
-        let data = validate_input_submission(
-            submission,
-            Some(payload.id),
-            chain_id,
-            auth.email.to_string(),
-        )
-        .map_err(|e| Status::invalid_argument(e.to_string()))?;
-        let submission = submissions::update_submission(&self.admin_client, data)
-            .await
-            .map_err(map_submissions_error)?;
-        Ok(tonic::Response::new(convert_submission(submission)))
+        let user: submissions::User =
+            authenticate(&self.auth_cache, &metadata, is_http_safe, &chain.config)
+                .await?
+                .into();
+        let caller = user_caller(chain_id, &user);
+        let etag = expected_etag(payload.etag.clone(), &metadata);
+        let method = "UpdateTokenInfoSubmission";
+        let submission = self
+            .idempotency
+            .run(&metadata, &caller, method, payload, |payload| async move {
+                let submission = payload
+                    .submission
+                    .ok_or_else(|| errors::invalid_argument("submission", "value is required"))?;
+                submissions::reconcile_user(&self.admin_client, &user)
+                    .await
+                    .map_err(map_submissions_error)?;
+                // Fails fast if the submission could not be updated by the user,
+                // so that Blockscout is not requested for such submissions
+                let current = submissions::get_updatable_submission(
+                    &self.admin_client,
+                    payload.id,
+                    &user,
+                    chain_id,
+                    etag.as_deref(),
+                )
+                .await
+                .map_err(map_submissions_error)?;
+                // Empty mask and `*` mean that all user editable fields are replaced
+                let update_mask = payload
+                    .update_mask
+                    .map(|mask| mask.paths)
+                    .filter(|paths| !paths.is_empty() && *paths != ["*"]);
+                let (submission, etag) = match update_mask {
+                    Some(paths) => {
+                        // Fields out of the mask keep their current values
+                        // only if the submission is not modified concurrently
+                        let etag = etag.unwrap_or_else(|| current.etag());
+                        let mut merged = convert_submission(current);
+                        apply_update_mask(&mut merged, &submission, &paths)?;
+                        (merged, Some(etag))
+                    }
+                    None => (submission, etag),
+                };
+                let mut data =
+                    validate_input_submission(submission, Some(payload.id), chain_id, user)?;
+                fetch_token(&chain, &data).await?.apply(&mut data);
+                let submission =
+                    submissions::update_submission(&self.admin_client, data, etag.as_deref())
+                        .await
+                        .map_err(map_submissions_error)?;
+                Ok(convert_submission(submission))
+            })
+            .await?;
+        Ok(tonic::Response::new(submission))
     }
 
     async fn list_token_info_submissions(
@@ -117,72 +232,366 @@ impl Admin for AdminService {
     ) -> Result<Response<ListTokenInfoSubmissionsResponse>, Status> {
         let (metadata, _, payload) = request.into_parts();
         let chain_id = validate_input_chain_id(payload.chain_id)?;
-        let (blockscout_url, blockscout_api_key) = get_url_and_apikey(&self.networks, chain_id)?;
+        let chain = get_chain(&self.chains, chain_id)?;
         let is_http_safe = true;
-        let auth = auth_from_metadata(&metadata, is_http_safe, blockscout_url, blockscout_api_key)
+        let user: submissions::User =
+            authenticate(&self.auth_cache, &metadata, is_http_safe, &chain.config)
+                .await?
+                .into();
+        let submissions = submissions::list_submissions(&self.admin_client, &user, chain_id)
             .await
-            .map_err(map_auth_error)?;
-        let submissions =
-            submissions::list_submissions(&self.admin_client, auth.email.to_string(), chain_id)
-                .await
-                .map_err(map_submissions_error)?;
+            .map_err(map_submissions_error)?;
         Ok(tonic::Response::new(ListTokenInfoSubmissionsResponse {
             submissions: submissions.into_iter().map(convert_submission).collect(),
         }))
     }
 
+    async fn export_user_data(
+        &self,
+        request: Request<ExportUserDataRequest>,
+    ) -> Result<Response<UserData>, Status> {
+        let (metadata, _, payload) = request.into_parts();
+        let chain_id = validate_input_chain_id(payload.chain_id)?;
+        let chain = get_chain(&self.chains, chain_id)?;
+        let is_http_safe = true;
+        let user: submissions::User =
+            authenticate(&self.auth_cache, &metadata, is_http_safe, &chain.config)
+                .await?
+                .into();
+        let user_data =
+            submissions::export_user_data(&self.admin_client, &user.into(), Some(chain_id))
+                .await
+                .map_err(map_submissions_error)?;
+        Ok(tonic::Response::new(convert_user_data(user_data)))
+    }
+
+    async fn erase_user_data(
+        &self,
+        request: Request<EraseUserDataRequest>,
+    ) -> Result<Response<EraseUserDataResponse>, Status> {
+        let (metadata, _, payload) = request.into_parts();
+        let chain_id = validate_input_chain_id(payload.chain_id)?;
+        let chain = get_chain(&self.chains, chain_id)?;
+        let is_http_safe = false;
+        let user: submissions::User =
+            authenticate(&self.auth_cache, &metadata, is_http_safe, &chain.config)
+                .await?
+                .into();
+        let caller = user_caller(chain_id, &user);
+        let response = self
+            .idempotency
+            .run(
+                &metadata,
+                &caller,
+                "EraseUserData",
+                payload,
+                |_| async move {
+                    submissions::reconcile_user(&self.admin_client, &user)
+                        .await
+                        .map_err(map_submissions_error)?;
+                    let anonymized_submissions = submissions::erase_user_data(
+                        &self.admin_client,
+                        &user.into(),
+                        Some(chain_id),
+                    )
+                    .await
+                    .map_err(map_submissions_error)?;
+                    Ok(EraseUserDataResponse {
+                        anonymized_submissions,
+                    })
+                },
+            )
+            .await?;
+        Ok(tonic::Response::new(response))
+    }
+
+    async fn export_user_data_admin(
+        &self,
+        request: Request<ExportUserDataAdminRequest>,
+    ) -> Result<Response<UserData>, Status> {
+        self.authorize_admin(&request)?;
+        let payload = request.into_inner();
+        let chain_id = validate_input_chain_id(payload.chain_id)?;
+        let subject = validate_data_subject(payload.user_id, payload.user_email)?;
+        let user_data = submissions::export_user_data(&self.admin_client, &subject, Some(chain_id))
+            .await
+            .map_err(map_submissions_error)?;
+        Ok(tonic::Response::new(convert_user_data(user_data)))
+    }
+
+    async fn erase_user_data_admin(
+        &self,
+        request: Request<EraseUserDataAdminRequest>,
+    ) -> Result<Response<EraseUserDataResponse>, Status> {
+        self.authorize_admin(&request)?;
+        let (metadata, _, payload) = request.into_parts();
+        let method = "EraseUserDataAdmin";
+        let response = self
+            .idempotency
+            .run(
+                &metadata,
+                ADMIN_CALLER,
+                method,
+                payload,
+                |payload| async move {
+                    let chain_id = validate_input_chain_id(payload.chain_id)?;
+                    let subject = validate_data_subject(payload.user_id, payload.user_email)?;
+                    if let submissions::DataSubject::User(user) = &subject {
+                        submissions::reconcile_user(&self.admin_client, user)
+                            .await
+                            .map_err(map_submissions_error)?;
+                    }
+                    let anonymized_submissions =
+                        submissions::erase_user_data(&self.admin_client, &subject, Some(chain_id))
+                            .await
+                            .map_err(map_submissions_error)?;
+                    Ok(EraseUserDataResponse {
+                        anonymized_submissions,
+                    })
+                },
+            )
+            .await?;
+        Ok(tonic::Response::new(response))
+    }
+
+    async fn export_user_data_all_chains_admin(
+        &self,
+        request: Request<ExportUserDataAllChainsAdminRequest>,
+    ) -> Result<Response<UserData>, Status> {
+        self.authorize_admin(&request)?;
+        let payload = request.into_inner();
+        let subject = validate_user_email(payload.user_email)?;
+        let user_data = submissions::export_user_data(&self.admin_client, &subject, None)
+            .await
+            .map_err(map_submissions_error)?;
+        Ok(tonic::Response::new(convert_user_data(user_data)))
+    }
+
+    async fn erase_user_data_all_chains_admin(
+        &self,
+        request: Request<EraseUserDataAllChainsAdminRequest>,
+    ) -> Result<Response<EraseUserDataResponse>, Status> {
+        self.authorize_admin(&request)?;
+        let (metadata, _, payload) = request.into_parts();
+        let method = "EraseUserDataAllChainsAdmin";
+        let response = self
+            .idempotency
+            .run(
+                &metadata,
+                ADMIN_CALLER,
+                method,
+                payload,
+                |payload| async move {
+                    let subject = validate_user_email(payload.user_email)?;
+                    let anonymized_submissions =
+                        submissions::erase_user_data(&self.admin_client, &subject, None)
+                            .await
+                            .map_err(map_submissions_error)?;
+                    Ok(EraseUserDataResponse {
+                        anonymized_submissions,
+                    })
+                },
+            )
+            .await?;
+        Ok(tonic::Response::new(response))
+    }
+
+    async fn backfill_user_ids_admin(
+        &self,
+        request: Request<BackfillUserIdsAdminRequest>,
+    ) -> Result<Response<BackfillUserIdsAdminResponse>, Status> {
+        self.authorize_admin(&request)?;
+        let request = request.into_inner();
+        let chain_id = validate_input_chain_id(request.chain_id)?;
+        let users = validate_blockscout_users(request.users)?;
+        let updated_submissions =
+            submissions::backfill_user_ids(&self.admin_client, chain_id, &users)
+                .await
+                .map_err(map_submissions_error)?;
+        Ok(tonic::Response::new(BackfillUserIdsAdminResponse {
+            updated_submissions,
+        }))
+    }
+
+    async fn search_token_info_submissions_admin(
+        &self,
+        request: Request<SearchTokenInfoSubmissionsAdminRequest>,
+    ) -> Result<Response<SearchTokenInfoSubmissionsAdminResponse>, Status> {
+        self.authorize_admin(&request)?;
+        let (query, page_size, page_token) = validate_search_query(request.into_inner())?;
+        let (submissions, next_page_token) =
+            submissions::search_submissions(&self.admin_client, &query, page_size, page_token)
+                .await
+                .map_err(map_submissions_error)?;
+        Ok(tonic::Response::new(
+            SearchTokenInfoSubmissionsAdminResponse {
+                submissions: submissions
+                    .into_iter()
+                    .map(convert_searched_submission)
+                    .collect(),
+                next_page_token: next_page_token.map(|token| token.to_string()),
+            },
+        ))
+    }
+
+    async fn list_token_info_submission_revisions_admin(
+        &self,
+        request: Request<ListTokenInfoSubmissionRevisionsAdminRequest>,
+    ) -> Result<Response<ListTokenInfoSubmissionRevisionsAdminResponse>, Status> {
+        self.authorize_admin(&request)?;
+        let payload = request.into_inner();
+        let revisions = submissions::list_revisions(&self.admin_client, payload.submission_id)
+            .await
+            .map_err(map_submissions_error)?;
+        Ok(tonic::Response::new(
+            ListTokenInfoSubmissionRevisionsAdminResponse {
+                revisions: revisions.into_iter().map(convert_revision).collect(),
+            },
+        ))
+    }
+
+    async fn diff_token_info_submission_revisions_admin(
+        &self,
+        request: Request<DiffTokenInfoSubmissionRevisionsAdminRequest>,
+    ) -> Result<Response<DiffTokenInfoSubmissionRevisionsAdminResponse>, Status> {
+        self.authorize_admin(&request)?;
+        let payload = request.into_inner();
+        let changes = submissions::diff_revisions(
+            &self.admin_client,
+            payload.submission_id,
+            payload.from_revision,
+            payload.to_revision,
+        )
+        .await
+        .map_err(map_submissions_error)?;
+        Ok(tonic::Response::new(
+            DiffTokenInfoSubmissionRevisionsAdminResponse {
+                changes: changes.into_iter().map(convert_field_change).collect(),
+            },
+        ))
+    }
+
     async fn list_token_info_submission_selectors(
         &self,
         _request: Request<ListTokenInfoSubmissionSelectorsRequest>,
     ) -> Result<Response<ListTokenInfoSubmissionSelectorsResponse>, Status> {
         let selectors_response = ListTokenInfoSubmissionSelectorsResponse {
             project_sectors: self.admin_client.selectors.project_sectors.clone(),
+            fields: self
+                .admin_client
+                .selectors
+                .submission_schema()
+                .into_iter()
+                .map(convert_field_descriptor)
+                .collect(),
         };
         Ok(tonic::Response::new(selectors_response))
     }
 }
 
 fn map_submissions_error(err: submissions::Error) -> Status {
-    match &err {
-        submissions::Error::NotFound(_) => tonic::Status::not_found(err.to_string()),
-        submissions::Error::Duplicate(_) => tonic::Status::already_exists(err.to_string()),
-        submissions::Error::InvalidStatusForUpdate(_) => {
-            tonic::Status::invalid_argument(err.to_string())
+    let message = err.to_string();
+    match err {
+        submissions::Error::NotFound(id) => {
+            ErrorBuilder::new(Code::NotFound, Reason::SubmissionNotFound, message)
+                .metadata("submission_id", id)
+                .build()
+        }
+        submissions::Error::RevisionNotFound {
+            submission_id,
+            revision,
+        } => ErrorBuilder::new(Code::NotFound, Reason::RevisionNotFound, message)
+            .metadata("submission_id", submission_id)
+            .metadata("revision", revision)
+            .build(),
+        submissions::Error::Duplicate(id) => {
+            ErrorBuilder::new(Code::AlreadyExists, Reason::DuplicateSubmission, message)
+                .metadata("submission_id", id)
+                .build()
         }
-        submissions::Error::InvalidSelector { .. } => {
-            tonic::Status::invalid_argument(err.to_string())
+        submissions::Error::InvalidStatusForUpdate(status) => ErrorBuilder::new(
+            Code::FailedPrecondition,
+            Reason::InvalidSubmissionStatus,
+            message,
+        )
+        .metadata("status", submissions::Status::from(status).as_str())
+        .precondition_violation(
+            "STATUS",
+            "submission",
+            "only submissions waiting for update can be updated",
+        )
+        .build(),
+        submissions::Error::EtagMismatch {
+            submission_id,
+            etag,
+        } => ErrorBuilder::new(Code::Aborted, Reason::SubmissionModified, message)
+            .metadata("submission_id", submission_id)
+            .metadata("etag", etag)
+            .build(),
+        submissions::Error::InvalidSelector { selector, value } => {
+            ErrorBuilder::new(Code::InvalidArgument, Reason::InvalidField, message)
+                .metadata("field", &selector)
+                .field_violation(selector, format!("unknown value: {value}"))
+                .build()
         }
-        _ => tonic::Status::internal(err.to_string()),
+        submissions::Error::InvalidField { field, reason } => {
+            ErrorBuilder::new(Code::InvalidArgument, Reason::InvalidField, message)
+                .metadata("field", &field)
+                .field_violation(field, reason)
+                .build()
+        }
+        submissions::Error::Db(_) | submissions::Error::Internal(_) => errors::internal(message),
     }
 }
 
 fn map_contracts_info_error(err: contracts_info::Error) -> Status {
+    let message = err.to_string();
     match err {
-        contracts_info::Error::PermissionDenied(_) => Status::permission_denied(err.to_string()),
-        contracts_info::Error::ContractNotFound => Status::not_found(err.to_string()),
-        contracts_info::Error::Internal(_) => Status::internal(err.to_string()),
+        contracts_info::Error::PermissionDenied(_) => {
+            ErrorBuilder::new(Code::PermissionDenied, Reason::NotTokenOwner, message).build()
+        }
+        contracts_info::Error::ContractNotFound => {
+            ErrorBuilder::new(Code::NotFound, Reason::VerifiedAddressNotFound, message).build()
+        }
+        contracts_info::Error::Internal(_) => {
+            errors::unavailable(Reason::ContractsInfoUnavailable, message)
+        }
     }
 }
 
-fn map_auth_error(err: blockscout_auth::Error) -> Status {
-    match err {
-        blockscout_auth::Error::Unauthorized(_) => Status::unauthenticated(err.to_string()),
-        blockscout_auth::Error::Forbidden(_) => Status::permission_denied(err.to_string()),
-        blockscout_auth::Error::InvalidCsrfToken(_) | blockscout_auth::Error::InvalidJwt(_) => {
-            Status::invalid_argument(err.to_string())
-        }
-        blockscout_auth::Error::BlockscoutApi(_) => Status::unauthenticated(err.to_string()),
-        _ => Status::internal(err.to_string()),
-    }
+/// Fetches on-chain metadata of the submitted token.
+/// Fails if the submitted address is not a token on the chain.
+async fn fetch_token(
+    chain: &Chain,
+    submission: &submissions::Submission,
+) -> Result<blockscout::Token, Status> {
+    chain
+        .blockscout_client
+        .get_token(&submission.token_address.to_string())
+        .await
+        .map_err(|err| match err {
+            blockscout::Error::NotAToken(_) => ErrorBuilder::new(
+                Code::InvalidArgument,
+                Reason::NotAToken,
+                format!("{err} on chain {}", submission.chain_id),
+            )
+            .metadata("chain_id", submission.chain_id)
+            .field_violation("token_address", "address is not a token")
+            .build(),
+            blockscout::Error::Internal(_) => {
+                errors::unavailable(Reason::BlockscoutUnavailable, err.to_string())
+            }
+        })
 }
 
-fn get_url_and_apikey(
-    config: &ChainsSettings,
-    chain_id: i64,
-) -> Result<(&Url, Option<&str>), Status> {
-    config
-        .networks
-        .get(&chain_id)
-        .map(|network| (&network.url, network.api_key.as_deref()))
-        .ok_or_else(|| Status::not_found(format!("chain {chain_id} not found")))
+fn get_chain(chains: &Chains, chain_id: i64) -> Result<Arc<Chain>, Status> {
+    chains.get(chain_id).ok_or_else(|| {
+        ErrorBuilder::new(
+            Code::NotFound,
+            Reason::ChainNotFound,
+            format!("chain {chain_id} not found"),
+        )
+        .metadata("chain_id", chain_id)
+        .build()
+    })
 }
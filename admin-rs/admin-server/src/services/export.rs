@@ -0,0 +1,86 @@
+use super::{admin::API_KEY_NAME, AdminService};
+use crate::types::validate_search_query;
+use actix_web::{http::StatusCode, web, HttpRequest, HttpResponse};
+use admin_core::submissions::{self, ExportFormat};
+use admin_proto::blockscout::admin::v1::SearchTokenInfoSubmissionsAdminRequest;
+use futures::StreamExt;
+use serde::Deserialize;
+use std::sync::Arc;
+use tonic::{Code, Status};
+
+const ROUTE: &str = "/api/v1/admin/token-info-submissions:export";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Format {
+    #[default]
+    Csv,
+    Ndjson,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExportParams {
+    #[serde(default)]
+    format: Format,
+}
+
+/// Registers the route streaming token info submissions matched by the search request.
+/// The route is served by actix directly, as server streaming is not supported for
+/// the routes generated from the proto definitions.
+pub fn route_export(config: &mut web::ServiceConfig, admin: Arc<AdminService>) {
+    config.route(
+        ROUTE,
+        web::post().to(
+            move |request: HttpRequest,
+                  params: web::Query<ExportParams>,
+                  body: web::Json<SearchTokenInfoSubmissionsAdminRequest>| {
+                let admin = admin.clone();
+                async move {
+                    export_submissions(&admin, &request, params.into_inner(), body.into_inner())
+                        .unwrap_or_else(status_to_response)
+                }
+            },
+        ),
+    );
+}
+
+fn export_submissions(
+    admin: &AdminService,
+    request: &HttpRequest,
+    params: ExportParams,
+    body: SearchTokenInfoSubmissionsAdminRequest,
+) -> Result<HttpResponse, Status> {
+    let api_key = request
+        .headers()
+        .get(API_KEY_NAME)
+        .and_then(|value| value.to_str().ok());
+    admin.check_api_key(api_key)?;
+    let (query, _, _) = validate_search_query(body)?;
+    let format = match params.format {
+        Format::Csv => ExportFormat::Csv,
+        Format::Ndjson => ExportFormat::Ndjson,
+    };
+
+    let stream =
+        submissions::export_submissions(admin.admin_client(), &query, format).map(|chunk| {
+            chunk.map(web::Bytes::from).map_err(|err| {
+                tracing::error!(err = ?err, "failed to export submissions");
+                std::io::Error::new(std::io::ErrorKind::Other, err.to_string())
+            })
+        });
+    Ok(HttpResponse::Ok()
+        .content_type(format.content_type())
+        .streaming(stream))
+}
+
+fn status_to_response(status: Status) -> HttpResponse {
+    let code = match status.code() {
+        Code::InvalidArgument => StatusCode::BAD_REQUEST,
+        Code::Unauthenticated => StatusCode::UNAUTHORIZED,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+    HttpResponse::build(code).json(serde_json::json!({
+        "code": status.code() as i32,
+        "message": status.message(),
+    }))
+}
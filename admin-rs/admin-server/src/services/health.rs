@@ -1,18 +1,100 @@
 use admin_proto::blockscout::admin::v1::{
-    health_check_response, health_server::Health, HealthCheckRequest, HealthCheckResponse,
+    health_check_response::ServingStatus, health_server::Health, HealthCheckRequest,
+    HealthCheckResponse,
 };
+use futures::Stream;
+use std::{collections::BTreeMap, pin::Pin};
+use tokio::sync::watch;
 
-#[derive(Default)]
-pub struct HealthService {}
+/// Name of the component which defines the overall serving status.
+/// The service cannot handle any request without the database,
+/// while external dependencies are reported per chain under their own names.
+pub const DATABASE_COMPONENT: &str = "database";
+
+/// Service names the overall serving status is reported for.
+const OVERALL_SERVICES: [&str; 2] = ["", "blockscout.admin.v1.Admin"];
+
+pub type Statuses = BTreeMap<String, ServingStatus>;
+
+pub struct HealthService {
+    statuses: watch::Sender<Statuses>,
+}
+
+impl Default for HealthService {
+    fn default() -> Self {
+        Self {
+            statuses: watch::channel(Statuses::new()).0,
+        }
+    }
+}
+
+impl HealthService {
+    /// Replaces statuses of all components. Watchers are notified only if anything has changed.
+    pub fn update(&self, statuses: Statuses) {
+        self.statuses.send_if_modified(|current| {
+            if *current == statuses {
+                return false;
+            }
+            *current = statuses;
+            true
+        });
+    }
+
+    fn status(statuses: &Statuses, service: &str) -> Option<ServingStatus> {
+        if OVERALL_SERVICES.contains(&service) {
+            return Some(
+                statuses
+                    .get(DATABASE_COMPONENT)
+                    .copied()
+                    .unwrap_or(ServingStatus::Unknown),
+            );
+        }
+        statuses.get(service).copied()
+    }
+}
 
 #[async_trait::async_trait]
 impl Health for HealthService {
     async fn check(
         &self,
-        _request: tonic::Request<HealthCheckRequest>,
+        request: tonic::Request<HealthCheckRequest>,
     ) -> Result<tonic::Response<HealthCheckResponse>, tonic::Status> {
+        let service = request.into_inner().service;
+        let status = Self::status(&self.statuses.borrow(), &service)
+            .ok_or_else(|| tonic::Status::not_found(format!("unknown service: {service}")))?;
         Ok(tonic::Response::new(HealthCheckResponse {
-            status: health_check_response::ServingStatus::Serving.into(),
+            status: status.into(),
         }))
     }
+
+    type WatchStream =
+        Pin<Box<dyn Stream<Item = Result<HealthCheckResponse, tonic::Status>> + Send>>;
+
+    async fn watch(
+        &self,
+        request: tonic::Request<HealthCheckRequest>,
+    ) -> Result<tonic::Response<Self::WatchStream>, tonic::Status> {
+        let service = request.into_inner().service;
+        let receiver = self.statuses.subscribe();
+        // The current status is sent immediately, then only its changes are sent
+        let stream =
+            futures::stream::unfold((receiver, None), move |(mut receiver, last_status)| {
+                let service = service.clone();
+                async move {
+                    loop {
+                        let status = Self::status(&receiver.borrow_and_update(), &service)
+                            .unwrap_or(ServingStatus::ServiceUnknown);
+                        if last_status != Some(status) {
+                            let response = HealthCheckResponse {
+                                status: status.into(),
+                            };
+                            return Some((Ok(response), (receiver, Some(status))));
+                        }
+                        // The stream ends when the server shuts down
+                        receiver.changed().await.ok()?;
+                    }
+                }
+            });
+        Ok(tonic::Response::new(Box::pin(stream)))
+    }
 }
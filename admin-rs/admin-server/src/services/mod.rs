@@ -1,5 +1,7 @@
 mod admin;
+mod export;
 mod health;
 
 pub use admin::AdminService;
-pub use health::HealthService;
+pub use export::route_export;
+pub use health::{HealthService, Statuses, DATABASE_COMPONENT};
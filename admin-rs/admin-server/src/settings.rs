@@ -3,9 +3,11 @@ use blockscout_service_launcher::{
 };
 use config::{Config, File};
 use serde::{de, Deserialize, Serialize};
+pub use service_common::{auth::AuthCacheSettings, idempotency::IdempotencySettings};
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
+    time::{Duration, SystemTime},
 };
 use url::Url;
 
@@ -47,6 +49,22 @@ pub struct Settings {
     pub chains_config_path: PathBuf,
     #[serde(skip_deserializing)]
     pub chains_config: ChainsSettings,
+    #[serde(default)]
+    pub chains_config_reload: ChainsReloadSettings,
+
+    #[serde(default)]
+    pub auth_cache: AuthCacheSettings,
+
+    #[serde(default)]
+    pub health_check: HealthCheckSettings,
+
+    #[serde(default)]
+    pub idempotency: IdempotencySettings,
+
+    // Key required to make admin requests (e.g., user data export and erasure).
+    // If not specified, admin requests are rejected.
+    #[serde(default)]
+    pub admin_api_key: Option<String>,
 
     // Is required as we deny unknown fields, but allow users provide
     // path to config through PREFIX__CONFIG env variable. If removed,
@@ -67,23 +85,117 @@ pub struct DatabaseSettings {
 pub struct ChainConfig {
     pub url: Url,
     pub api_key: Option<String>,
+    // Overrides global `contracts_info_addr` for the chain, if specified
+    #[serde(default)]
+    pub contracts_info_url: Option<Url>,
+    #[serde(default)]
+    pub contracts_info_api_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields, default)]
+pub struct ChainsReloadSettings {
+    // Only applies if the chains config has been loaded from `chains_config_path`
+    pub enabled: bool,
+    // How often the chains config file is checked for modifications
+    pub interval_secs: u64,
+}
+
+impl Default for ChainsReloadSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: 30,
+        }
+    }
+}
+
+impl ChainsReloadSettings {
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_secs)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields, default)]
+pub struct HealthCheckSettings {
+    // How often the database and external services are checked
+    pub interval_secs: u64,
+    // How long a single dependency check may take before it is considered failed
+    pub timeout_secs: u64,
+}
+
+impl Default for HealthCheckSettings {
+    fn default() -> Self {
+        Self {
+            interval_secs: 30,
+            timeout_secs: 5,
+        }
+    }
+}
+
+impl HealthCheckSettings {
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_secs)
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs)
+    }
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(deny_unknown_fields, default)]
 pub struct ChainsSettings {
     pub networks: HashMap<i64, ChainConfig>,
+    // Modification time of the file the settings have been loaded from.
+    // Not set for settings which have not been loaded from a file.
+    #[serde(skip)]
+    pub modified_at: Option<SystemTime>,
 }
 
 impl ChainsSettings {
     pub fn new(config_path: &Path) -> anyhow::Result<Self> {
-        let settings = Config::builder()
+        // Taken before the file is read, so that modifications
+        // made while reading are detected on the next check
+        let modified_at = modified_at(config_path);
+        let mut settings: Self = Config::builder()
             .add_source(File::from(config_path))
             .add_source(config::Environment::with_prefix("ADMIN_RS__CHAINS_CONFIG").separator("__"))
             .build()?
             .try_deserialize()?;
+        settings.modified_at = modified_at;
         Ok(settings)
     }
+
+    /// Returns a copy of the settings with api keys hidden, so that it can be logged.
+    pub fn redacted(&self) -> Self {
+        let redact = |key: &Option<String>| key.as_ref().map(|_| REDACTED.to_string());
+        let networks = self
+            .networks
+            .iter()
+            .map(|(chain_id, config)| {
+                let config = ChainConfig {
+                    api_key: redact(&config.api_key),
+                    contracts_info_api_key: redact(&config.contracts_info_api_key),
+                    ..config.clone()
+                };
+                (*chain_id, config)
+            })
+            .collect();
+        Self {
+            networks,
+            modified_at: self.modified_at,
+        }
+    }
+}
+
+const REDACTED: &str = "<redacted>";
+
+pub(crate) fn modified_at(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
 }
 
 fn default_chains_config_path() -> PathBuf {
@@ -125,6 +237,11 @@ impl Settings {
             selectors_list_path: default_selectors_list_path(),
             chains_config_path: default_chains_config_path(),
             chains_config: Default::default(),
+            chains_config_reload: Default::default(),
+            auth_cache: Default::default(),
+            health_check: Default::default(),
+            idempotency: Default::default(),
+            admin_api_key: Default::default(),
             config_path: Default::default(),
         }
     }
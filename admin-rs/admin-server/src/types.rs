@@ -1,17 +1,30 @@
+use crate::errors::{self, ErrorBuilder, Reason};
 use admin_core::submissions;
-use admin_proto::blockscout::admin::v1::{TokenInfoSubmission, TokenInfoSubmissionStatus};
+use admin_proto::blockscout::admin::v1::{
+    review_comment, search_token_info_submissions_admin_request,
+    token_info_submission_field_descriptor, BlockscoutUser, LocalizedDescription, ReviewComment,
+    SearchTokenInfoSubmissionsAdminRequest, SearchedTokenInfoSubmission, TokenInfoSubmission,
+    TokenInfoSubmissionFieldChange, TokenInfoSubmissionFieldDescriptor,
+    TokenInfoSubmissionRevision, TokenInfoSubmissionStatus, UserData, UserDataSubmission,
+};
 use blockscout_display_bytes::Bytes;
-use chrono::NaiveDateTime;
+use chrono::{DateTime, NaiveDateTime};
 use std::str::FromStr;
-use tonic::Status;
+use tonic::{Code, Status};
 
 pub fn convert_submission(s: submissions::Submission) -> TokenInfoSubmission {
     TokenInfoSubmission {
+        etag: s.etag(),
         id: s.id as u64,
         token_address: s.token_address.to_string(),
         status: convert_status(s.status).into(),
         updated_at: convert_datetime(s.updated_at),
         admin_comments: s.admin_comments,
+        token_name: s.token_name,
+        token_symbol: s.token_symbol,
+        token_type: s.token_type,
+        token_decimals: s.token_decimals.map(|decimals| decimals as u32),
+        token_holders: s.token_holders.map(|holders| holders as u64),
         requester_name: s.requester_name,
         requester_email: s.requester_email,
         project_name: s.project_name,
@@ -36,6 +49,15 @@ pub fn convert_submission(s: submissions::Submission) -> TokenInfoSubmission {
         coin_market_cap_ticker: s.coin_market_cap_ticker,
         coin_gecko_ticker: s.coin_gecko_ticker,
         defi_llama_ticker: s.defi_llama_ticker,
+        localized_descriptions: s
+            .localized_descriptions
+            .into_iter()
+            .map(|localized| LocalizedDescription {
+                language: localized.language,
+                project_name: localized.project_name,
+                project_description: localized.project_description,
+            })
+            .collect(),
     }
 }
 
@@ -48,6 +70,129 @@ fn convert_status(sub: submissions::Status) -> TokenInfoSubmissionStatus {
     }
 }
 
+pub fn convert_searched_submission(s: submissions::Submission) -> SearchedTokenInfoSubmission {
+    SearchedTokenInfoSubmission {
+        chain_id: s.chain_id as u64,
+        risk_score: s.risk_score,
+        risk_reasons: s.risk_reasons.iter().map(ToString::to_string).collect(),
+        submission: Some(convert_submission(s)),
+    }
+}
+
+pub fn convert_user_data(user_data: submissions::UserData) -> UserData {
+    UserData {
+        submissions: user_data
+            .submissions
+            .into_iter()
+            .map(|data| UserDataSubmission {
+                chain_id: data.submission.chain_id as u64,
+                submission: Some(convert_submission(data.submission)),
+                review_comments: data
+                    .review_comments
+                    .into_iter()
+                    .map(convert_review_comment)
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+fn convert_review_comment(comment: submissions::ReviewComment) -> ReviewComment {
+    let kind = match comment.kind {
+        submissions::ReviewCommentKind::WaitingForUpdate => review_comment::Kind::UpdateRequired,
+        submissions::ReviewCommentKind::Rejected => review_comment::Kind::Rejected,
+    };
+    ReviewComment {
+        kind: kind.into(),
+        comment: comment.comment,
+        created_at: convert_datetime(comment.created_at),
+    }
+}
+
+pub fn convert_revision(revision: submissions::Revision) -> TokenInfoSubmissionRevision {
+    TokenInfoSubmissionRevision {
+        revision: revision.revision,
+        created_at: convert_datetime(revision.created_at),
+        fields: revision.data.into_iter().collect(),
+    }
+}
+
+pub fn convert_field_change(change: submissions::FieldChange) -> TokenInfoSubmissionFieldChange {
+    TokenInfoSubmissionFieldChange {
+        field: change.field,
+        old_value: change.old_value,
+        new_value: change.new_value,
+    }
+}
+
+pub fn convert_field_descriptor(
+    field: submissions::FieldDescriptor,
+) -> TokenInfoSubmissionFieldDescriptor {
+    let kind = match field.kind {
+        submissions::FieldKind::Address => token_info_submission_field_descriptor::Kind::Address,
+        submissions::FieldKind::Text => token_info_submission_field_descriptor::Kind::Text,
+        submissions::FieldKind::Selector => token_info_submission_field_descriptor::Kind::Selector,
+    };
+    TokenInfoSubmissionFieldDescriptor {
+        name: field.name.to_string(),
+        kind: kind.into(),
+        required: field.required,
+        max_length: field.max_length as u32,
+        allowed_values: field.allowed_values,
+    }
+}
+
+/// Copies user editable fields listed in the update mask from `source` to `target`.
+pub fn apply_update_mask(
+    target: &mut TokenInfoSubmission,
+    source: &TokenInfoSubmission,
+    paths: &[String],
+) -> Result<(), Status> {
+    for path in paths {
+        match path.as_str() {
+            "token_address" => target.token_address = source.token_address.clone(),
+            "requester_name" => target.requester_name = source.requester_name.clone(),
+            "requester_email" => target.requester_email = source.requester_email.clone(),
+            "project_name" => target.project_name = source.project_name.clone(),
+            "project_website" => target.project_website = source.project_website.clone(),
+            "project_email" => target.project_email = source.project_email.clone(),
+            "icon_url" => target.icon_url = source.icon_url.clone(),
+            "project_description" => {
+                target.project_description = source.project_description.clone()
+            }
+            "project_sector" => target.project_sector = source.project_sector.clone(),
+            "comment" => target.comment = source.comment.clone(),
+            "docs" => target.docs = source.docs.clone(),
+            "github" => target.github = source.github.clone(),
+            "telegram" => target.telegram = source.telegram.clone(),
+            "linkedin" => target.linkedin = source.linkedin.clone(),
+            "discord" => target.discord = source.discord.clone(),
+            "slack" => target.slack = source.slack.clone(),
+            "twitter" => target.twitter = source.twitter.clone(),
+            "open_sea" => target.open_sea = source.open_sea.clone(),
+            "facebook" => target.facebook = source.facebook.clone(),
+            "medium" => target.medium = source.medium.clone(),
+            "reddit" => target.reddit = source.reddit.clone(),
+            "support" => target.support = source.support.clone(),
+            "coin_market_cap_ticker" => {
+                target.coin_market_cap_ticker = source.coin_market_cap_ticker.clone()
+            }
+            "coin_gecko_ticker" => target.coin_gecko_ticker = source.coin_gecko_ticker.clone(),
+            "defi_llama_ticker" => target.defi_llama_ticker = source.defi_llama_ticker.clone(),
+            "localized_descriptions" => {
+                target.localized_descriptions = source.localized_descriptions.clone()
+            }
+            _ => {
+                return Err(errors::invalid_argument(
+                    "update_mask",
+                    format!("{path} is not a user editable field"),
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
 fn convert_datetime(datetime: NaiveDateTime) -> String {
     datetime.format("%F %T%.6fZ").to_string()
 }
@@ -56,17 +201,26 @@ pub fn validate_input_submission(
     sub: TokenInfoSubmission,
     id: Option<i64>,
     chain_id: i64,
-    user_email: String,
+    user: submissions::User,
 ) -> Result<submissions::Submission, Status> {
     let validated_submission = submissions::Submission {
         id: id.unwrap_or_default(),
         status: submissions::Status::InProcess,
         updated_at: Default::default(),
+        version: Default::default(),
+        risk_score: Default::default(),
+        risk_reasons: Default::default(),
         chain_id,
         admin_comments: None,
-        blockscout_user_email: user_email,
+        blockscout_user_id: Some(user.id),
+        blockscout_user_email: user.email,
+        token_name: None,
+        token_symbol: None,
+        token_type: None,
+        token_decimals: None,
+        token_holders: None,
         token_address: Bytes::from_str(&sub.token_address)
-            .map_err(|e| Status::invalid_argument(e.to_string()))?,
+            .map_err(|e| errors::invalid_argument("token_address", e.to_string()))?,
         requester_name: sub.requester_name,
         requester_email: sub.requester_email,
         project_name: sub.project_name,
@@ -91,12 +245,178 @@ pub fn validate_input_submission(
         coin_market_cap_ticker: sub.coin_market_cap_ticker,
         coin_gecko_ticker: sub.coin_gecko_ticker,
         defi_llama_ticker: sub.defi_llama_ticker,
+        localized_descriptions: sub
+            .localized_descriptions
+            .into_iter()
+            .map(|localized| submissions::LocalizedDescription {
+                language: localized.language,
+                project_name: localized.project_name,
+                project_description: localized.project_description,
+            })
+            .collect(),
     };
     Ok(validated_submission)
 }
 pub fn validate_input_chain_id(chain_id: u64) -> Result<i64, Status> {
     let chain_id = chain_id
         .try_into()
-        .map_err(|_| Status::invalid_argument("invalid chain_id"))?;
+        .map_err(|_| errors::invalid_argument("chain_id", "value is too large"))?;
     Ok(chain_id)
 }
+
+pub fn validate_data_subject(
+    user_id: Option<String>,
+    user_email: Option<String>,
+) -> Result<submissions::DataSubject, Status> {
+    let user_id = user_id.filter(|id| !id.is_empty());
+    let user_email = user_email.filter(|email| !email.is_empty());
+    match (user_id, user_email) {
+        (Some(id), Some(email)) => Ok(submissions::DataSubject::User(submissions::User::new(
+            id, email,
+        ))),
+        (Some(id), None) => Ok(submissions::DataSubject::Id(id)),
+        (None, Some(email)) => Ok(submissions::DataSubject::Email(email)),
+        (None, None) => Err(ErrorBuilder::new(
+            Code::InvalidArgument,
+            Reason::InvalidArgument,
+            "either user_id or user_email must be specified",
+        )
+        .field_violation("user_id", "either user_id or user_email must be specified")
+        .field_violation(
+            "user_email",
+            "either user_id or user_email must be specified",
+        )
+        .build()),
+    }
+}
+
+/// Users are identified by the email in requests across all chains,
+/// as Blockscout user ids are specific to the chain.
+pub fn validate_user_email(user_email: String) -> Result<submissions::DataSubject, Status> {
+    if user_email.is_empty() {
+        return Err(ErrorBuilder::new(
+            Code::InvalidArgument,
+            Reason::InvalidArgument,
+            "user_email must not be empty",
+        )
+        .field_violation("user_email", "must not be empty")
+        .build());
+    }
+    Ok(submissions::DataSubject::Email(user_email))
+}
+
+pub fn validate_blockscout_users(
+    users: Vec<BlockscoutUser>,
+) -> Result<Vec<submissions::User>, Status> {
+    if users.is_empty() {
+        return Err(ErrorBuilder::new(
+            Code::InvalidArgument,
+            Reason::InvalidArgument,
+            "users must not be empty",
+        )
+        .field_violation("users", "must not be empty")
+        .build());
+    }
+    users
+        .into_iter()
+        .enumerate()
+        .map(|(i, user)| {
+            for (field, value) in [("id", &user.id), ("email", &user.email)] {
+                if value.is_empty() {
+                    return Err(ErrorBuilder::new(
+                        Code::InvalidArgument,
+                        Reason::InvalidArgument,
+                        format!("users[{i}].{field} must not be empty"),
+                    )
+                    .field_violation(format!("users[{i}].{field}"), "must not be empty")
+                    .build());
+                }
+            }
+            Ok(submissions::User::new(user.id, user.email))
+        })
+        .collect()
+}
+
+/// Returns the search query together with the requested page size and token.
+pub fn validate_search_query(
+    request: SearchTokenInfoSubmissionsAdminRequest,
+) -> Result<
+    (
+        submissions::SearchQuery,
+        u64,
+        Option<submissions::PageToken>,
+    ),
+    Status,
+> {
+    let statuses = request
+        .statuses
+        .into_iter()
+        .map(validate_input_status)
+        .collect::<Result<_, _>>()?;
+    let chain_ids = request
+        .chain_ids
+        .into_iter()
+        .map(validate_input_chain_id)
+        .collect::<Result<_, _>>()?;
+    let query = submissions::SearchQuery {
+        text: request.query,
+        statuses,
+        chain_ids,
+        created_from: request
+            .created_from
+            .map(|value| validate_input_datetime("created_from", &value))
+            .transpose()?,
+        created_to: request
+            .created_to
+            .map(|value| validate_input_datetime("created_to", &value))
+            .transpose()?,
+        order: validate_input_order(request.order)?,
+    };
+    let page_size = request
+        .page_size
+        .map(u64::from)
+        .unwrap_or(submissions::DEFAULT_PAGE_SIZE);
+    let page_token = request
+        .page_token
+        .filter(|token| !token.is_empty())
+        .map(|token| {
+            token
+                .parse()
+                .map_err(|_| errors::invalid_argument("page_token", "malformed page token"))
+        })
+        .transpose()?;
+    Ok((query, page_size, page_token))
+}
+
+fn validate_input_status(status: i32) -> Result<submissions::Status, Status> {
+    match TokenInfoSubmissionStatus::from_i32(status) {
+        Some(TokenInfoSubmissionStatus::Approved) => Ok(submissions::Status::Approved),
+        Some(TokenInfoSubmissionStatus::InProcess) => Ok(submissions::Status::InProcess),
+        Some(TokenInfoSubmissionStatus::Rejected) => Ok(submissions::Status::Rejected),
+        Some(TokenInfoSubmissionStatus::UpdateRequired) => {
+            Ok(submissions::Status::WaitingForUpdate)
+        }
+        Some(TokenInfoSubmissionStatus::StatusUnknown) | None => Err(errors::invalid_argument(
+            "statuses",
+            format!("unknown status {status}"),
+        )),
+    }
+}
+
+fn validate_input_order(order: i32) -> Result<submissions::SearchOrder, Status> {
+    use search_token_info_submissions_admin_request::Order;
+    match Order::from_i32(order) {
+        Some(Order::Newest) => Ok(submissions::SearchOrder::Newest),
+        Some(Order::Risk) => Ok(submissions::SearchOrder::Risk),
+        None => Err(errors::invalid_argument(
+            "order",
+            format!("unknown order {order}"),
+        )),
+    }
+}
+
+fn validate_input_datetime(field: &str, value: &str) -> Result<NaiveDateTime, Status> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|datetime| datetime.naive_utc())
+        .map_err(|e| errors::invalid_argument(field, e.to_string()))
+}
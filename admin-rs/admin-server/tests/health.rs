@@ -0,0 +1,87 @@
+mod helpers;
+
+use crate::helpers::{db::init_db, server::init_server};
+use admin_proto::blockscout::admin::v1::{
+    health_check_response::ServingStatus, HealthCheckResponse,
+};
+use reqwest::StatusCode;
+use std::time::Duration;
+use url::Url;
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+async fn get_status(base: &Url, service: &str) -> Option<ServingStatus> {
+    let mut url = base.join("/health").unwrap();
+    url.query_pairs_mut().append_pair("service", service);
+    let response = reqwest::get(url).await.expect("failed to send request");
+    if response.status() == StatusCode::NOT_FOUND {
+        return None;
+    }
+    let response: HealthCheckResponse = response.json().await.expect("invalid response");
+    Some(response.status())
+}
+
+/// Waits for the dependencies to be checked at least once.
+async fn wait_for_status(base: &Url, service: &str) -> Option<ServingStatus> {
+    for _ in 0..50 {
+        let status = get_status(base, service).await;
+        if status != Some(ServingStatus::Unknown) {
+            return status;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("dependencies of {service:?} have not been checked in time")
+}
+
+#[tokio::test]
+#[ignore = "Needs db to run"]
+async fn health_reports_dependency_statuses() {
+    let db = init_db("health", "health_reports_dependency_statuses").await;
+    let blockscout = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v1/health"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&blockscout)
+        .await;
+    let contracts_info = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/health"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&contracts_info)
+        .await;
+    let config = serde_json::from_value(serde_json::json!({
+        "networks": {
+            "77": {
+                "url": blockscout.uri(),
+            }
+        }
+    }))
+    .unwrap();
+    let base = init_server(
+        db.db_url(),
+        config,
+        contracts_info.uri().parse().unwrap(),
+        None,
+    )
+    .await;
+
+    assert_eq!(
+        Some(ServingStatus::Serving),
+        wait_for_status(&base, "").await
+    );
+    assert_eq!(
+        Some(ServingStatus::Serving),
+        get_status(&base, "database").await
+    );
+    assert_eq!(
+        Some(ServingStatus::Serving),
+        get_status(&base, "chains/77/blockscout").await
+    );
+    assert_eq!(
+        Some(ServingStatus::NotServing),
+        get_status(&base, "chains/77/contracts-info").await
+    );
+    assert_eq!(None, get_status(&base, "chains/1/blockscout").await);
+}
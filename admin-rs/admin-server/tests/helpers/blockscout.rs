@@ -0,0 +1,22 @@
+#![allow(dead_code)]
+
+use serde_json::json;
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
+
+pub async fn mock_token(blockscout: &MockServer, token_address: &str) {
+    Mock::given(method("GET"))
+        .and(path(format!("/api/v2/tokens/{token_address}")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "address": token_address,
+            "name": "Cafe Token",
+            "symbol": "CAFE",
+            "type": "ERC-20",
+            "decimals": "18",
+            "holders": "42",
+        })))
+        .mount(blockscout)
+        .await;
+}
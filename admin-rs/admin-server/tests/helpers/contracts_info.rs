@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use serde_json::json;
 use wiremock::{
     matchers::{method, path},
@@ -1,3 +1,4 @@
+pub mod blockscout;
 pub mod contracts_info;
 pub mod db;
 pub mod server;
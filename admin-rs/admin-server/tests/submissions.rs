@@ -2,8 +2,8 @@ mod helpers;
 
 use crate::helpers::{db::init_db, server::init_server};
 use admin_proto::blockscout::admin::v1::{
-    ListTokenInfoSubmissionSelectorsResponse, ListTokenInfoSubmissionsResponse,
-    TokenInfoSubmission, TokenInfoSubmissionStatus,
+    EraseUserDataResponse, ListTokenInfoSubmissionSelectorsResponse,
+    ListTokenInfoSubmissionsResponse, TokenInfoSubmission, TokenInfoSubmissionStatus, UserData,
 };
 use admin_server::ChainsSettings;
 use blockscout_auth::{init_mocked_blockscout_auth_service, MockUser};
@@ -11,16 +11,27 @@ use entity::{
     rejected_submissions, sea_orm_active_enums::SubmissionStatus, submissions,
     waiting_for_update_submissions,
 };
-use helpers::contracts_info::init_mocked_contracts_info_service;
+use helpers::{blockscout::mock_token, contracts_info::init_mocked_contracts_info_service};
 use pretty_assertions::assert_eq;
 use reqwest::StatusCode;
 use sea_orm::{prelude::*, sea_query::Expr, ActiveValue::Set};
 use std::{fs::File, io::Write, str::FromStr};
 use url::Url;
-use wiremock::MockServer;
+use wiremock::{
+    matchers::{method, path},
+    Mock, MockServer, ResponseTemplate,
+};
 
 const ROUTE_MANY: &str = "/api/v1/chains/{chain_id}/token-info-submissions";
 const ROUTE_SINGLE: &str = "/api/v1/chains/{chain_id}/token-info-submissions/{id}";
+const ROUTE_USER_DATA: &str = "/api/v1/chains/{chain_id}/user-data";
+const ROUTE_USER_DATA_ERASE: &str = "/api/v1/chains/{chain_id}/user-data:erase";
+const ROUTE_USER_DATA_EXPORT_ADMIN: &str = "/api/v1/chains/{chain_id}/admin/user-data:export";
+const ROUTE_USER_DATA_EXPORT_ALL_CHAINS_ADMIN: &str = "/api/v1/admin/user-data:export";
+const ROUTE_USER_DATA_ERASE_ALL_CHAINS_ADMIN: &str = "/api/v1/admin/user-data:erase";
+const ROUTE_SEARCH_ADMIN: &str = "/api/v1/admin/token-info-submissions:search";
+const ROUTE_EXPORT_ADMIN: &str = "/api/v1/admin/token-info-submissions:export";
+const ROUTE_REVISIONS_ADMIN: &str = "/api/v1/admin/token-info-submissions/{id}/revisions";
 
 const CAFE_ADDRESS_CHECKSUM: &str = "0xCAfEcAfeCAfECaFeCaFecaFecaFECafECafeCaFe";
 const CAFE_ADDRESS_LOWER: &str = "0xcafecafecafecafecafecafecafecafecafecafe";
@@ -84,6 +95,7 @@ async fn check_get_list(
     for (mut from_list, expected) in submissions_from_list.submissions.into_iter().zip(expected) {
         if expected.updated_at.is_empty() {
             from_list.updated_at = "".to_string();
+            from_list.etag = "".to_string();
         }
         assert_eq!(&&from_list, expected, "invalid submission")
     }
@@ -111,6 +123,7 @@ async fn check_get_list(
             .expect("failed to convert response data to submission");
         if submission.updated_at.is_empty() {
             submission_from_get.updated_at = "".to_string();
+            submission_from_get.etag = "".to_string();
         }
         assert_eq!(&submission_from_get, *submission);
     }
@@ -135,6 +148,7 @@ async fn init(
         }],
     )
     .await;
+    mock_token(&blockscout, CAFE_ADDRESS_LOWER).await;
     let config = serde_json::from_value(serde_json::json!({
         "networks": {
             "77": {
@@ -208,6 +222,10 @@ async fn basic() {
     );
     assert_eq!(submission_from_create.token_address, CAFE_ADDRESS_LOWER);
     assert_eq!(submission_from_create.project_name.as_deref(), Some(data));
+    assert_eq!(submission_from_create.token_symbol.as_deref(), Some("CAFE"));
+    assert_eq!(submission_from_create.token_type.as_deref(), Some("ERC-20"));
+    assert_eq!(submission_from_create.token_decimals, Some(18));
+    assert_eq!(submission_from_create.token_holders, Some(42));
 
     // LIST + GET
     check_get_list(chain_id, jwt, &server_base_url, &[&submission_from_create]).await;
@@ -340,6 +358,13 @@ async fn check_auth() {
         }],
     )
     .await;
+    // The token is not requested from blockscout for users without permission
+    Mock::given(method("GET"))
+        .and(path(format!("/api/v2/tokens/{CAFE_ADDRESS_LOWER}")))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&blockscout)
+        .await;
     let config = serde_json::from_value(serde_json::json!({
         "networks": {
             "77": {
@@ -457,10 +482,27 @@ async fn list_token_info_submission_selectors() {
     let selectors: ListTokenInfoSubmissionSelectorsResponse =
         response.json().await.expect("failed to convert response");
 
-    let expected = ListTokenInfoSubmissionSelectorsResponse {
-        project_sectors: project_sectors.iter().map(|v| v.to_string()).collect(),
-    };
-    assert_eq!(expected, selectors, "Invalid selectors returned");
+    let expected_project_sectors: Vec<_> = project_sectors.iter().map(|v| v.to_string()).collect();
+    assert_eq!(
+        expected_project_sectors, selectors.project_sectors,
+        "Invalid selectors returned"
+    );
+    let project_sector_field = selectors
+        .fields
+        .iter()
+        .find(|field| field.name == "project_sector")
+        .expect("project sector field is missing");
+    assert_eq!(
+        expected_project_sectors, project_sector_field.allowed_values,
+        "Invalid allowed values of project sector field"
+    );
+    assert!(
+        selectors
+            .fields
+            .iter()
+            .any(|field| field.name == "project_description" && field.required),
+        "Project description field should be required"
+    );
 }
 
 #[ignore = "Needs db to run"]
@@ -536,6 +578,7 @@ async fn correct_admin_comments() {
     submission_from_create.admin_comments = Some("invalid icon url".into());
     submission_from_create.status = TokenInfoSubmissionStatus::UpdateRequired.into();
     submission_from_create.updated_at = "".into(); // ignore
+    submission_from_create.etag = "".into(); // ignore
     check_get_list(chain_id, jwt, &server_base_url, &[&submission_from_create]).await;
 
     waiting_for_update_submissions::Entity::insert(waiting_for_update_submissions::ActiveModel {
@@ -572,3 +615,386 @@ async fn correct_admin_comments() {
     submission_from_create.status = TokenInfoSubmissionStatus::Rejected.into();
     check_get_list(chain_id, jwt, &server_base_url, &[&submission_from_create]).await;
 }
+
+#[ignore = "Needs db to run"]
+#[tokio::test]
+async fn partial_update_checks_etag() {
+    let db = init_db("submissions", "partial_update_checks_etag").await;
+    let chain_id = 77;
+    let jwt = "jwt1";
+    let csrf_token = "csrf1";
+    let (_blockscout_mock, server_base_url) = init(
+        Some("apikey"),
+        "user@gmail.com".into(),
+        chain_id as i64,
+        jwt,
+        csrf_token,
+        db.db_url(),
+    )
+    .await;
+
+    let data = "some data";
+    let route = ROUTE_MANY.replace("{chain_id}", &chain_id.to_string());
+    let request = serde_json::json!({ "submission": mock_submission(data) });
+    let submission_from_create: TokenInfoSubmission = reqwest::Client::new()
+        .post(server_base_url.join(route.as_str()).unwrap())
+        .json(&request)
+        .header("cookie", &format!("_explorer_key={jwt}"))
+        .header("x-csrf-token", csrf_token)
+        .send()
+        .await
+        .expect("Failed to send request")
+        .json()
+        .await
+        .expect("failed to convert response data to submission");
+    let outdated_etag = submission_from_create.etag.clone();
+
+    submissions::Entity::update_many()
+        .col_expr(
+            submissions::Column::Status,
+            Expr::value(SubmissionStatus::WaitingForUpdate.as_enum()),
+        )
+        .exec(db.client().as_ref())
+        .await
+        .expect("failed to manually update database");
+    let route = ROUTE_SINGLE
+        .replace("{chain_id}", &chain_id.to_string())
+        .replace("{id}", &submission_from_create.id.to_string());
+    let current: TokenInfoSubmission = reqwest::Client::new()
+        .get(server_base_url.join(route.as_str()).unwrap())
+        .header("cookie", &format!("_explorer_key={jwt}"))
+        .send()
+        .await
+        .expect("Failed to send request")
+        .json()
+        .await
+        .expect("failed to convert response data to submission");
+
+    let patch = |etag: &str| {
+        reqwest::Client::new()
+            .patch(server_base_url.join(route.as_str()).unwrap())
+            .json(&serde_json::json!({
+                "submission": { "projectName": "new name" },
+                "updateMask": { "paths": ["project_name"] },
+                "etag": etag,
+            }))
+            .header("cookie", &format!("_explorer_key={jwt}"))
+            .header("x-csrf-token", csrf_token)
+            .send()
+    };
+
+    // The submission has been modified since it was created
+    let response = patch(&outdated_etag).await.expect("Failed to send request");
+    assert_eq!(
+        response.status(),
+        StatusCode::CONFLICT,
+        "invalid status code: {}. response: {}",
+        response.status(),
+        response.text().await.unwrap()
+    );
+
+    let response = patch(&current.etag).await.expect("Failed to send request");
+    assert!(
+        response.status().is_success(),
+        "invalid status code: {}. response: {}",
+        response.status(),
+        response.text().await.unwrap()
+    );
+    let submission_from_update: TokenInfoSubmission = response
+        .json()
+        .await
+        .expect("failed to convert response data to submission");
+    assert_eq!(
+        submission_from_update.project_name.as_deref(),
+        Some("new name")
+    );
+    // Fields out of the mask are not changed
+    assert_eq!(submission_from_update.docs.as_deref(), Some(data));
+    assert_eq!(submission_from_update.requester_name, data);
+    assert_eq!(submission_from_update.token_address, CAFE_ADDRESS_LOWER);
+    assert_ne!(submission_from_update.etag, current.etag);
+}
+
+#[ignore = "Needs db to run"]
+#[tokio::test]
+async fn chain_specific_contracts_info() {
+    let db = init_db("submissions", "chain_specific_contracts_info").await;
+    let db_url = db.db_url();
+    let chain_id = 77;
+    let api_key = Some("apikey");
+    let user_email = "user@gmail.com";
+    let jwt = "jwt1";
+    let csrf_token = "csrf1";
+
+    let blockscout = init_mocked_blockscout_auth_service(
+        api_key,
+        &[MockUser {
+            id: 0,
+            email: user_email.into(),
+            chain_id,
+            jwt: jwt.into(),
+            csrf_token: csrf_token.into(),
+        }],
+    )
+    .await;
+    mock_token(&blockscout, CAFE_ADDRESS_LOWER).await;
+    let contracts_info =
+        init_mocked_contracts_info_service(&[(user_email, chain_id, CAFE_ADDRESS_CHECKSUM)]).await;
+    let config = serde_json::from_value(serde_json::json!({
+        "networks": {
+            "77": {
+                "url": blockscout.uri(),
+                "api_key": api_key,
+                "contracts_info_url": contracts_info.uri(),
+            }
+        }
+    }))
+    .unwrap();
+    // global contracts info address must not be used for the chain
+    let contracts_info_addr = Url::from_str("http://127.0.0.1:1").unwrap();
+    let server_base_url = init_server(db_url, config, contracts_info_addr, None).await;
+
+    let route = ROUTE_MANY.replace("{chain_id}", &chain_id.to_string());
+    let request = serde_json::json!({ "submission": mock_submission("data") });
+    let response = reqwest::Client::new()
+        .post(server_base_url.join(route.as_str()).unwrap())
+        .json(&request)
+        .header("cookie", &format!("_explorer_key={jwt}"))
+        .header("x-csrf-token", csrf_token)
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert!(
+        response.status().is_success(),
+        "invalid status code: {}. response: {}",
+        response.status(),
+        response.text().await.unwrap()
+    );
+}
+
+#[ignore = "Needs db to run"]
+#[tokio::test]
+async fn export_and_erase_user_data() {
+    let db = init_db("submissions", "export_and_erase_user_data").await;
+    let db_url = db.db_url();
+    let chain_id = 77;
+    let api_key = Some("apikey");
+    let user_email = "user@gmail.com".into();
+    let jwt = "jwt1";
+    let csrf_token = "csrf1";
+    let (_blockscout_mock, server_base_url) = init(
+        api_key,
+        user_email,
+        chain_id as i64,
+        jwt,
+        csrf_token,
+        db_url,
+    )
+    .await;
+
+    let route = ROUTE_MANY.replace("{chain_id}", &chain_id.to_string());
+    let request = serde_json::json!({ "submission": mock_submission("data") });
+    let response = reqwest::Client::new()
+        .post(server_base_url.join(route.as_str()).unwrap())
+        .json(&request)
+        .header("cookie", &format!("_explorer_key={jwt}"))
+        .header("x-csrf-token", csrf_token)
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert!(
+        response.status().is_success(),
+        "invalid status code: {}. response: {}",
+        response.status(),
+        response.text().await.unwrap()
+    );
+
+    let base_url = &server_base_url;
+    let export = || async move {
+        let route = ROUTE_USER_DATA.replace("{chain_id}", &chain_id.to_string());
+        let response = reqwest::Client::new()
+            .get(base_url.join(route.as_str()).unwrap())
+            .header("cookie", &format!("_explorer_key={jwt}"))
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert!(
+            response.status().is_success(),
+            "invalid status code: {}. response: {}",
+            response.status(),
+            response.text().await.unwrap()
+        );
+        response
+            .json::<UserData>()
+            .await
+            .expect("failed to convert response data to user data")
+    };
+
+    let user_data = export().await;
+    assert_eq!(user_data.submissions.len(), 1);
+    let submission = user_data.submissions[0].submission.clone().unwrap();
+    assert_eq!(submission.requester_email, "data");
+
+    let route = ROUTE_USER_DATA_ERASE.replace("{chain_id}", &chain_id.to_string());
+    let response = reqwest::Client::new()
+        .post(server_base_url.join(route.as_str()).unwrap())
+        .json(&serde_json::json!({}))
+        .header("cookie", &format!("_explorer_key={jwt}"))
+        .header("x-csrf-token", csrf_token)
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert!(
+        response.status().is_success(),
+        "invalid status code: {}. response: {}",
+        response.status(),
+        response.text().await.unwrap()
+    );
+    let response: EraseUserDataResponse = response
+        .json()
+        .await
+        .expect("failed to convert response data to erase response");
+    assert_eq!(response.anonymized_submissions, 1);
+
+    // The submission does not belong to the user anymore
+    let user_data = export().await;
+    assert!(user_data.submissions.is_empty());
+
+    // Admin requests require an api key
+    for route in [
+        ROUTE_USER_DATA_EXPORT_ADMIN.replace("{chain_id}", &chain_id.to_string()),
+        ROUTE_USER_DATA_EXPORT_ALL_CHAINS_ADMIN.to_string(),
+        ROUTE_USER_DATA_ERASE_ALL_CHAINS_ADMIN.to_string(),
+    ] {
+        let response = reqwest::Client::new()
+            .post(server_base_url.join(route.as_str()).unwrap())
+            .json(&serde_json::json!({ "userEmail": "user@gmail.com" }))
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(
+            response.status(),
+            StatusCode::UNAUTHORIZED,
+            "invalid status code for {route}: {}. response: {}",
+            response.status(),
+            response.text().await.unwrap()
+        );
+    }
+}
+
+#[ignore = "Needs db to run"]
+#[tokio::test]
+async fn search_and_export_require_api_key() {
+    let db = init_db("submissions", "search_and_export_require_api_key").await;
+    let db_url = db.db_url();
+    let chain_id = 77;
+    let (_blockscout_mock, server_base_url) = init(
+        Some("apikey"),
+        "user@gmail.com".into(),
+        chain_id,
+        "jwt1",
+        "csrf1",
+        db_url,
+    )
+    .await;
+
+    for route in [ROUTE_SEARCH_ADMIN, ROUTE_EXPORT_ADMIN] {
+        let response = reqwest::Client::new()
+            .post(server_base_url.join(route).unwrap())
+            .json(&serde_json::json!({ "query": "data", "chainIds": [chain_id.to_string()] }))
+            .header("x-api-key", "invalid")
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(
+            response.status(),
+            StatusCode::UNAUTHORIZED,
+            "invalid status code for {route}: {}. response: {}",
+            response.status(),
+            response.text().await.unwrap()
+        );
+    }
+}
+
+#[ignore = "Needs db to run"]
+#[tokio::test]
+async fn create_for_non_token_fails() {
+    let db = init_db("submissions", "create_for_non_token_fails").await;
+    let db_url = db.db_url();
+    let chain_id = 77;
+    let jwt = "jwt1";
+    let csrf_token = "csrf1";
+    let (blockscout_mock, server_base_url) = init(
+        Some("apikey"),
+        "user@gmail.com".into(),
+        chain_id,
+        jwt,
+        csrf_token,
+        db_url,
+    )
+    .await;
+    // Takes precedence over the token mocked by `init`
+    Mock::given(method("GET"))
+        .and(path(format!("/api/v2/tokens/{CAFE_ADDRESS_LOWER}")))
+        .respond_with(
+            ResponseTemplate::new(404).set_body_json(serde_json::json!({ "message": "Not found" })),
+        )
+        .with_priority(1)
+        .mount(&blockscout_mock)
+        .await;
+
+    let route = ROUTE_MANY.replace("{chain_id}", &chain_id.to_string());
+    let request = serde_json::json!({ "submission": mock_submission("data") });
+    let response = reqwest::Client::new()
+        .post(server_base_url.join(route.as_str()).unwrap())
+        .json(&request)
+        .header("cookie", &format!("_explorer_key={jwt}"))
+        .header("x-csrf-token", csrf_token)
+        .send()
+        .await
+        .expect("Failed to send request");
+    assert_eq!(
+        response.status(),
+        StatusCode::BAD_REQUEST,
+        "invalid status code: {}. response: {}",
+        response.status(),
+        response.text().await.unwrap()
+    );
+}
+
+#[ignore = "Needs db to run"]
+#[tokio::test]
+async fn list_revisions_of_unknown_submission() {
+    let db = init_db("submissions", "list_revisions_of_unknown_submission").await;
+    let db_url = db.db_url();
+    let chain_id = 77;
+    let (_blockscout_mock, server_base_url) = init(
+        Some("apikey"),
+        "user@gmail.com".into(),
+        chain_id,
+        "jwt1",
+        "csrf1",
+        db_url,
+    )
+    .await;
+
+    let route = ROUTE_REVISIONS_ADMIN.replace("{id}", "1000");
+    for (api_key, expected_status) in [
+        ("invalid", StatusCode::UNAUTHORIZED),
+        ("apikey", StatusCode::NOT_FOUND),
+    ] {
+        let response = reqwest::Client::new()
+            .get(server_base_url.join(route.as_str()).unwrap())
+            .header("x-api-key", api_key)
+            .send()
+            .await
+            .expect("Failed to send request");
+        assert_eq!(
+            response.status(),
+            expected_status,
+            "invalid status code: {}. response: {}",
+            response.status(),
+            response.text().await.unwrap()
+        );
+    }
+}
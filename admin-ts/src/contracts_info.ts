@@ -31,10 +31,25 @@ export const sendRecordToService = async (record: BaseRecord) => {
     defiLlamaTicker: record.get("defi_llama_ticker"),
     tokenName: record.get("token_name"),
     tokenSymbol: record.get("token_symbol"),
+    localizedDescriptions: convertLocalizedDescriptions(
+      record.get("localized_descriptions")
+    ),
   };
   return await importTokenInfo(tokenInfo);
 };
 
+// Submissions store translations with snake_case keys, while token infos use camelCase ones
+const convertLocalizedDescriptions = (localized: any) => {
+  if (!Array.isArray(localized)) {
+    return [];
+  }
+  return localized.map((item: any) => ({
+    language: item.language,
+    projectName: item.project_name ?? item.projectName,
+    projectDescription: item.project_description ?? item.projectDescription,
+  }));
+};
+
 const importTokenInfo = async (tokenInfo: any) => {
   console.log(
     `import token info. address: ${tokenInfo.tokenAddress}, chainId: ${tokenInfo.chainId}`
@@ -215,6 +215,21 @@ pub mod api {
         Response::try_from_reqwest_response(response).await
     }
 
+    #[instrument(name = "blockscout_api:health", skip_all, err, level = "debug")]
+    pub async fn health(client: &Client, timeout: std::time::Duration) -> reqwest::Result<()> {
+        let url = client
+            .endpoint()
+            .join("/api/v1/health")
+            .expect("should be valid url");
+        reqwest::Client::new()
+            .get(url)
+            .timeout(timeout)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
     const API_KEY_NAME: &str = "api_key";
     async fn send_request_with_api_key(
         request: reqwest::RequestBuilder,
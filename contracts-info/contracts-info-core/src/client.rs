@@ -1,15 +1,39 @@
-use super::blockscout;
+use super::{
+    blockscout, rpc,
+    verify::{OwnershipConfig, OwnershipResolver},
+};
+use ethers::types::Address;
 use sea_orm::DatabaseConnection;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use url::Url;
 
 #[derive(Clone)]
 pub struct Client {
     pub db: Arc<DatabaseConnection>,
     pub blockscout: blockscout::Client,
+    pub rpc: Option<rpc::Client>,
     pub max_verified_addresses: u64,
+    /// If set, contracts owned by Safe wallets are verified only after
+    /// the threshold of the Safe owners have signed the verification message.
+    /// Otherwise, a signature of any owner is enough.
+    pub require_safe_threshold: bool,
+    /// How long signing messages are valid since they have been issued.
+    pub message_validity: Duration,
+    /// How far in the future message timestamps may be to tolerate clock differences.
+    pub max_clock_skew: Duration,
+    /// Rules defining who is allowed to verify the contract ownership.
+    pub ownership: OwnershipConfig,
+    /// If set, legacy messages without nonces are still accepted. Such messages
+    /// are not bound to the user and may be replayed until they expire.
+    /// Deprecated, to be removed once clients have switched to messages with nonces.
+    pub accept_messages_without_nonce: bool,
 }
 
+/// Default lifetime of signing messages.
+const DEFAULT_MESSAGE_VALIDITY: Duration = Duration::from_secs(24 * 60 * 60);
+/// Default tolerance for message timestamps in the future.
+const DEFAULT_MAX_CLOCK_SKEW: Duration = Duration::from_secs(5 * 60);
+
 impl Client {
     pub fn new(
         db: DatabaseConnection,
@@ -35,7 +59,70 @@ impl Client {
         Self {
             db,
             blockscout,
+            rpc: None,
             max_verified_addresses,
+            require_safe_threshold: false,
+            message_validity: DEFAULT_MESSAGE_VALIDITY,
+            max_clock_skew: DEFAULT_MAX_CLOCK_SKEW,
+            ownership: OwnershipConfig::default(),
+            accept_messages_without_nonce: false,
         }
     }
+
+    /// Sets the json-rpc endpoint of the chain, which is required
+    /// to verify signatures of smart contract wallets (EIP-1271).
+    pub fn with_rpc_url(mut self, rpc_url: Option<Url>, timeout: Duration) -> Self {
+        self.rpc = rpc_url.map(|rpc_url| rpc::Client::new(rpc_url, timeout));
+        self
+    }
+
+    pub fn with_require_safe_threshold(mut self, require_safe_threshold: bool) -> Self {
+        self.require_safe_threshold = require_safe_threshold;
+        self
+    }
+
+    pub fn with_message_validity(mut self, message_validity: Duration) -> Self {
+        self.message_validity = message_validity;
+        self
+    }
+
+    pub fn with_max_clock_skew(mut self, max_clock_skew: Duration) -> Self {
+        self.max_clock_skew = max_clock_skew;
+        self
+    }
+
+    pub fn with_ownership_resolvers(mut self, ownership_resolvers: Vec<OwnershipResolver>) -> Self {
+        self.ownership.resolvers = ownership_resolvers;
+        self
+    }
+
+    pub fn with_deployer_factories(mut self, deployer_factories: Vec<Address>) -> Self {
+        self.ownership.deployer_factories = deployer_factories;
+        self
+    }
+
+    /// If set, the sender of the creation transaction is considered to be
+    /// the creator of contracts created by other contracts.
+    pub fn with_resolve_contract_creators(mut self, resolve_contract_creators: bool) -> Self {
+        self.ownership.resolve_contract_creators = resolve_contract_creators;
+        self
+    }
+
+    /// Sets how many contracts may be between the contract owner and the signer.
+    /// Ownership chains are not followed if set to zero or if the json-rpc
+    /// endpoint is not set.
+    pub fn with_max_ownership_chain_depth(mut self, max_depth: usize) -> Self {
+        self.ownership.max_chain_depth = max_depth;
+        self
+    }
+
+    pub fn with_accept_messages_without_nonce(mut self, accept: bool) -> Self {
+        self.accept_messages_without_nonce = accept;
+        self
+    }
+
+    /// Checks that the blockscout instance of the chain is reachable and healthy.
+    pub async fn check_blockscout_health(&self, timeout: Duration) -> reqwest::Result<()> {
+        blockscout::api::health(&self.blockscout, timeout).await
+    }
 }
@@ -1,3 +1,5 @@
+use crate::verify::SafeOwners;
+use ethers::types::Address;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -9,12 +11,26 @@ pub enum Error {
         #[from]
         kind: crate::verify::Error,
     },
-    #[error("the contract address ownership was already verified by: {0}")]
-    AddressIsVerified(String),
+    #[error("the contract address ownership was already verified by: {owner_email}")]
+    AddressIsVerified {
+        owner_id: Option<String>,
+        owner_email: String,
+    },
     #[error("unexpected internal error: {0}")]
     Unexpected(String),
     #[error("user cannot have more than {0} verified addresses")]
     MaxVerifiedAddressesLimit(u64),
     #[error("blockscout request failed: {0}")]
     BlockscoutRequest(String),
+    #[error("verification message nonce is unknown, expired or has already been used")]
+    InvalidNonce,
+    #[error(
+        "{} of {} required safe owners have signed the verification message",
+        confirmed_signers.len(),
+        safe.threshold
+    )]
+    SafeConfirmationsPending {
+        safe: SafeOwners,
+        confirmed_signers: Vec<Address>,
+    },
 }
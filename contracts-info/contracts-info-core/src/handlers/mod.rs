@@ -1,10 +1,16 @@
 mod token_info;
+mod user;
+mod user_data;
 mod verified_address;
 
+pub use service_common::user::{DataSubject, User, ANONYMIZED_EMAIL};
 pub use token_info::{
-    get_token_info, import_token_info, list_user_token_infos, TokenInfo, TokenInfoProviderLevel,
+    get_token_info, import_token_info, list_user_token_infos, LocalizedDescription, TokenInfo,
+    TokenInfoProviderLevel,
 };
+pub use user::{backfill_user_ids, reconcile_user};
+pub use user_data::{erase_user_data, export_user_data, UserData};
 pub use verified_address::{
     get_verified_address, list_user_verified_addresses, prepare_address, verify_address,
-    VerifiedAddress,
+    MessageFormat, VerifiedAddress,
 };
@@ -1,14 +1,26 @@
-use crate::{blockscout, client::Client, errors::Error};
+use super::{user::owned_addresses_condition, User};
+use crate::{blockscout, client::Client, errors::Error, localization};
 use blockscout_display_bytes::Bytes as DisplayBytes;
 use entity::{token_infos, verified_addresses};
 use sea_orm::{
-    sea_query, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QuerySelect, QueryTrait,
-    Set, TransactionTrait,
+    sea_query::{self, Condition},
+    ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QuerySelect, QueryTrait, Set,
+    TransactionTrait,
 };
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 use tracing::instrument;
 
+/// Project name and description translated into the language
+/// identified by the BCP-47 `language` tag (e.g. `de`, `pt-BR`).
+#[derive(Clone, Debug, PartialOrd, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocalizedDescription {
+    pub language: String,
+    pub project_name: Option<String>,
+    pub project_description: String,
+}
+
 #[derive(Clone, Debug, PartialOrd, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TokenInfo {
@@ -37,6 +49,8 @@ pub struct TokenInfo {
     pub defi_llama_ticker: Option<String>,
     pub token_name: Option<String>,
     pub token_symbol: Option<String>,
+    #[serde(default)]
+    pub localized_descriptions: Vec<LocalizedDescription>,
 }
 
 impl TryFrom<token_infos::Model> for TokenInfo {
@@ -70,6 +84,8 @@ impl TryFrom<token_infos::Model> for TokenInfo {
             defi_llama_ticker: model.defi_llama_ticker,
             token_name: model.token_name,
             token_symbol: model.token_symbol,
+            localized_descriptions: serde_json::from_value(model.localized_descriptions)
+                .map_err(|e| Error::Unexpected(format!("invalid localized_descriptions: {e}")))?,
         })
     }
 }
@@ -103,10 +119,30 @@ impl TokenInfo {
             is_user_submitted: Set(is_user_submitted),
             token_name: Set(self.token_name),
             token_symbol: Set(self.token_symbol),
+            localized_descriptions: Set(serde_json::json!(self.localized_descriptions)),
             ..Default::default()
         }
     }
 
+    /// Replaces the project name and description with the translation best matching
+    /// the languages in `Accept-Language` header format. Returns the language
+    /// of the selected translation, or `None` if the default ones are kept.
+    pub fn localize(&mut self, accept_language: &str) -> Option<String> {
+        let ranges = localization::parse_accept_language(accept_language);
+        let index = localization::lookup(
+            &ranges,
+            self.localized_descriptions
+                .iter()
+                .map(|localized| localized.language.as_str()),
+        )?;
+        let localized = self.localized_descriptions[index].clone();
+        if let Some(project_name) = localized.project_name {
+            self.project_name = Some(project_name);
+        }
+        self.project_description = localized.project_description;
+        Some(localized.language)
+    }
+
     #[instrument(
         name = "insert_or_update_token_info", 
         skip_all,
@@ -155,6 +191,7 @@ impl TokenInfo {
                     token_infos::Column::CoinGeckoTicker,
                     token_infos::Column::DefiLlamaTicker,
                     token_infos::Column::IsUserSubmitted,
+                    token_infos::Column::LocalizedDescriptions,
                 ])
                 .to_owned(),
             )
@@ -208,19 +245,27 @@ pub async fn get_token_info(
     ret,
     level = "debug",
     fields(
-        user_email = user_email,
+        user_id = ?user.id,
         chain_id = ?chain_id,
     ))
 ]
 pub async fn list_user_token_infos(
     client: &Client,
-    user_email: String,
+    user: User,
+    chain_id: i64,
+) -> Result<Vec<TokenInfo>, Error> {
+    list_owned_token_infos(client, owned_addresses_condition(&user), chain_id).await
+}
+
+pub(crate) async fn list_owned_token_infos(
+    client: &Client,
+    owner_condition: Condition,
     chain_id: i64,
 ) -> Result<Vec<TokenInfo>, Error> {
     let verified_addresses_query = verified_addresses::Entity::find()
         .select_only()
         .column(verified_addresses::Column::Address)
-        .filter(verified_addresses::Column::OwnerEmail.eq(user_email))
+        .filter(owner_condition)
         .filter(verified_addresses::Column::ChainId.eq(chain_id))
         .into_query();
 
@@ -397,6 +442,10 @@ mod tests {
         }
     }
 
+    fn mock_user(email: &str) -> User {
+        User::new(format!("{email}_id"), email)
+    }
+
     fn init_verified_address_model(
         address: DisplayBytes,
         chain_id: i64,
@@ -441,6 +490,7 @@ mod tests {
             defi_llama_ticker: Some("defi_llama_ticker".into()),
             token_name: Some("token_name".into()),
             token_symbol: Some("token_symbol".into()),
+            localized_descriptions: vec![],
         }
     }
 
@@ -519,6 +569,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_localize_token_info() {
+        let address = DisplayBytes::from_str("0x000102030405060708090a0b0c0d0e0f10111213").unwrap();
+        let mut token_info = expected_token_info(address, CHAIN_ID_1, "Project".into());
+        token_info.localized_descriptions = vec![
+            LocalizedDescription {
+                language: "de".into(),
+                project_name: Some("Projekt".into()),
+                project_description: "Projektbeschreibung".into(),
+            },
+            LocalizedDescription {
+                language: "pt-BR".into(),
+                project_name: None,
+                project_description: "Descrição do projeto".into(),
+            },
+        ];
+
+        let mut default = token_info.clone();
+        assert_eq!(None, default.localize("fr, it;q=0.5"));
+        assert_eq!(token_info, default);
+
+        let mut german = token_info.clone();
+        assert_eq!(Some("de".into()), german.localize("fr, de-AT;q=0.8"));
+        assert_eq!(Some("Projekt".into()), german.project_name);
+        assert_eq!("Projektbeschreibung", german.project_description);
+
+        // The default project name is kept if it is not translated
+        let mut portuguese = token_info;
+        assert_eq!(Some("pt-BR".into()), portuguese.localize("pt-PT"));
+        assert_eq!(Some("Project".into()), portuguese.project_name);
+        assert_eq!("Descrição do projeto", portuguese.project_description);
+    }
+
     /********** list_user_token_infos ***********/
 
     #[tokio::test]
@@ -553,7 +636,7 @@ mod tests {
 
         let client = init_client(verified_addresses_data, token_infos_data, None).await;
 
-        let result = list_user_token_infos(&client, user_1.to_string(), CHAIN_ID_1)
+        let result = list_user_token_infos(&client, mock_user(user_1), CHAIN_ID_1)
             .await
             .expect("Error when listing user token infos");
 
@@ -584,7 +667,7 @@ mod tests {
         let token_infos_data = vec![];
         let client = init_client(verified_addresses_data, token_infos_data, None).await;
 
-        let result = list_user_token_infos(&client, user_1.to_string(), CHAIN_ID_1)
+        let result = list_user_token_infos(&client, mock_user(user_1), CHAIN_ID_1)
             .await
             .expect("Error when listing user token infos");
         assert!(result.is_empty(), "Result should be empty")
@@ -605,7 +688,7 @@ mod tests {
         let token_infos_data = vec![];
         let client = init_client(verified_addresses_data, token_infos_data, None).await;
 
-        let result = list_user_token_infos(&client, user_1.to_string(), CHAIN_ID_1)
+        let result = list_user_token_infos(&client, mock_user(user_1), CHAIN_ID_1)
             .await
             .expect("Error when listing user token infos");
         assert!(result.is_empty(), "Result should be empty")
@@ -641,7 +724,7 @@ mod tests {
         let client = init_client(verified_addresses_data, token_infos_data, None).await;
 
         // Chain id = 1
-        let result = list_user_token_infos(&client, user.to_string(), CHAIN_ID_1)
+        let result = list_user_token_infos(&client, mock_user(user), CHAIN_ID_1)
             .await
             .expect("Error when listing user token infos for the first chain id");
 
@@ -668,7 +751,7 @@ mod tests {
         );
 
         // Chain id = 2
-        let result = list_user_token_infos(&client, user.to_string(), CHAIN_ID_2)
+        let result = list_user_token_infos(&client, mock_user(user), CHAIN_ID_2)
             .await
             .expect("Error when listing user token infos for the second chain id");
 
@@ -735,7 +818,7 @@ mod tests {
         .await
         .expect("Error when importing new token info from extractor");
 
-        let result = list_user_token_infos(&client, user.to_string(), CHAIN_ID_1)
+        let result = list_user_token_infos(&client, mock_user(user), CHAIN_ID_1)
             .await
             .expect("Error when listing user token infos");
 
@@ -751,7 +834,7 @@ mod tests {
         .await
         .expect("Error when importing new token info from extractor");
 
-        let result = list_user_token_infos(&client, user.to_string(), CHAIN_ID_1)
+        let result = list_user_token_infos(&client, mock_user(user), CHAIN_ID_1)
             .await
             .expect("Error when listing user token infos");
 
@@ -767,7 +850,7 @@ mod tests {
         .await
         .expect("Error when importing new token info from admin_service");
 
-        let result = list_user_token_infos(&client, user.to_string(), CHAIN_ID_1)
+        let result = list_user_token_infos(&client, mock_user(user), CHAIN_ID_1)
             .await
             .expect("Error when listing user token infos");
 
@@ -785,7 +868,7 @@ mod tests {
         .await
         .expect("Error when importing new token info from extractor");
 
-        let result = list_user_token_infos(&client, user.to_string(), CHAIN_ID_1)
+        let result = list_user_token_infos(&client, mock_user(user), CHAIN_ID_1)
             .await
             .expect("Error when listing user token infos");
 
@@ -801,7 +884,7 @@ mod tests {
         .await
         .expect("Error when importing new token info from admin_service");
 
-        let result = list_user_token_infos(&client, user.to_string(), CHAIN_ID_1)
+        let result = list_user_token_infos(&client, mock_user(user), CHAIN_ID_1)
             .await
             .expect("Error when listing user token infos");
         assert_eq!(result, vec![old_token_info.clone(), new_token_info.clone()]);
@@ -0,0 +1,225 @@
+use super::{User, ANONYMIZED_EMAIL};
+use crate::{client::Client, errors::Error};
+use entity::verified_addresses;
+use sea_orm::{
+    sea_query::{Condition, Expr},
+    ColumnTrait, EntityTrait, QueryFilter, TransactionTrait,
+};
+use tracing::instrument;
+
+/// Addresses verified before user ids were stored have no owner id,
+/// so the owner is identified by the email for them.
+pub(crate) fn owned_addresses_condition(user: &User) -> Condition {
+    Condition::any()
+        .add(verified_addresses::Column::OwnerId.eq(user.id.as_str()))
+        .add(
+            Condition::all()
+                .add(verified_addresses::Column::OwnerId.is_null())
+                .add(verified_addresses::Column::OwnerEmail.eq(user.email.as_str())),
+        )
+}
+
+/// Brings stored ownership data in line with the current state of the user account:
+/// addresses without owner id which belong to the user email are assigned the user id,
+/// and addresses of the user get the current email, if it has been changed
+/// and has not been erased on the user request.
+///
+/// Returns the number of updated addresses.
+#[instrument(
+    skip_all,
+    err,
+    ret,
+    level = "debug",
+    fields(
+        user_id = ?user.id,
+    ))
+]
+pub async fn reconcile_user(client: &Client, user: &User) -> Result<u64, Error> {
+    let txn = client.db.begin().await?;
+    let backfilled = verified_addresses::Entity::update_many()
+        .col_expr(
+            verified_addresses::Column::OwnerId,
+            Expr::value(user.id.clone()),
+        )
+        .filter(verified_addresses::Column::OwnerId.is_null())
+        .filter(verified_addresses::Column::OwnerEmail.eq(user.email.as_str()))
+        .exec(&txn)
+        .await?
+        .rows_affected;
+    let email_updated = verified_addresses::Entity::update_many()
+        .col_expr(
+            verified_addresses::Column::OwnerEmail,
+            Expr::value(user.email.clone()),
+        )
+        .filter(verified_addresses::Column::OwnerId.eq(user.id.as_str()))
+        .filter(verified_addresses::Column::OwnerEmail.ne(user.email.as_str()))
+        // Erased emails must not be restored
+        .filter(verified_addresses::Column::OwnerEmail.ne(ANONYMIZED_EMAIL))
+        .exec(&txn)
+        .await?
+        .rows_affected;
+    txn.commit().await?;
+
+    if email_updated > 0 {
+        tracing::info!(
+            user_id = %user.id,
+            addresses = email_updated,
+            "owner email of verified addresses has been updated"
+        );
+    }
+    Ok(backfilled + email_updated)
+}
+
+/// Assigns owner ids to the addresses at the chain verified before the ids were stored.
+/// `users` are accounts of the chain Blockscout instance (e.g. exported from its database),
+/// which are matched with the owners by email. Addresses of users who are not
+/// authenticated anymore would otherwise be identified by the email only.
+///
+/// Returns the number of updated addresses.
+#[instrument(skip_all, err, ret, level = "debug", fields(chain_id = chain_id))]
+pub async fn backfill_user_ids(
+    client: &Client,
+    chain_id: i64,
+    users: &[User],
+) -> Result<u64, Error> {
+    let txn = client.db.begin().await?;
+    let mut updated = 0;
+    for user in users {
+        updated += verified_addresses::Entity::update_many()
+            .col_expr(
+                verified_addresses::Column::OwnerId,
+                Expr::value(user.id.clone()),
+            )
+            .filter(verified_addresses::Column::ChainId.eq(chain_id))
+            .filter(verified_addresses::Column::OwnerId.is_null())
+            .filter(verified_addresses::Column::OwnerEmail.eq(user.email.as_str()))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+    }
+    txn.commit().await?;
+
+    tracing::info!(
+        chain_id,
+        users = users.len(),
+        addresses = updated,
+        "owner ids of verified addresses have been backfilled"
+    );
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use migration::{Migrator, MigratorTrait};
+    use pretty_assertions::assert_eq;
+    use sea_orm::{ActiveValue::Set, QueryOrder};
+    use url::Url;
+
+    async fn init_client(
+        verified_addresses_data: impl IntoIterator<Item = verified_addresses::ActiveModel>,
+    ) -> Client {
+        let db = sea_orm::Database::connect("sqlite::memory:")
+            .await
+            .expect("Database connection error");
+        Migrator::up(&db, None).await.expect("Migrations failed");
+        verified_addresses::Entity::insert_many(verified_addresses_data)
+            .exec(&db)
+            .await
+            .expect("Predefined verified addresses insertion failed");
+
+        let blockscout_url = Url::parse("http://127.0.0.1:80").unwrap();
+        Client::new(db, blockscout_url, None, 100)
+    }
+
+    fn verified_address_model(
+        address: &str,
+        owner_id: Option<&str>,
+        owner_email: &str,
+    ) -> verified_addresses::ActiveModel {
+        verified_addresses::ActiveModel {
+            address: Set(address.into()),
+            chain_id: Set(1),
+            owner_id: Set(owner_id.map(str::to_string)),
+            owner_email: Set(owner_email.into()),
+            ..Default::default()
+        }
+    }
+
+    async fn owners(client: &Client) -> Vec<(Option<String>, String)> {
+        verified_addresses::Entity::find()
+            .order_by_asc(verified_addresses::Column::Address)
+            .all(client.db.as_ref())
+            .await
+            .expect("Verified addresses retrieval failed")
+            .into_iter()
+            .map(|model| (model.owner_id, model.owner_email))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn reconcile_user_backfills_id_and_updates_email() {
+        let client = init_client([
+            verified_address_model("0x01", None, "user@gmail.com"),
+            verified_address_model("0x02", Some("1"), "old_email@gmail.com"),
+            verified_address_model("0x03", None, "another@gmail.com"),
+            verified_address_model("0x04", Some("2"), "another@gmail.com"),
+        ])
+        .await;
+
+        let user = User::new("1", "user@gmail.com");
+        let updated = reconcile_user(&client, &user)
+            .await
+            .expect("Reconciliation failed");
+        assert_eq!(2, updated, "Invalid number of updated addresses");
+
+        let expected = vec![
+            (Some("1".to_string()), "user@gmail.com".to_string()),
+            (Some("1".to_string()), "user@gmail.com".to_string()),
+            (None, "another@gmail.com".to_string()),
+            (Some("2".to_string()), "another@gmail.com".to_string()),
+        ];
+        assert_eq!(expected, owners(&client).await);
+
+        let updated = reconcile_user(&client, &user)
+            .await
+            .expect("Second reconciliation failed");
+        assert_eq!(0, updated, "Reconciliation must be idempotent");
+    }
+
+    #[tokio::test]
+    async fn backfill_user_ids_assigns_ids_at_chain() {
+        let client = init_client([
+            verified_address_model("0x01", None, "user@gmail.com"),
+            verified_addresses::ActiveModel {
+                chain_id: Set(2),
+                ..verified_address_model("0x02", None, "user@gmail.com")
+            },
+            verified_address_model("0x03", None, "another@gmail.com"),
+            verified_address_model("0x04", Some("2"), "user@gmail.com"),
+        ])
+        .await;
+
+        let users = [
+            User::new("1", "user@gmail.com"),
+            User::new("3", "unknown@gmail.com"),
+        ];
+        let updated = backfill_user_ids(&client, 1, &users)
+            .await
+            .expect("Backfill failed");
+        assert_eq!(1, updated, "Invalid number of updated addresses");
+
+        let expected = vec![
+            (Some("1".to_string()), "user@gmail.com".to_string()),
+            (None, "user@gmail.com".to_string()),
+            (None, "another@gmail.com".to_string()),
+            (Some("2".to_string()), "user@gmail.com".to_string()),
+        ];
+        assert_eq!(expected, owners(&client).await);
+
+        let updated = backfill_user_ids(&client, 1, &users)
+            .await
+            .expect("Second backfill failed");
+        assert_eq!(0, updated, "Backfill must be idempotent");
+    }
+}
@@ -0,0 +1,226 @@
+use super::{
+    token_info::list_owned_token_infos, user::owned_addresses_condition,
+    verified_address::list_owned_verified_addresses, DataSubject, TokenInfo, VerifiedAddress,
+    ANONYMIZED_EMAIL,
+};
+use crate::{client::Client, errors::Error};
+use entity::verified_addresses;
+use sea_orm::{
+    sea_query::{Condition, Expr},
+    ColumnTrait, EntityTrait, QueryFilter,
+};
+use tracing::instrument;
+
+fn subject_addresses_condition(subject: &DataSubject) -> Condition {
+    match subject {
+        DataSubject::User(user) => owned_addresses_condition(user),
+        DataSubject::Id(id) => {
+            Condition::all().add(verified_addresses::Column::OwnerId.eq(id.as_str()))
+        }
+        DataSubject::Email(email) => {
+            Condition::all().add(verified_addresses::Column::OwnerEmail.eq(email.as_str()))
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UserData {
+    pub verified_addresses: Vec<VerifiedAddress>,
+    pub token_infos: Vec<TokenInfo>,
+}
+
+/// Returns all data stored for the user at the given chain.
+#[instrument(
+    skip_all,
+    err,
+    level = "debug",
+    fields(
+        subject = ?subject,
+        chain_id = ?chain_id,
+    ))
+]
+pub async fn export_user_data(
+    client: &Client,
+    subject: &DataSubject,
+    chain_id: i64,
+) -> Result<UserData, Error> {
+    let condition = subject_addresses_condition(subject);
+    let verified_addresses =
+        list_owned_verified_addresses(client, condition.clone(), chain_id).await?;
+    let token_infos = list_owned_token_infos(client, condition, chain_id).await?;
+
+    Ok(UserData {
+        verified_addresses,
+        token_infos,
+    })
+}
+
+/// Anonymizes personal data of the user at the given chain.
+/// Verified addresses themselves are kept, so that the ownership
+/// history and the statistics built on top of it remain intact.
+///
+/// Returns the number of anonymized verified addresses.
+#[instrument(
+    skip_all,
+    err,
+    ret,
+    level = "debug",
+    fields(
+        subject = ?subject,
+        chain_id = ?chain_id,
+    ))
+]
+pub async fn erase_user_data(
+    client: &Client,
+    subject: &DataSubject,
+    chain_id: i64,
+) -> Result<u64, Error> {
+    let erased = verified_addresses::Entity::update_many()
+        .col_expr(
+            verified_addresses::Column::OwnerEmail,
+            Expr::value(ANONYMIZED_EMAIL),
+        )
+        .filter(subject_addresses_condition(subject))
+        .filter(verified_addresses::Column::ChainId.eq(chain_id))
+        .exec(client.db.as_ref())
+        .await?
+        .rows_affected;
+
+    tracing::info!(chain_id, addresses = erased, "user data has been erased");
+    Ok(erased)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handlers::{list_user_verified_addresses, reconcile_user, User};
+    use entity::token_infos;
+    use migration::{Migrator, MigratorTrait};
+    use pretty_assertions::assert_eq;
+    use sea_orm::ActiveValue::Set;
+    use url::Url;
+
+    async fn init_client() -> Client {
+        let db = sea_orm::Database::connect("sqlite::memory:")
+            .await
+            .expect("Database connection error");
+        Migrator::up(&db, None).await.expect("Migrations failed");
+
+        let verified_addresses = [
+            ("0x01", 1, Some("1"), "user@gmail.com"),
+            ("0x02", 1, None, "user@gmail.com"),
+            ("0x03", 2, Some("1"), "user@gmail.com"),
+            ("0x04", 1, Some("2"), "another@gmail.com"),
+        ]
+        .map(|(address, chain_id, owner_id, owner_email)| {
+            verified_addresses::ActiveModel {
+                address: Set(address.into()),
+                chain_id: Set(chain_id),
+                owner_id: Set(owner_id.map(str::to_string)),
+                owner_email: Set(owner_email.into()),
+                ..Default::default()
+            }
+        });
+        verified_addresses::Entity::insert_many(verified_addresses)
+            .exec(&db)
+            .await
+            .expect("Predefined verified addresses insertion failed");
+
+        let token_infos = ["0x01", "0x04"].map(|address| token_infos::ActiveModel {
+            address: Set(address.into()),
+            chain_id: Set(1),
+            project_website: Set("project_website".into()),
+            project_email: Set("project_email".into()),
+            icon_url: Set("icon_url".into()),
+            project_description: Set("project_description".into()),
+            ..Default::default()
+        });
+        token_infos::Entity::insert_many(token_infos)
+            .exec(&db)
+            .await
+            .expect("Predefined token infos insertion failed");
+
+        let blockscout_url = Url::parse("http://127.0.0.1:80").unwrap();
+        Client::new(db, blockscout_url, None, 100)
+    }
+
+    fn addresses(user_data: &UserData) -> Vec<String> {
+        let mut addresses: Vec<_> = user_data
+            .verified_addresses
+            .iter()
+            .map(|address| address.contract_address.to_string())
+            .collect();
+        addresses.sort();
+        addresses
+    }
+
+    #[tokio::test]
+    async fn export_user_data_by_subject() {
+        let client = init_client().await;
+        let user = User::new("1", "user@gmail.com");
+
+        let user_data = export_user_data(&client, &user.clone().into(), 1)
+            .await
+            .expect("Export failed");
+        assert_eq!(vec!["0x01", "0x02"], addresses(&user_data));
+        assert_eq!(1, user_data.token_infos.len(), "Invalid token infos");
+        assert_eq!("0x01", user_data.token_infos[0].token_address.to_string());
+
+        let user_data = export_user_data(&client, &DataSubject::Id("1".into()), 1)
+            .await
+            .expect("Export by id failed");
+        assert_eq!(vec!["0x01"], addresses(&user_data));
+
+        let user_data = export_user_data(&client, &DataSubject::Email("user@gmail.com".into()), 1)
+            .await
+            .expect("Export by email failed");
+        assert_eq!(vec!["0x01", "0x02"], addresses(&user_data));
+    }
+
+    #[tokio::test]
+    async fn erase_user_data_anonymizes_emails() {
+        let client = init_client().await;
+        let user = User::new("1", "user@gmail.com");
+        reconcile_user(&client, &user)
+            .await
+            .expect("Reconciliation failed");
+
+        let erased = erase_user_data(&client, &user.clone().into(), 1)
+            .await
+            .expect("Erasure failed");
+        assert_eq!(2, erased, "Invalid number of erased addresses");
+
+        // Addresses are kept and still belong to the user account
+        let verified_addresses = list_user_verified_addresses(&client, user.clone(), 1)
+            .await
+            .expect("Listing verified addresses failed");
+        assert_eq!(2, verified_addresses.len());
+        assert!(verified_addresses
+            .iter()
+            .all(|address| address.user_email == ANONYMIZED_EMAIL));
+
+        // Erased emails are not restored by the reconciliation
+        reconcile_user(&client, &user)
+            .await
+            .expect("Second reconciliation failed");
+        let user_data = export_user_data(&client, &user.into(), 1)
+            .await
+            .expect("Export failed");
+        assert!(user_data
+            .verified_addresses
+            .iter()
+            .all(|address| address.user_email == ANONYMIZED_EMAIL));
+
+        // Data of other chains and users is untouched
+        let another_chain_data =
+            export_user_data(&client, &DataSubject::Email("user@gmail.com".into()), 2)
+                .await
+                .expect("Export failed");
+        assert_eq!(vec!["0x03"], addresses(&another_chain_data));
+        let another_user_data =
+            export_user_data(&client, &DataSubject::Email("another@gmail.com".into()), 1)
+                .await
+                .expect("Export failed");
+        assert_eq!(vec!["0x04"], addresses(&another_user_data));
+    }
+}
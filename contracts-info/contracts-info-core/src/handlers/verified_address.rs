@@ -1,16 +1,21 @@
+use super::{user::owned_addresses_condition, User};
 use crate::{
     blockscout,
     client::Client,
     errors::Error,
-    verify::{self, ownership_options, Message},
+    verify::{
+        self, ownership_options, ChainLink, CreatorResolution, Message, ResolvedOwner, SafeOwners,
+    },
 };
 use blockscout_display_bytes::Bytes as DisplayBytes;
 use chrono::{Duration, NaiveDateTime, SubsecRound, Utc};
-use entity::verified_addresses;
+use entity::{safe_confirmations, verification_nonces, verified_addresses};
 use ethers::types::Address;
 use sea_orm::{
-    sea_query::OnConflict, ActiveValue::Set, ColumnTrait, DatabaseConnection, DbErr, EntityTrait,
-    PaginatorTrait, QueryFilter, TransactionTrait,
+    sea_query::{Condition, OnConflict},
+    ActiveValue::Set,
+    ColumnTrait, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait, QueryFilter,
+    TransactionTrait,
 };
 use std::str::FromStr;
 use tracing::instrument;
@@ -23,11 +28,15 @@ pub struct AddressMetadata {
 
 #[derive(Clone, Debug, PartialOrd, PartialEq, Eq, Hash)]
 pub struct VerifiedAddress {
+    pub user_id: Option<String>,
     pub user_email: String,
     pub chain_id: i64,
     pub contract_address: DisplayBytes,
     pub verified_date: NaiveDateTime,
     pub metadata: AddressMetadata,
+    /// Contracts through which the owner controls the verified contract.
+    /// Empty if the contract is controlled by the owner directly.
+    pub ownership_chain: Vec<ChainLink>,
 }
 
 impl TryFrom<verified_addresses::Model> for VerifiedAddress {
@@ -35,6 +44,7 @@ impl TryFrom<verified_addresses::Model> for VerifiedAddress {
 
     fn try_from(model: verified_addresses::Model) -> Result<Self, Self::Error> {
         Ok(Self {
+            user_id: model.owner_id,
             user_email: model.owner_email,
             chain_id: model.chain_id,
             contract_address: DisplayBytes::from_str(&model.address).map_err(|err| {
@@ -47,15 +57,45 @@ impl TryFrom<verified_addresses::Model> for VerifiedAddress {
                 token_name: model.token_name,
                 token_symbol: model.token_symbol,
             },
+            ownership_chain: model
+                .ownership_chain
+                .map(serde_json::from_value)
+                .transpose()
+                .map_err(|err| {
+                    Error::Unexpected(format!(
+                        "Database model ownership chain conversion error: {err}"
+                    ))
+                })?
+                .unwrap_or_default(),
         })
     }
 }
 
+/// Format of the message the user signs to verify the address ownership.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum MessageFormat {
+    /// `[site] [timestamp] text [address] [nonce]` message.
+    #[default]
+    Legacy,
+    /// Sign-In with Ethereum (EIP-4361) message, which has to be signed by `signer`.
+    Siwe { signer: Address },
+    /// EIP-712 typed data, which has to be signed with `eth_signTypedData_v4`.
+    TypedData,
+}
+
 #[derive(Clone, Debug, PartialOrd, PartialEq, Eq, Hash)]
 pub struct PreparedAddress {
     pub signing_message: Message,
     pub contract_creator: Address,
+    /// Rule the contract creator has been determined by.
+    pub contract_creator_resolution: CreatorResolution,
     pub contract_owner: Option<Address>,
+    /// Set if the contract owner is a Safe multisig wallet, whose owners can sign instead.
+    pub contract_owner_safe: Option<SafeOwners>,
+    /// Accounts found by the ownership resolvers configured for the chain.
+    pub resolved_owners: Vec<ResolvedOwner>,
+    /// Time after which the signing message is not accepted anymore.
+    pub expires_at: NaiveDateTime,
 }
 
 #[instrument(
@@ -66,12 +106,15 @@ pub struct PreparedAddress {
     fields(
         contract.address = ?Address::from(contract),
         chain_id = chain_id,
+        user_id = ?user.id,
     ))
 ]
 pub async fn prepare_address(
     client: &Client,
+    user: User,
     chain_id: i64,
     contract: [u8; 20],
+    format: MessageFormat,
 ) -> Result<PreparedAddress, Error> {
     let contract: Address = contract.into();
     let maybe_verified = verified_addresses::Entity::find()
@@ -80,17 +123,45 @@ pub async fn prepare_address(
         .one(client.db.as_ref())
         .await?;
     if let Some(verified) = maybe_verified {
-        return Err(Error::AddressIsVerified(verified.owner_email));
+        return Err(Error::AddressIsVerified {
+            owner_id: verified.owner_id,
+            owner_email: verified.owner_email,
+        });
     };
 
-    let options = ownership_options(&client.blockscout, contract).await?;
-    let site = client.blockscout.host();
-    let signing_message = Message::new(site.to_string(), contract);
+    let options = ownership_options(
+        &client.blockscout,
+        client.rpc.as_ref(),
+        &client.ownership,
+        contract,
+    )
+    .await?;
+    let validity = to_chrono_duration(client.message_validity)?;
+    let nonce = issue_nonce(&client.db, &user, chain_id, contract, validity).await?;
+    let site = client.blockscout.host().to_string();
+    let signing_message = match format {
+        MessageFormat::Legacy => Message::new(site, contract, nonce),
+        MessageFormat::Siwe { signer } => Message::new_siwe(
+            site,
+            client.blockscout.endpoint().to_string(),
+            chain_id,
+            contract,
+            signer,
+            nonce,
+            validity,
+        ),
+        MessageFormat::TypedData => Message::new_typed_data(site, chain_id, contract, nonce),
+    };
 
+    let expires_at = signing_message.timestamp + validity;
     Ok(PreparedAddress {
         signing_message,
         contract_creator: options.creator,
+        contract_creator_resolution: options.creator_resolution,
         contract_owner: options.owner,
+        contract_owner_safe: options.safe,
+        resolved_owners: options.resolved,
+        expires_at,
     })
 }
 
@@ -102,44 +173,82 @@ pub async fn prepare_address(
     fields(
         contract.address = ?Address::from(contract_address),
         chain_id = ?chain_id,
-        user_email = ?user_email
+        user_id = ?user.id,
+        user_email = ?user.email
     ))
 ]
 pub async fn verify_address(
     client: &Client,
-    user_email: String,
+    user: User,
     chain_id: i64,
     contract_address: [u8; 20],
     message: String,
-    signature: [u8; 65],
+    signature: Vec<u8>,
+    signer: Option<[u8; 20]>,
 ) -> Result<VerifiedAddress, Error> {
-    let verified_addresses = count_verified_addresses(&client.db, chain_id, &user_email).await?;
+    let verified_addresses = count_verified_addresses(&client.db, chain_id, &user).await?;
     if verified_addresses >= client.max_verified_addresses {
         return Err(Error::MaxVerifiedAddressesLimit(
             client.max_verified_addresses,
         ));
     }
-    let signature = signature
-        .as_ref()
-        .try_into()
-        .map_err(|err| Error::Unexpected(format!("signature conversion failed: {err}")))?; // error is unexpected as the signature size is always 65 bytes
     let contract_address = contract_address.into();
+    let now = Utc::now();
     let min_timestamp = {
-        let min_date_time = Utc::now() - Duration::hours(24);
+        let min_date_time = now - to_chrono_duration(client.message_validity)?;
         NaiveDateTime::from_timestamp_opt(min_date_time.timestamp(), 0)
             .ok_or(Error::Unexpected("naive datetime conversion failed".into()))?
     };
+    let max_timestamp = (now + to_chrono_duration(client.max_clock_skew)?).naive_utc();
     let site = client.blockscout.host();
 
     let validated = verify::ValidatedOwnership::validate(
         &client.blockscout,
+        client.rpc.as_ref(),
+        &client.ownership,
         &signature,
+        signer.map(Address::from),
         &message,
         contract_address,
         site,
+        chain_id,
         min_timestamp,
+        max_timestamp,
     )
     .await?;
+    let nonce_condition = match validated.message.0.nonce() {
+        Some(nonce) => Some(nonce_condition(&user, chain_id, validated.contract, nonce)),
+        None if client.accept_messages_without_nonce => {
+            tracing::warn!("accepted deprecated verification message without nonce");
+            None
+        }
+        None => return Err(Error::InvalidNonce),
+    };
+    if let Some(nonce_condition) = nonce_condition.clone() {
+        let nonce_exists = verification_nonces::Entity::find()
+            .filter(nonce_condition)
+            .count(client.db.as_ref())
+            .await?
+            > 0;
+        if !nonce_exists {
+            return Err(Error::InvalidNonce);
+        }
+    }
+    if let Some(safe) = validated
+        .safe
+        .as_ref()
+        .filter(|_| client.require_safe_threshold)
+    {
+        let confirmed_signers =
+            confirm_safe_ownership(&client.db, chain_id, &user, &validated, safe, min_timestamp)
+                .await?;
+        if (confirmed_signers.len() as u64) < safe.threshold {
+            return Err(Error::SafeConfirmationsPending {
+                safe: safe.clone(),
+                confirmed_signers,
+            });
+        }
+    }
     let contract_address = validated.contract;
     let (token_name, token_symbol) =
         match blockscout::api::token(&client.blockscout, &contract_address)
@@ -165,12 +274,28 @@ pub async fn verify_address(
         let _guard = span.enter();
         let txn = client.db.begin().await?;
 
+        // The nonce is consumed in the same transaction, so that concurrent requests
+        // with the same message cannot both succeed
+        if let Some(nonce_condition) = nonce_condition {
+            let consumed = verification_nonces::Entity::delete_many()
+                .filter(nonce_condition)
+                .exec(&txn)
+                .await?;
+            if consumed.rows_affected == 0 {
+                return Err(Error::InvalidNonce);
+            }
+        }
+
+        let ownership_chain = (!validated.ownership_chain.is_empty())
+            .then(|| serde_json::json!(validated.ownership_chain));
         let active_model = verified_addresses::ActiveModel {
             chain_id: Set(chain_id),
             address: Set(contract_address.to_string()),
-            owner_email: Set(user_email.clone()),
+            owner_id: Set(Some(user.id.clone())),
+            owner_email: Set(user.email.clone()),
             token_name: Set(token_name),
             token_symbol: Set(token_symbol),
+            ownership_chain: Set(ownership_chain),
             ..Default::default()
         };
         match verified_addresses::Entity::insert(active_model)
@@ -189,25 +314,135 @@ pub async fn verify_address(
             .ok_or(Error::Unexpected(
                 "Verified address was not found after being inserted".into(),
             ))?;
+        safe_confirmations::Entity::delete_many()
+            .filter(safe_confirmations::Column::ChainId.eq(chain_id))
+            .filter(safe_confirmations::Column::Address.eq(contract_address.to_string()))
+            .exec(&txn)
+            .await?;
         txn.commit().await?;
 
         model
     };
 
-    if model.owner_email != user_email {
-        return Err(Error::AddressIsVerified(model.owner_email));
+    if !user.is_owner(model.owner_id.as_deref(), &model.owner_email) {
+        return Err(Error::AddressIsVerified {
+            owner_id: model.owner_id,
+            owner_email: model.owner_email,
+        });
     }
 
     model.try_into()
 }
 
+/// Issues a single-use nonce for the verification message of the contract,
+/// which can only be redeemed by the user within the message validity period.
+async fn issue_nonce(
+    db: &DatabaseConnection,
+    user: &User,
+    chain_id: i64,
+    contract: Address,
+    validity: Duration,
+) -> Result<String, Error> {
+    let now = Utc::now().naive_utc();
+    verification_nonces::Entity::delete_many()
+        .filter(verification_nonces::Column::ExpiresAt.lte(now))
+        .exec(db)
+        .await?;
+
+    let nonce = Message::generate_nonce();
+    let active_model = verification_nonces::ActiveModel {
+        created_at: Set(now),
+        expires_at: Set(now + validity),
+        nonce: Set(nonce.clone()),
+        owner_id: Set(user.id.clone()),
+        chain_id: Set(chain_id),
+        address: Set(format!("{contract:#x}")),
+        ..Default::default()
+    };
+    verification_nonces::Entity::insert(active_model)
+        .exec(db)
+        .await?;
+    Ok(nonce)
+}
+
+fn to_chrono_duration(duration: std::time::Duration) -> Result<Duration, Error> {
+    Duration::from_std(duration)
+        .map_err(|err| Error::Unexpected(format!("duration conversion failed: {err}")))
+}
+
+/// Matches the nonce if it has been issued to the user for the contract and has not expired.
+fn nonce_condition(user: &User, chain_id: i64, contract: Address, nonce: &str) -> Condition {
+    Condition::all()
+        .add(verification_nonces::Column::Nonce.eq(nonce))
+        .add(verification_nonces::Column::OwnerId.eq(user.id.as_str()))
+        .add(verification_nonces::Column::ChainId.eq(chain_id))
+        .add(verification_nonces::Column::Address.eq(format!("{contract:#x}")))
+        .add(verification_nonces::Column::ExpiresAt.gt(Utc::now().naive_utc()))
+}
+
+/// Stores the signature of the Safe owner and returns all owners who have signed
+/// the verification message of the contract for the user since `min_timestamp`.
+async fn confirm_safe_ownership(
+    db: &DatabaseConnection,
+    chain_id: i64,
+    user: &User,
+    validated: &verify::ValidatedOwnership,
+    safe: &SafeOwners,
+    min_timestamp: NaiveDateTime,
+) -> Result<Vec<Address>, Error> {
+    let contract_address = format!("{:#x}", validated.contract);
+    let safe_address = format!("{:#x}", safe.safe);
+    let active_model = safe_confirmations::ActiveModel {
+        created_at: Set(Utc::now().naive_utc()),
+        chain_id: Set(chain_id),
+        address: Set(contract_address.clone()),
+        safe_address: Set(safe_address.clone()),
+        signer: Set(format!("{:#x}", validated.owner)),
+        owner_id: Set(user.id.clone()),
+        ..Default::default()
+    };
+    // Repeated signatures of the same owner just prolong its confirmation
+    safe_confirmations::Entity::insert(active_model)
+        .on_conflict(
+            OnConflict::columns([
+                safe_confirmations::Column::ChainId,
+                safe_confirmations::Column::Address,
+                safe_confirmations::Column::OwnerId,
+                safe_confirmations::Column::Signer,
+            ])
+            .update_columns([
+                safe_confirmations::Column::CreatedAt,
+                safe_confirmations::Column::SafeAddress,
+            ])
+            .to_owned(),
+        )
+        .exec(db)
+        .await?;
+
+    let confirmations = safe_confirmations::Entity::find()
+        .filter(safe_confirmations::Column::ChainId.eq(chain_id))
+        .filter(safe_confirmations::Column::Address.eq(contract_address))
+        .filter(safe_confirmations::Column::OwnerId.eq(user.id.as_str()))
+        .filter(safe_confirmations::Column::SafeAddress.eq(safe_address))
+        .filter(safe_confirmations::Column::CreatedAt.gte(min_timestamp))
+        .all(db)
+        .await?;
+    // Signers could have been removed from the Safe since their confirmation
+    let confirmed_signers = confirmations
+        .into_iter()
+        .filter_map(|confirmation| Address::from_str(&confirmation.signer).ok())
+        .filter(|signer| safe.owners.contains(signer))
+        .collect();
+    Ok(confirmed_signers)
+}
+
 async fn count_verified_addresses(
     db: &DatabaseConnection,
     chain_id: i64,
-    user_email: &str,
+    user: &User,
 ) -> Result<u64, sea_orm::DbErr> {
     verified_addresses::Entity::find()
-        .filter(verified_addresses::Column::OwnerEmail.eq(user_email))
+        .filter(owned_addresses_condition(user))
         .filter(verified_addresses::Column::ChainId.eq(chain_id))
         .count(db)
         .await
@@ -219,17 +454,25 @@ async fn count_verified_addresses(
     ret,
     level = "debug",
     fields(
-        user_email = user_email,
+        user_id = ?user.id,
         chain_id = ?chain_id,
     ))
 ]
 pub async fn list_user_verified_addresses(
     client: &Client,
-    user_email: String,
+    user: User,
+    chain_id: i64,
+) -> Result<Vec<VerifiedAddress>, Error> {
+    list_owned_verified_addresses(client, owned_addresses_condition(&user), chain_id).await
+}
+
+pub(crate) async fn list_owned_verified_addresses(
+    client: &Client,
+    owner_condition: Condition,
     chain_id: i64,
 ) -> Result<Vec<VerifiedAddress>, Error> {
     let verified_addresses = verified_addresses::Entity::find()
-        .filter(verified_addresses::Column::OwnerEmail.eq(user_email))
+        .filter(owner_condition)
         .filter(verified_addresses::Column::ChainId.eq(chain_id))
         .all(client.db.as_ref())
         .await?;
@@ -439,6 +682,10 @@ mod tests {
         }
     }
 
+    fn mock_user(email: &str) -> User {
+        User::new(format!("{email}_id"), email)
+    }
+
     fn init_verified_address_model(
         address: &str,
         chain_id: i64,
@@ -461,12 +708,14 @@ mod tests {
         token_meta: AddressMetadata,
     ) -> VerifiedAddress {
         VerifiedAddress {
+            user_id: None,
             user_email: user_email.to_string(),
             chain_id,
             contract_address: DisplayBytes::from_str(contract_address)
                 .expect("Invalid contract address for expected verified address"),
             verified_date: Default::default(),
             metadata: token_meta,
+            ownership_chain: vec![],
         }
     }
 
@@ -475,10 +724,25 @@ mod tests {
         LocalWallet::new(&mut rand::thread_rng())
     }
 
-    fn generate_message(timestamp: DateTime<Utc>, contract_address: DisplayBytes) -> String {
+    async fn generate_message(
+        client: &Client,
+        user: &User,
+        chain_id: i64,
+        timestamp: DateTime<Utc>,
+        contract_address: DisplayBytes,
+    ) -> String {
+        let nonce = issue_nonce(
+            &client.db,
+            user,
+            chain_id,
+            Address::from_slice(&contract_address),
+            Duration::hours(24),
+        )
+        .await
+        .expect("Nonce issuance failed");
         let timestamp_display = timestamp.format(TS_FORMAT).to_string();
         let contract_address_display = contract_address.to_string();
-        format!("[127.0.0.1] [{timestamp_display}] I, hereby verify that I am the owner/creator of the address [{contract_address_display}]")
+        format!("[127.0.0.1] [{timestamp_display}] I, hereby verify that I am the owner/creator of the address [{contract_address_display}] [{nonce}]")
     }
 
     /********** prepare_address ***********/
@@ -496,9 +760,15 @@ mod tests {
         .with_owner(creator_address.to_string())])
         .await;
         let client = init_client(blockscout_url, []).await;
-        let result = prepare_address(&client, chain_id, contract_address)
-            .await
-            .expect("Prepare address returned an error");
+        let result = prepare_address(
+            &client,
+            mock_user("user1@gmail.com"),
+            chain_id,
+            contract_address,
+            MessageFormat::Legacy,
+        )
+        .await
+        .expect("Prepare address returned an error");
 
         assert_eq!(
             result.contract_creator,
@@ -508,11 +778,26 @@ mod tests {
             result.contract_owner,
             Some(Address::from_slice(&creator_address))
         );
+        assert_eq!(
+            result.contract_creator_resolution,
+            CreatorResolution::Sender
+        );
+        assert_eq!(
+            result.resolved_owners,
+            vec![ResolvedOwner {
+                address: Address::from_slice(&creator_address),
+                ownership_type: verify::OwnershipType::Owner,
+            }]
+        );
         assert_eq!(
             result.signing_message.address,
             Address::from_slice(contract_address.as_slice())
         );
         assert_eq!(result.signing_message.site, "127.0.0.1");
+        assert_eq!(
+            result.signing_message.timestamp + Duration::hours(24),
+            result.expires_at
+        );
     }
 
     #[rstest]
@@ -534,9 +819,15 @@ mod tests {
         .with_proxy_owner(owner_address.to_string())])
         .await;
         let client = init_client(blockscout_url, []).await;
-        let result = prepare_address(&client, chain_id, contract_address)
-            .await
-            .expect("Prepare address returned an error");
+        let result = prepare_address(
+            &client,
+            mock_user("user1@gmail.com"),
+            chain_id,
+            contract_address,
+            MessageFormat::Legacy,
+        )
+        .await
+        .expect("Prepare address returned an error");
 
         assert_eq!(
             result.contract_creator,
@@ -580,37 +871,55 @@ mod tests {
         .await;
         let client = init_client(blockscout_url, []).await;
         let timestamp = Utc::now();
-        let message = generate_message(timestamp, contract_address_display_1.clone());
-        let signature: [u8; 65] = wallet
+        let message = generate_message(
+            &client,
+            &mock_user(user_email_1),
+            chain_id,
+            timestamp,
+            contract_address_display_1.clone(),
+        )
+        .await;
+        let signature = wallet
             .sign_message(&message)
             .await
             .expect("Error signing message")
-            .to_vec()
-            .try_into()
-            .expect("Conversion of signature failed");
+            .to_vec();
         let _ = verify_address(
             &client,
-            user_email_1.into(),
+            mock_user(user_email_1),
             chain_id,
             contract_address_1,
             message.clone(),
-            signature,
+            signature.clone(),
+            None,
         )
         .await
         .expect("Initial verify address returned an error");
 
         // already verified
-        let result = prepare_address(&client, chain_id, contract_address_1)
-            .await
-            .expect_err("Prepare address should return error for alreadt verified address");
+        let result = prepare_address(
+            &client,
+            mock_user(user_email_1),
+            chain_id,
+            contract_address_1,
+            MessageFormat::Legacy,
+        )
+        .await
+        .expect_err("Prepare address should return error for alreadt verified address");
         assert!(
-            matches!(result, Error::AddressIsVerified(user_email) if user_email == user_email_1),
+            matches!(result, Error::AddressIsVerified { owner_email, .. } if owner_email == user_email_1),
             "Invalid error returned"
         );
         // contract source code not verified
-        let result = prepare_address(&client, chain_id, contract_address_2)
-            .await
-            .expect_err("Prepare address should return error for not verified contract");
+        let result = prepare_address(
+            &client,
+            mock_user(user_email_1),
+            chain_id,
+            contract_address_2,
+            MessageFormat::Legacy,
+        )
+        .await
+        .expect_err("Prepare address should return error for not verified contract");
         assert!(
             matches!(
                 result,
@@ -622,9 +931,15 @@ mod tests {
         );
 
         // invalid contract address
-        let result = prepare_address(&client, chain_id, random_address)
-            .await
-            .expect_err("Prepare address should return error for random contract address");
+        let result = prepare_address(
+            &client,
+            mock_user(user_email_1),
+            chain_id,
+            random_address,
+            MessageFormat::Legacy,
+        )
+        .await
+        .expect_err("Prepare address should return error for random contract address");
         assert!(
             matches!(
                 result,
@@ -659,27 +974,34 @@ mod tests {
         let client = init_client(blockscout_url, []).await;
 
         let timestamp = Utc::now();
-        let message = generate_message(timestamp, contract_address_display.clone());
-        let signature: [u8; 65] = wallet
+        let message = generate_message(
+            &client,
+            &mock_user(user_email),
+            chain_id,
+            timestamp,
+            contract_address_display.clone(),
+        )
+        .await;
+        let signature = wallet
             .sign_message(&message)
             .await
             .expect("Error signing message")
-            .to_vec()
-            .try_into()
-            .expect("Conversion of signature failed");
+            .to_vec();
 
         let result = verify_address(
             &client,
-            user_email.into(),
+            mock_user(user_email),
             chain_id,
             contract_address,
             message,
             signature,
+            None,
         )
         .await
         .expect("Verify address returned an error");
 
         let expected = VerifiedAddress {
+            user_id: Some(mock_user(user_email).id),
             user_email: user_email.to_string(),
             chain_id,
             contract_address: contract_address_display.clone(),
@@ -688,6 +1010,7 @@ mod tests {
                 token_name: Some("TOKEN_NAME".into()),
                 token_symbol: Some("T".into()),
             },
+            ownership_chain: vec![],
         };
         assert_eq!(expected, result, "Invalid verified address returned");
 
@@ -730,39 +1053,59 @@ mod tests {
 
         // Setup
         let timestamp = Utc::now();
-        let message = generate_message(timestamp, contract_address_display.clone());
-        let signature: [u8; 65] = wallet
+        let message = generate_message(
+            &client,
+            &mock_user(user_email_1),
+            chain_id,
+            timestamp,
+            contract_address_display.clone(),
+        )
+        .await;
+        let signature = wallet
             .sign_message(&message)
             .await
             .expect("Error signing message")
-            .to_vec()
-            .try_into()
-            .expect("Conversion of signature failed");
+            .to_vec();
         let _ = verify_address(
             &client,
-            user_email_1.into(),
+            mock_user(user_email_1),
             chain_id,
             contract_address,
             message.clone(),
-            signature,
+            signature.clone(),
+            None,
         )
         .await
         .expect("Initial verify address returned an error");
 
         // The actual check
         let another_user_email = "another user";
+        let message = generate_message(
+            &client,
+            &mock_user(another_user_email),
+            chain_id,
+            timestamp,
+            contract_address_display.clone(),
+        )
+        .await;
+        let signature = wallet
+            .sign_message(&message)
+            .await
+            .expect("Error signing message")
+            .to_vec();
         let result = verify_address(
             &client,
-            another_user_email.into(),
+            mock_user(another_user_email),
             chain_id,
             contract_address,
             message,
             signature,
+            None,
         )
         .await
         .expect_err("Error expected as a result of the second verification");
         assert!(
-            matches!(result, Error::AddressIsVerified(user_email) if user_email == user_email_1),
+            matches!(result, Error::AddressIsVerified { owner_email, .. } if owner_email == user_email_1),
             "Invalid error returned"
         );
 
@@ -795,52 +1138,66 @@ mod tests {
 
         // Setup
         let timestamp = Utc::now();
-        let message = generate_message(timestamp, contract_address_display.clone());
-        let signature: [u8; 65] = wallet
+        let message = generate_message(
+            &client,
+            &mock_user(user_email),
+            chain_id,
+            timestamp,
+            contract_address_display.clone(),
+        )
+        .await;
+        let signature = wallet
             .sign_message(&message)
             .await
             .expect("Error signing message")
-            .to_vec()
-            .try_into()
-            .expect("Conversion of signature failed");
+            .to_vec();
         let initial_result = verify_address(
             &client,
-            user_email.into(),
+            mock_user(user_email),
             chain_id,
             contract_address,
             message.clone(),
-            signature,
+            signature.clone(),
+            None,
         )
         .await
         .expect("Initial verify address returned an error");
 
         // The actual check
         let timestamp_2 = timestamp - Duration::seconds(10);
-        let message = generate_message(timestamp_2, contract_address_display.clone());
-        let signature: [u8; 65] = wallet
+        let message = generate_message(
+            &client,
+            &mock_user(user_email),
+            chain_id,
+            timestamp_2,
+            contract_address_display.clone(),
+        )
+        .await;
+        let signature = wallet
             .sign_message(&message)
             .await
             .expect("Error signing message")
-            .to_vec()
-            .try_into()
-            .expect("Conversion of signature failed");
+            .to_vec();
         let result = verify_address(
             &client,
-            user_email.into(),
+            mock_user(user_email),
             chain_id,
             contract_address,
             message,
             signature,
+            None,
         )
         .await
         .expect("Verify address returned an error");
 
         let expected = VerifiedAddress {
+            user_id: Some(mock_user(user_email).id),
             user_email: user_email.to_string(),
             chain_id,
             contract_address: contract_address_display.clone(),
             verified_date: initial_result.verified_date,
             metadata: Default::default(),
+            ownership_chain: vec![],
         };
         assert_eq!(expected, result, "Invalid verified address returned");
 
@@ -855,6 +1212,502 @@ mod tests {
         assert_eq!(1, count, "Only one item expected to be in the database");
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn verify_address_future_timestamp_error(wallet: Wallet<SigningKey>) {
+        let user_email = "user1@gmail.com";
+        let contract_address: [u8; 20] = [19; 20];
+        let chain_id = 1;
+        let creator_address = DisplayBytes::from(wallet.address().to_fixed_bytes());
+
+        let contract_address_display = DisplayBytes::from(contract_address.to_vec());
+        let (_blockscout_server, blockscout_url) = init_blockscout([MockedContract::new(
+            contract_address_display.to_string(),
+            creator_address.to_string(),
+        )
+        .with_meta(AddressMetadata::default())])
+        .await;
+        let client = init_client(blockscout_url, [])
+            .await
+            .with_max_clock_skew(std::time::Duration::from_secs(60));
+
+        for (offset, is_accepted) in [
+            (Duration::minutes(10), false),
+            (Duration::seconds(30), true),
+        ] {
+            let message = generate_message(
+                &client,
+                &mock_user(user_email),
+                chain_id,
+                Utc::now() + offset,
+                contract_address_display.clone(),
+            )
+            .await;
+            let signature = wallet
+                .sign_message(&message)
+                .await
+                .expect("Error signing message")
+                .to_vec();
+            let result = verify_address(
+                &client,
+                mock_user(user_email),
+                chain_id,
+                contract_address,
+                message,
+                signature,
+                None,
+            )
+            .await;
+            if is_accepted {
+                result.expect("Verify address returned an error");
+            } else {
+                let result = result.expect_err("Future message should be rejected");
+                assert!(
+                    matches!(
+                        result,
+                        Error::SignatureVerification {
+                            kind: crate::verify::Error::FutureTimestamp(_)
+                        }
+                    ),
+                    "Invalid error returned: {result:?}"
+                );
+            }
+        }
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn verify_address_nonce_errors(wallet: Wallet<SigningKey>) {
+        let user_email = "user1@gmail.com";
+        let contract_address: [u8; 20] = [19; 20];
+        let chain_id = 1;
+        let creator_address = DisplayBytes::from(wallet.address().to_fixed_bytes());
+
+        let contract_address_display = DisplayBytes::from(contract_address.to_vec());
+        let (_blockscout_server, blockscout_url) = init_blockscout([MockedContract::new(
+            contract_address_display.to_string(),
+            creator_address.to_string(),
+        )
+        .with_meta(AddressMetadata::default())])
+        .await;
+        let client = init_client(blockscout_url, []).await;
+
+        let prepared = prepare_address(
+            &client,
+            mock_user(user_email),
+            chain_id,
+            contract_address,
+            MessageFormat::Legacy,
+        )
+        .await
+        .expect("Prepare address returned an error");
+        let message = prepared.signing_message.to_string();
+        let signature = wallet
+            .sign_message(&message)
+            .await
+            .expect("Error signing message")
+            .to_vec();
+        let verify = |user: User, message: String| {
+            verify_address(
+                &client,
+                user,
+                chain_id,
+                contract_address,
+                message.clone(),
+                futures::executor::block_on(wallet.sign_message(&message))
+                    .expect("Error signing message")
+                    .to_vec(),
+                None,
+            )
+        };
+
+        // Messages without nonces are not accepted anymore
+        let message_without_nonce = message
+            .rsplit_once(' ')
+            .expect("Message has no nonce")
+            .0
+            .to_string();
+        let result = verify(mock_user(user_email), message_without_nonce)
+            .await
+            .expect_err("Message without nonce should be rejected");
+        assert!(
+            matches!(result, Error::InvalidNonce),
+            "Invalid error returned: {result:?}"
+        );
+
+        // The nonce is bound to the user it has been issued for
+        let result = verify(mock_user("another user"), message.clone())
+            .await
+            .expect_err("Foreign nonce should be rejected");
+        assert!(
+            matches!(result, Error::InvalidNonce),
+            "Invalid error returned: {result:?}"
+        );
+
+        verify_address(
+            &client,
+            mock_user(user_email),
+            chain_id,
+            contract_address,
+            message.clone(),
+            signature.clone(),
+            None,
+        )
+        .await
+        .expect("Verify address returned an error");
+
+        // The nonce is consumed by the successful verification
+        let result = verify_address(
+            &client,
+            mock_user(user_email),
+            chain_id,
+            contract_address,
+            message,
+            signature,
+            None,
+        )
+        .await
+        .expect_err("Replayed nonce should be rejected");
+        assert!(
+            matches!(result, Error::InvalidNonce),
+            "Invalid error returned: {result:?}"
+        );
+        let nonces = verification_nonces::Entity::find()
+            .count(client.db.as_ref())
+            .await
+            .expect("Error when trying to count nonces");
+        assert_eq!(0, nonces, "Nonce should be removed");
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn verify_address_without_nonce_accepted_if_enabled(wallet: Wallet<SigningKey>) {
+        let user_email = "user1@gmail.com";
+        let contract_address: [u8; 20] = [20; 20];
+        let chain_id = 1;
+        let creator_address = DisplayBytes::from(wallet.address().to_fixed_bytes());
+
+        let contract_address_display = DisplayBytes::from(contract_address.to_vec());
+        let (_blockscout_server, blockscout_url) = init_blockscout([MockedContract::new(
+            contract_address_display.to_string(),
+            creator_address.to_string(),
+        )
+        .with_meta(AddressMetadata::default())])
+        .await;
+        let client = init_client(blockscout_url, [])
+            .await
+            .with_accept_messages_without_nonce(true);
+
+        let prepared = prepare_address(
+            &client,
+            mock_user(user_email),
+            chain_id,
+            contract_address,
+            MessageFormat::Legacy,
+        )
+        .await
+        .expect("Prepare address returned an error");
+        let message = prepared
+            .signing_message
+            .to_string()
+            .rsplit_once(' ')
+            .expect("Message has no nonce")
+            .0
+            .to_string();
+        let signature = wallet
+            .sign_message(&message)
+            .await
+            .expect("Error signing message")
+            .to_vec();
+
+        let result = verify_address(
+            &client,
+            mock_user(user_email),
+            chain_id,
+            contract_address,
+            message,
+            signature,
+            None,
+        )
+        .await
+        .expect("Message without nonce should be accepted");
+        assert_eq!(contract_address_display, result.contract_address);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn verify_address_siwe_success(wallet: Wallet<SigningKey>) {
+        let user_email = "user1@gmail.com";
+        let contract_address: [u8; 20] = [19; 20];
+        let chain_id = 5;
+        let creator_address = DisplayBytes::from(wallet.address().to_fixed_bytes());
+
+        let contract_address_display = DisplayBytes::from(contract_address.to_vec());
+        let (_blockscout_server, blockscout_url) = init_blockscout([MockedContract::new(
+            contract_address_display.to_string(),
+            creator_address.to_string(),
+        )
+        .with_meta(AddressMetadata::default())])
+        .await;
+        let client = init_client(blockscout_url, []).await;
+
+        let format = MessageFormat::Siwe {
+            signer: wallet.address(),
+        };
+        let prepared = prepare_address(
+            &client,
+            mock_user(user_email),
+            chain_id,
+            contract_address,
+            format,
+        )
+        .await
+        .expect("Prepare address returned an error");
+        let message = prepared.signing_message.to_string();
+        assert!(
+            message.starts_with("127.0.0.1 wants you to sign in with your Ethereum account:"),
+            "Invalid message: {message}"
+        );
+
+        // Messages are bound to the chain they have been issued for
+        let signature = wallet
+            .sign_message(&message)
+            .await
+            .expect("Error signing message")
+            .to_vec();
+        let result = verify_address(
+            &client,
+            mock_user(user_email),
+            1,
+            contract_address,
+            message.clone(),
+            signature.clone(),
+            None,
+        )
+        .await
+        .expect_err("Message of another chain should be rejected");
+        assert!(
+            matches!(
+                result,
+                Error::SignatureVerification {
+                    kind: crate::verify::Error::InvalidValue(_)
+                }
+            ),
+            "Invalid error returned: {result:?}"
+        );
+
+        let result = verify_address(
+            &client,
+            mock_user(user_email),
+            chain_id,
+            contract_address,
+            message,
+            signature,
+            None,
+        )
+        .await
+        .expect("Verify address returned an error");
+        assert_eq!(contract_address_display, result.contract_address);
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn verify_address_typed_data_success(wallet: Wallet<SigningKey>) {
+        use ethers::types::transaction::eip712::TypedData;
+
+        let user_email = "user1@gmail.com";
+        let contract_address: [u8; 20] = [20; 20];
+        let chain_id = 5;
+        let creator_address = DisplayBytes::from(wallet.address().to_fixed_bytes());
+
+        let contract_address_display = DisplayBytes::from(contract_address.to_vec());
+        let (_blockscout_server, blockscout_url) = init_blockscout([MockedContract::new(
+            contract_address_display.to_string(),
+            creator_address.to_string(),
+        )
+        .with_meta(AddressMetadata::default())])
+        .await;
+        let client = init_client(blockscout_url, []).await;
+
+        let prepared = prepare_address(
+            &client,
+            mock_user(user_email),
+            chain_id,
+            contract_address,
+            MessageFormat::TypedData,
+        )
+        .await
+        .expect("Prepare address returned an error");
+        let message = prepared.signing_message.to_string();
+        let typed_data: TypedData =
+            serde_json::from_str(&message).expect("Message is not a valid typed data");
+        let signature = wallet
+            .sign_typed_data(&typed_data)
+            .await
+            .expect("Error signing typed data")
+            .to_vec();
+
+        // Typed data signatures are not valid for the same data signed as a text
+        let text_signature = wallet
+            .sign_message(&message)
+            .await
+            .expect("Error signing message")
+            .to_vec();
+        let result = verify_address(
+            &client,
+            mock_user(user_email),
+            chain_id,
+            contract_address,
+            message.clone(),
+            text_signature,
+            Some(wallet.address().to_fixed_bytes()),
+        )
+        .await
+        .expect_err("Text signature should be rejected");
+        assert!(
+            matches!(
+                result,
+                Error::SignatureVerification {
+                    kind: crate::verify::Error::Signature(_)
+                }
+            ),
+            "Invalid error returned: {result:?}"
+        );
+
+        let result = verify_address(
+            &client,
+            mock_user(user_email),
+            chain_id,
+            contract_address,
+            message,
+            signature,
+            None,
+        )
+        .await
+        .expect("Verify address returned an error");
+        assert_eq!(contract_address_display, result.contract_address);
+    }
+
+    fn init_safe_rpc(owners: &[Address], threshold: u64) -> (MockServer, Url) {
+        use ethers::abi::{encode, Token};
+
+        let rpc_server = MockServer::start();
+        let results = [
+            (r#""method":"eth_getCode""#, "0x6080".to_string()),
+            (
+                r#""data":"0xa0e67e2b""#,
+                format!(
+                    "0x{}",
+                    ethers::utils::hex::encode(encode(&[Token::Array(
+                        owners.iter().copied().map(Token::Address).collect()
+                    )]))
+                ),
+            ),
+            (
+                r#""data":"0xe75235b8""#,
+                format!(
+                    "0x{}",
+                    ethers::utils::hex::encode(encode(&[Token::Uint(threshold.into())]))
+                ),
+            ),
+        ];
+        for (request, result) in results {
+            let _ = rpc_server.mock(|when, then| {
+                when.method(Method::POST).path("/").body_contains(request);
+                then.status(200)
+                    .header("content-type", "application/json")
+                    .json_body(serde_json::json!({"jsonrpc": "2.0", "id": 1, "result": result}));
+            });
+        }
+        let url = Url::from_str(&rpc_server.base_url()).expect("Invalid rpc base url");
+        (rpc_server, url)
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn verify_address_safe_threshold() {
+        let user_email = "user1@gmail.com";
+        let owners = [
+            LocalWallet::new(&mut rand::thread_rng()),
+            LocalWallet::new(&mut rand::thread_rng()),
+            LocalWallet::new(&mut rand::thread_rng()),
+        ];
+        let safe = Address::from([0x5a; 20]);
+        let contract_address: [u8; 20] = [19; 20];
+        let chain_id = 1;
+
+        let contract_address_display = DisplayBytes::from(contract_address.to_vec());
+        let (_blockscout_server, blockscout_url) = init_blockscout([MockedContract::new(
+            contract_address_display.to_string(),
+            "0x1111111111111111111111111111111111111111",
+        )
+        .with_owner(format!("{safe:#x}"))
+        .with_meta(AddressMetadata::default())])
+        .await;
+        let owner_addresses = owners.iter().map(Signer::address).collect::<Vec<_>>();
+        let (_rpc_server, rpc_url) = init_safe_rpc(&owner_addresses, 2);
+        let client = init_client(blockscout_url, [])
+            .await
+            .with_rpc_url(Some(rpc_url), crate::rpc::DEFAULT_RPC_TIMEOUT)
+            .with_require_safe_threshold(true);
+
+        let prepared = prepare_address(
+            &client,
+            mock_user(user_email),
+            chain_id,
+            contract_address,
+            MessageFormat::Legacy,
+        )
+        .await
+        .expect("Prepare address returned an error");
+        assert_eq!(
+            Some(SafeOwners {
+                safe,
+                owners: owner_addresses.clone(),
+                threshold: 2,
+            }),
+            prepared.contract_owner_safe
+        );
+
+        let message = prepared.signing_message.to_string();
+        let verify = |wallet: &LocalWallet| {
+            let signature = futures::executor::block_on(wallet.sign_message(&message))
+                .expect("Error signing message")
+                .to_vec();
+            verify_address(
+                &client,
+                mock_user(user_email),
+                chain_id,
+                contract_address,
+                message.clone(),
+                signature,
+                None,
+            )
+        };
+
+        // Repeated signatures of the same owner are counted once
+        for _ in 0..2 {
+            let result = verify(&owners[0])
+                .await
+                .expect_err("Single signature should not be enough");
+            assert!(
+                matches!(&result, Error::SafeConfirmationsPending { confirmed_signers, .. } if confirmed_signers == &[owner_addresses[0]]),
+                "Invalid error returned: {result:?}"
+            );
+        }
+
+        let result = verify(&owners[2])
+            .await
+            .expect("Verify address returned an error");
+        assert_eq!(contract_address_display, result.contract_address);
+
+        let confirmations = safe_confirmations::Entity::find()
+            .count(client.db.as_ref())
+            .await
+            .expect("Error when trying to count safe confirmations");
+        assert_eq!(0, confirmations, "Confirmations should be removed");
+    }
+
     #[rstest]
     #[tokio::test]
     async fn max_verified_addresses_error() {
@@ -863,14 +1716,15 @@ mod tests {
         client.max_verified_addresses = 0;
         let contract_address: [u8; 20] = [19; 20];
         let chain_id = 1;
-        let user_email = "user1@gmail.com".into();
+        let user = mock_user("user1@gmail.com");
         let result = verify_address(
             &client,
-            user_email,
+            user,
             chain_id,
             contract_address,
             "msg".to_string(),
-            [0; 65],
+            vec![0; 65],
+            None,
         )
         .await
         .expect_err(
@@ -921,7 +1775,7 @@ mod tests {
         )
         .await;
 
-        let result = list_user_verified_addresses(&client, user_id_1.to_string(), chain_id)
+        let result = list_user_verified_addresses(&client, mock_user(user_id_1), chain_id)
             .await
             .expect("Error when listing user verified addresses");
 
@@ -967,7 +1821,7 @@ mod tests {
         )
         .await;
 
-        let result = list_user_verified_addresses(&client, user_id_2.to_string(), chain_id)
+        let result = list_user_verified_addresses(&client, mock_user(user_id_2), chain_id)
             .await
             .expect("Error when listing user verified addresses");
         assert!(result.is_empty(), "Result should be empty")
@@ -990,7 +1844,7 @@ mod tests {
         )
         .await;
 
-        let result = list_user_verified_addresses(&client, user_id.to_string(), chain_id_1)
+        let result = list_user_verified_addresses(&client, mock_user(user_id), chain_id_1)
             .await
             .expect("Error when listing user verified addresses");
         assert_eq!(
@@ -1008,6 +1862,50 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn list_user_verified_addresses_by_user_id() {
+        let user = mock_user("user1@gmail.com");
+        let chain_id = 1;
+        let contract_address_1 = "0xcafecafecafecafecafecafecafecafecafeca01";
+        let contract_address_2 = "0xcafecafecafecafecafecafecafecafecafeca02";
+
+        let client = init_client(
+            Url::from_str(DEFAULT_BLOCKSCOUT_URL).unwrap(),
+            [
+                // The user has changed the email since the verification
+                verified_addresses::ActiveModel {
+                    owner_id: Set(Some(user.id.clone())),
+                    ..init_verified_address_model(
+                        contract_address_1,
+                        chain_id,
+                        "old_email@gmail.com".into(),
+                    )
+                },
+                // The email now belongs to the user, but the address was verified by another one
+                verified_addresses::ActiveModel {
+                    owner_id: Set(Some("another_user_id".into())),
+                    ..init_verified_address_model(contract_address_2, chain_id, user.email.clone())
+                },
+            ],
+        )
+        .await;
+
+        let result = list_user_verified_addresses(&client, user.clone(), chain_id)
+            .await
+            .expect("Error when listing user verified addresses");
+        assert_eq!(
+            1,
+            result.len(),
+            "Invalid number of verified addresses returned"
+        );
+        assert_eq!(
+            DisplayBytes::from_str(contract_address_1).unwrap(),
+            result[0].contract_address,
+            "Invalid verified address returned"
+        );
+        assert_eq!(Some(user.id), result[0].user_id, "Invalid user id");
+    }
+
     /********** get_verified_address ***********/
 
     fn assert_eq_address(expected: VerifiedAddress, result: VerifiedAddress) {
@@ -1048,6 +1946,35 @@ mod tests {
         assert_eq_address(expected, result);
     }
 
+    #[tokio::test]
+    async fn get_verified_address_ownership_chain() {
+        let chain_id = 1;
+        let contract_address = "0xcafecafecafecafecafecafecafecafecafeca01";
+        let ownership_chain = vec![ChainLink {
+            address: Address::from([1; 20]),
+            relation: verify::ControlRelation::Owner,
+        }];
+
+        let client = init_client(
+            Url::from_str(DEFAULT_BLOCKSCOUT_URL).unwrap(),
+            [verified_addresses::ActiveModel {
+                ownership_chain: Set(Some(serde_json::json!(ownership_chain))),
+                ..init_verified_address_model(contract_address, chain_id, "user_1".into())
+            }],
+        )
+        .await;
+
+        let result = get_verified_address(
+            &client,
+            chain_id,
+            DisplayBytes::from_str(contract_address).unwrap(),
+        )
+        .await
+        .expect("Error when getting verified addresses")
+        .expect("No verified address was returned for existing address");
+        assert_eq!(ownership_chain, result.ownership_chain);
+    }
+
     #[tokio::test]
     async fn get_verified_address_empty() {
         let user_id = "user_1";
@@ -1,11 +1,16 @@
 mod blockscout;
 mod client;
 mod errors;
+mod rpc;
 
 pub mod handlers;
+pub mod localization;
 pub mod verify;
 
 pub use client::Client;
 pub use errors::Error;
-pub use handlers::{TokenInfo, TokenInfoProviderLevel, VerifiedAddress};
+pub use handlers::{
+    LocalizedDescription, TokenInfo, TokenInfoProviderLevel, User, VerifiedAddress,
+};
+pub use rpc::DEFAULT_RPC_TIMEOUT;
 pub use verify::{Error as VerificationError, ValidatedOwnership};
@@ -0,0 +1,126 @@
+//! Selection of translations by BCP-47 language tags.
+
+const LANGUAGE_TAG_MAX_LENGTH: usize = 35;
+
+/// Checks that the tag is a well-formed BCP-47 language tag: 2-3 or 5-8 letter
+/// language subtag followed by alphanumeric subtags of up to 8 characters,
+/// e.g. `en`, `pt-BR` or `zh-Hant-TW`. Subtags are not checked against the registry.
+pub fn is_language_tag(tag: &str) -> bool {
+    let mut subtags = tag.split('-');
+    let language = subtags.next().unwrap_or_default();
+    tag.len() <= LANGUAGE_TAG_MAX_LENGTH
+        && matches!(language.len(), 2..=3 | 5..=8)
+        && language.chars().all(|c| c.is_ascii_alphabetic())
+        && subtags.all(|subtag| {
+            (1..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+        })
+}
+
+/// Parses the value of `Accept-Language` header into the language ranges
+/// ordered by their quality values. Wildcards, rejected (`q=0`) and malformed
+/// ranges are skipped.
+pub fn parse_accept_language(value: &str) -> Vec<&str> {
+    let mut ranges: Vec<(&str, f32)> = value
+        .split(',')
+        .filter_map(|item| {
+            let mut params = item.split(';').map(str::trim);
+            let range = params.next().filter(|range| is_language_tag(range))?;
+            let quality = match params.find_map(|param| param.strip_prefix("q=")) {
+                Some(quality) => quality.parse().ok()?,
+                None => 1.0,
+            };
+            (quality > 0.0).then_some((range, quality))
+        })
+        .collect();
+    // Sort is stable, so ranges with the same quality keep the order they were listed in
+    ranges.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    ranges.into_iter().map(|(range, _)| range).collect()
+}
+
+/// Returns the index of the tag best matching the language ranges.
+///
+/// Each range is looked up as described in RFC 4647: the range is progressively
+/// truncated until it matches one of the tags (`de-CH-1996` -> `de-CH` -> `de`).
+/// If nothing is found, any tag of the same primary language is matched,
+/// so that `en-GB` is preferred to the default text if only `en-US` is available.
+pub fn lookup<'a>(
+    ranges: &[&str],
+    tags: impl IntoIterator<Item = &'a str> + Clone,
+) -> Option<usize> {
+    let position = |matches: &dyn Fn(&str) -> bool| tags.clone().into_iter().position(matches);
+    ranges.iter().find_map(|range| {
+        let mut prefix = *range;
+        loop {
+            if let Some(index) = position(&|tag| tag.eq_ignore_ascii_case(prefix)) {
+                return Some(index);
+            }
+            prefix = match prefix.rfind('-') {
+                // Single character subtags introduce extensions and are removed together with them
+                Some(i) if i >= 2 && prefix.as_bytes()[i - 2] == b'-' => &prefix[..i - 2],
+                Some(i) => &prefix[..i],
+                None => break,
+            };
+        }
+        position(&|tag| primary_language(tag).eq_ignore_ascii_case(prefix))
+    })
+}
+
+fn primary_language(tag: &str) -> &str {
+    tag.split('-').next().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn is_language_tag_works() {
+        for tag in [
+            "en",
+            "pt-BR",
+            "zh-Hant-TW",
+            "de-CH-1996",
+            "yue",
+            "en-a-bbb-x-a-ccc",
+        ] {
+            assert!(is_language_tag(tag), "{tag} should be valid");
+        }
+        for tag in [
+            "",
+            "e",
+            "en_US",
+            "en-",
+            "-en",
+            "12",
+            "en-toolongsubtag",
+            "*",
+        ] {
+            assert!(!is_language_tag(tag), "{tag} should be invalid");
+        }
+    }
+
+    #[test]
+    fn parse_accept_language_works() {
+        assert_eq!(
+            vec!["fr-CH", "fr", "de", "en"],
+            parse_accept_language("en;q=0.5, fr;q=0.9, fr-CH, *;q=0.1, de;q=0.9, it;q=0")
+        );
+        assert_eq!(vec!["de"], parse_accept_language("de, en;q=invalid, en_US"));
+        assert!(parse_accept_language("").is_empty());
+    }
+
+    #[test]
+    fn lookup_works() {
+        let tags = ["en-US", "de", "pt-BR", "zh-Hant"];
+        let lookup = |ranges: &[&str]| lookup(ranges, tags.iter().copied());
+
+        assert_eq!(Some(1), lookup(&["de"]));
+        assert_eq!(Some(1), lookup(&["DE-ch-1996"]));
+        assert_eq!(Some(3), lookup(&["zh-Hant-CN-x-private"]));
+        assert_eq!(Some(2), lookup(&["fr", "pt"]));
+        assert_eq!(Some(0), lookup(&["en-GB", "de"]));
+        assert_eq!(None, lookup(&["fr", "it"]));
+        assert_eq!(None, lookup(&[]));
+    }
+}
@@ -0,0 +1,158 @@
+use crate::verify::Error;
+use api::Response;
+use ethers::{
+    abi::{self, ParamType, Token},
+    types::{Address, Bytes},
+};
+use std::time::Duration;
+use url::Url;
+
+/// Default time limit of a single request to the node.
+pub const DEFAULT_RPC_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Client of the JSON-RPC node of the chain, used for calls
+/// which cannot be served by the blockscout API.
+#[derive(Debug, Clone)]
+pub struct Client {
+    endpoint: Url,
+    http: reqwest::Client,
+}
+
+impl Client {
+    /// Requests taking longer than `timeout` fail, so that a stalled node
+    /// does not block verifications. The connection pool is shared by the clones.
+    pub fn new(endpoint: Url, timeout: Duration) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .expect("failed to build rpc http client");
+        Self { endpoint, http }
+    }
+
+    pub fn endpoint(&self) -> &Url {
+        &self.endpoint
+    }
+}
+
+/// Calls the method with ABI-encoded `args`, returning `None` if the contract
+/// does not implement it (the call reverts or returns unexpected data).
+pub(crate) async fn call(
+    rpc: &Client,
+    address: Address,
+    selector: [u8; 4],
+    args: &[Token],
+    output: ParamType,
+) -> Result<Option<Token>, Error> {
+    let data = Bytes::from([selector.as_slice(), &abi::encode(args)].concat());
+    match api::call(rpc, &address, &data)
+        .await
+        .map_err(|e| Error::RpcRequest(e.to_string()))?
+    {
+        Response::Ok(result) => Ok(abi::decode(&[output], &result)
+            .ok()
+            .and_then(|tokens| tokens.into_iter().next())),
+        Response::Error(_) => Ok(None),
+    }
+}
+
+pub mod api {
+    use super::*;
+    use ethers::types::{Address, Bytes, H256};
+    use serde::{de::DeserializeOwned, Deserialize};
+    use tracing::instrument;
+
+    #[derive(Debug, Deserialize)]
+    pub struct JsonRpcError {
+        pub code: i64,
+        pub message: String,
+    }
+
+    #[derive(Debug)]
+    pub enum Response<T> {
+        Ok(T),
+        /// The node has processed the request and returned an error,
+        /// e.g. if the called contract has reverted.
+        Error(JsonRpcError),
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct JsonRpcResponse<T> {
+        result: Option<T>,
+        error: Option<JsonRpcError>,
+    }
+
+    impl<T> Response<T>
+    where
+        T: DeserializeOwned,
+    {
+        async fn try_from_reqwest_response(response: reqwest::Response) -> reqwest::Result<Self> {
+            let response: JsonRpcResponse<T> = response.error_for_status()?.json().await?;
+            let response = match (response.result, response.error) {
+                (_, Some(error)) => Response::Error(error),
+                (Some(result), None) => Response::Ok(result),
+                (None, None) => Response::Error(JsonRpcError {
+                    code: 0,
+                    message: "response contains neither result nor error".into(),
+                }),
+            };
+            Ok(response)
+        }
+    }
+
+    async fn request<T: DeserializeOwned>(
+        client: &Client,
+        method: &str,
+        params: serde_json::Value,
+    ) -> reqwest::Result<Response<T>> {
+        let response = client
+            .http
+            .post(client.endpoint().clone())
+            .json(&serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": method,
+                "params": params,
+            }))
+            .send()
+            .await?;
+        Response::try_from_reqwest_response(response).await
+    }
+
+    #[instrument(name = "rpc_api:get_code", skip_all, err, level = "debug")]
+    pub async fn get_code(client: &Client, address: &Address) -> reqwest::Result<Response<Bytes>> {
+        request(
+            client,
+            "eth_getCode",
+            serde_json::json!([format!("{address:#x}"), "latest"]),
+        )
+        .await
+    }
+
+    #[instrument(name = "rpc_api:call", skip_all, err, level = "debug")]
+    pub async fn call(
+        client: &Client,
+        to: &Address,
+        data: &Bytes,
+    ) -> reqwest::Result<Response<Bytes>> {
+        request(
+            client,
+            "eth_call",
+            serde_json::json!([{ "to": format!("{to:#x}"), "data": data }, "latest"]),
+        )
+        .await
+    }
+
+    #[instrument(name = "rpc_api:get_storage_at", skip_all, err, level = "debug")]
+    pub async fn get_storage_at(
+        client: &Client,
+        address: &Address,
+        slot: &H256,
+    ) -> reqwest::Result<Response<H256>> {
+        request(
+            client,
+            "eth_getStorageAt",
+            serde_json::json!([format!("{address:#x}"), slot, "latest"]),
+        )
+        .await
+    }
+}
@@ -0,0 +1,303 @@
+use super::{
+    resolvers::{has_role, role_members, DEFAULT_ADMIN_ROLE, OWNER_SELECTOR},
+    signer::is_contract,
+    validate::{OwnershipType, ResolvedOwner},
+};
+use crate::{
+    rpc::{self, call},
+    verify::Error,
+};
+use ethers::{
+    abi::{ParamType, Token},
+    types::Address,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+use tracing::instrument;
+
+/// `keccak256("PROPOSER_ROLE")` of OpenZeppelin `TimelockController`.
+const PROPOSER_ROLE: [u8; 32] = [
+    0xb0, 0x9a, 0xa5, 0xae, 0xb3, 0x70, 0x2c, 0xfd, 0x50, 0xb6, 0xb6, 0x2b, 0xc4, 0x53, 0x26, 0x04,
+    0x93, 0x8f, 0x21, 0x24, 0x8a, 0x27, 0xa1, 0xd5, 0xca, 0x73, 0x60, 0x82, 0xb6, 0x81, 0x9c, 0xc1,
+];
+/// Upper bound of accounts visited while searching for the chain, so that
+/// contracts with many controllers could not make us send too many requests.
+const MAX_CHAIN_ACCOUNTS: usize = 32;
+
+/// How the account controls the previous account of the ownership chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ControlRelation {
+    Owner,
+    GetOwner,
+    Admin,
+    AccessControlAdmin,
+    ProxyAdmin,
+    /// Holder of `PROPOSER_ROLE` of a `TimelockController`.
+    Proposer,
+}
+
+impl ControlRelation {
+    fn of_resolved(ownership_type: &OwnershipType) -> Option<Self> {
+        match ownership_type {
+            OwnershipType::Owner => Some(ControlRelation::Owner),
+            OwnershipType::GetOwner => Some(ControlRelation::GetOwner),
+            OwnershipType::Admin => Some(ControlRelation::Admin),
+            OwnershipType::AccessControlAdmin => Some(ControlRelation::AccessControlAdmin),
+            OwnershipType::ProxyAdmin => Some(ControlRelation::ProxyAdmin),
+            OwnershipType::Creator
+            | OwnershipType::SelfOwner
+            | OwnershipType::SafeOwner(_)
+            | OwnershipType::ChainOwner(_) => None,
+        }
+    }
+}
+
+/// Account of the ownership chain, controlling the previous one
+/// (or the verified contract, for the first account of the chain).
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ChainLink {
+    pub address: Address,
+    pub relation: ControlRelation,
+}
+
+/// Searches for the chain of contracts through which `signer` controls one of
+/// the resolved owners of the contract. Contracts are followed through their
+/// `owner()`, admins and proposers, up to `max_depth` contracts after the owner.
+#[instrument(skip(rpc), err, ret, level = "debug")]
+pub(super) async fn find_ownership_chain(
+    rpc: &rpc::Client,
+    owners: &[ResolvedOwner],
+    signer: Address,
+    max_depth: usize,
+) -> Result<Option<Vec<ChainLink>>, Error> {
+    let mut visited: HashSet<Address> = owners.iter().map(|owner| owner.address).collect();
+    let mut chains: VecDeque<Vec<ChainLink>> = owners
+        .iter()
+        .filter_map(|owner| {
+            ControlRelation::of_resolved(&owner.ownership_type).map(|relation| {
+                vec![ChainLink {
+                    address: owner.address,
+                    relation,
+                }]
+            })
+        })
+        .collect();
+
+    while let Some(chain) = chains.pop_front() {
+        let last = chain.last().expect("chains are not empty").address;
+        if chain.len() > max_depth || !is_contract(rpc, last).await? {
+            continue;
+        }
+        for link in controllers(rpc, last, signer).await? {
+            if link.address == signer {
+                let mut chain = chain;
+                chain.push(link);
+                return Ok(Some(chain));
+            }
+            if visited.len() < MAX_CHAIN_ACCOUNTS && visited.insert(link.address) {
+                let mut next = chain.clone();
+                next.push(link);
+                chains.push_back(next);
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Returns accounts controlling the contract. Roles of contracts which
+/// cannot enumerate their holders are only checked for `signer`.
+async fn controllers(
+    rpc: &rpc::Client,
+    contract: Address,
+    signer: Address,
+) -> Result<Vec<ChainLink>, Error> {
+    let mut links = Vec::new();
+    if let Some(Token::Address(owner)) =
+        call(rpc, contract, OWNER_SELECTOR, &[], ParamType::Address).await?
+    {
+        links.push(ChainLink {
+            address: owner,
+            relation: ControlRelation::Owner,
+        });
+    }
+    for (role, relation) in [
+        (DEFAULT_ADMIN_ROLE, ControlRelation::AccessControlAdmin),
+        (PROPOSER_ROLE, ControlRelation::Proposer),
+    ] {
+        if has_role(rpc, contract, role, signer).await? {
+            links.push(ChainLink {
+                address: signer,
+                relation,
+            });
+        }
+        let members = role_members(rpc, contract, role).await?;
+        links.extend(
+            members
+                .into_iter()
+                .map(|address| ChainLink { address, relation }),
+        );
+    }
+    links.retain(|link| !link.address.is_zero());
+    Ok(links)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::{abi, utils::hex};
+    use httpmock::prelude::*;
+    use pretty_assertions::assert_eq;
+    use std::str::FromStr;
+    use url::Url;
+
+    fn address(byte: u8) -> Address {
+        Address::from([byte; 20])
+    }
+
+    fn mock_call(server: &MockServer, to: Address, data: Vec<u8>, result: Option<Vec<Token>>) {
+        let response = match result {
+            Some(tokens) => serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": format!("0x{}", hex::encode(abi::encode(&tokens))),
+            }),
+            None => serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": {"code": 3, "message": "execution reverted"},
+            }),
+        };
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .body_contains(format!("\"to\":\"{to:#x}\""))
+                .body_contains(format!("\"data\":\"0x{}\"", hex::encode(data)));
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(response);
+        });
+    }
+
+    /// Mocks a contract with the given `owner()`, which grants `proposer`
+    /// the proposer role and cannot enumerate role holders.
+    fn mock_contract(
+        server: &MockServer,
+        contract: Address,
+        owner: Option<Address>,
+        proposer: Option<Address>,
+        signer: Address,
+    ) {
+        server.mock(|when, then| {
+            when.method(POST).path("/").json_body_partial(format!(
+                r#"{{"method": "eth_getCode", "params": ["{contract:#x}", "latest"]}}"#
+            ));
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(serde_json::json!({"jsonrpc": "2.0", "id": 1, "result": "0x6080"}));
+        });
+        mock_call(
+            server,
+            contract,
+            OWNER_SELECTOR.to_vec(),
+            owner.map(|owner| vec![Token::Address(owner)]),
+        );
+        for role in [DEFAULT_ADMIN_ROLE, PROPOSER_ROLE] {
+            let role_token = Token::FixedBytes(role.to_vec());
+            let has_role = role == PROPOSER_ROLE && proposer == Some(signer);
+            let data = [
+                [0x91, 0xd1, 0x48, 0x54].as_slice(),
+                &abi::encode(&[role_token.clone(), Token::Address(signer)]),
+            ]
+            .concat();
+            mock_call(server, contract, data, Some(vec![Token::Bool(has_role)]));
+            let data = [
+                [0xca, 0x15, 0xc8, 0x73].as_slice(),
+                &abi::encode(&[role_token]),
+            ]
+            .concat();
+            mock_call(server, contract, data, None);
+        }
+    }
+
+    fn root(address: Address) -> Vec<ResolvedOwner> {
+        vec![ResolvedOwner {
+            address,
+            ownership_type: OwnershipType::Owner,
+        }]
+    }
+
+    #[tokio::test]
+    async fn follows_owners() {
+        let server = MockServer::start();
+        let rpc = rpc::Client::new(
+            Url::from_str(&server.base_url()).unwrap(),
+            rpc::DEFAULT_RPC_TIMEOUT,
+        );
+        let signer = address(3);
+        mock_contract(&server, address(1), Some(address(2)), None, signer);
+        mock_contract(&server, address(2), Some(signer), None, signer);
+
+        let chain = find_ownership_chain(&rpc, &root(address(1)), signer, 2)
+            .await
+            .unwrap();
+        assert_eq!(
+            Some(vec![
+                ChainLink {
+                    address: address(1),
+                    relation: ControlRelation::Owner,
+                },
+                ChainLink {
+                    address: address(2),
+                    relation: ControlRelation::Owner,
+                },
+                ChainLink {
+                    address: signer,
+                    relation: ControlRelation::Owner,
+                },
+            ]),
+            chain
+        );
+
+        // The signer is too far from the contract owner
+        let chain = find_ownership_chain(&rpc, &root(address(1)), signer, 1)
+            .await
+            .unwrap();
+        assert_eq!(None, chain);
+    }
+
+    #[tokio::test]
+    async fn follows_timelock_proposers() {
+        let server = MockServer::start();
+        let rpc = rpc::Client::new(
+            Url::from_str(&server.base_url()).unwrap(),
+            rpc::DEFAULT_RPC_TIMEOUT,
+        );
+        let signer = address(3);
+        mock_contract(&server, address(1), None, Some(signer), signer);
+
+        let chain = find_ownership_chain(&rpc, &root(address(1)), signer, 1)
+            .await
+            .unwrap();
+        assert_eq!(
+            Some(vec![
+                ChainLink {
+                    address: address(1),
+                    relation: ControlRelation::Owner,
+                },
+                ChainLink {
+                    address: signer,
+                    relation: ControlRelation::Proposer,
+                },
+            ]),
+            chain
+        );
+
+        let other_signer = address(4);
+        mock_contract(&server, address(1), None, None, other_signer);
+        let chain = find_ownership_chain(&rpc, &root(address(1)), other_signer, 1)
+            .await
+            .unwrap();
+        assert_eq!(None, chain);
+    }
+}
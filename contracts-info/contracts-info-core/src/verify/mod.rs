@@ -1,7 +1,22 @@
+mod chain;
 mod ownership;
+mod resolvers;
+mod safe;
+mod signer;
+mod siwe;
+mod typed_data;
 mod validate;
-pub use ownership::{Message, ValidatedMessage, ValidatedOwnership, TS_FORMAT};
-pub use validate::ownership_options;
+pub use chain::{ChainLink, ControlRelation};
+pub use ownership::{Message, MessageKind, ValidatedMessage, ValidatedOwnership, TS_FORMAT};
+pub use resolvers::{OwnershipResolver, DEFAULT_OWNERSHIP_RESOLVERS};
+pub use safe::SafeOwners;
+pub use signer::verify_signer;
+pub use siwe::Siwe;
+pub use typed_data::TypedClaim;
+pub use validate::{
+    ownership_options, CreatorResolution, OwnershipConfig, OwnershipType, ResolvedOwner,
+    SINGLETON_FACTORY_ADDRESSES,
+};
 
 use ethers::types::{Address, SignatureError};
 use thiserror::Error;
@@ -16,6 +31,8 @@ pub enum Error {
     Signature(#[from] SignatureError),
     #[error("message expired")]
     Expired,
+    #[error("message timestamp {0} is too far in the future")]
+    FutureTimestamp(chrono::NaiveDateTime),
     #[error("{suggested_owner:#x} is not an owner of contract {contract:#x}; possible owners: {possible_owners:?}")]
     WrongOwner {
         contract: Address,
@@ -30,4 +47,10 @@ pub enum Error {
     ContractNotVerified(Address),
     #[error("blockscout request failed: {0}")]
     BlockscoutRequest(String),
+    #[error("signature is not valid for contract signer {0:#x}")]
+    InvalidContractSignature(Address),
+    #[error("smart contract signatures are not supported for the chain")]
+    ContractSignaturesNotSupported,
+    #[error("json-rpc request failed: {0}")]
+    RpcRequest(String),
 }
@@ -1,16 +1,41 @@
-use super::validate::try_validate_ownership;
-use crate::{blockscout, verify::Error};
+use super::{
+    chain::ChainLink,
+    safe::SafeOwners,
+    signer::verify_signer,
+    siwe::{self, Siwe},
+    typed_data::{self, TypedClaim},
+    validate::{try_validate_ownership, OwnershipConfig, OwnershipType},
+};
+use crate::{blockscout, rpc, verify::Error};
 use chrono::{NaiveDateTime, Utc};
-use ethers::types::{Address, Signature};
+use ethers::{
+    core::rand::{distributions::Alphanumeric, thread_rng, Rng},
+    types::{Address, H256},
+    utils::hash_message,
+};
 use std::{fmt::Display, str::FromStr};
 
 pub const TS_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+const NONCE_LENGTH: usize = 17;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Message {
     pub site: String,
     pub timestamp: NaiveDateTime,
     pub address: Address,
+    pub kind: MessageKind,
+}
+
+/// Format of the message and the fields specific to it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MessageKind {
+    /// `[site] [timestamp] text [address] [nonce]` message signed with `personal_sign`.
+    /// Messages issued before nonces were introduced have no nonce.
+    Legacy { nonce: Option<String> },
+    /// Sign-In with Ethereum (EIP-4361) message signed with `personal_sign`.
+    Siwe(Siwe),
+    /// EIP-712 typed data signed with `eth_signTypedData_v4`.
+    TypedData(TypedClaim),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -24,7 +49,9 @@ impl Message {
     pub fn validate(
         self,
         site: &str,
+        chain_id: i64,
         min_timestamp: NaiveDateTime,
+        max_timestamp: NaiveDateTime,
         address: &Address,
     ) -> Result<ValidatedMessage, Error> {
         if self.site != site {
@@ -42,14 +69,93 @@ impl Message {
         if self.timestamp < min_timestamp {
             return Err(Error::Expired);
         }
+        if self.timestamp > max_timestamp {
+            return Err(Error::FutureTimestamp(self.timestamp));
+        }
+        match &self.kind {
+            MessageKind::Legacy { .. } => {}
+            MessageKind::Siwe(siwe) => siwe.validate(site, chain_id)?,
+            MessageKind::TypedData(claim) if claim.chain_id != chain_id => {
+                return Err(Error::InvalidValue(format!(
+                    "expected chain id {chain_id}, got {}",
+                    claim.chain_id
+                )))
+            }
+            MessageKind::TypedData(_) => {}
+        }
         Ok(ValidatedMessage(self))
     }
 
-    pub fn new(site: String, address: Address) -> Self {
+    pub fn new(site: String, address: Address, nonce: String) -> Self {
         Self {
             site,
             timestamp: Utc::now().naive_utc(),
             address,
+            kind: MessageKind::Legacy { nonce: Some(nonce) },
+        }
+    }
+
+    /// Creates Sign-In with Ethereum (EIP-4361) message, which has to be signed by `signer`
+    /// before the expiration time and is bound to the site and chain.
+    pub fn new_siwe(
+        site: String,
+        uri: String,
+        chain_id: i64,
+        address: Address,
+        signer: Address,
+        nonce: String,
+        validity: chrono::Duration,
+    ) -> Self {
+        let timestamp = Utc::now().naive_utc();
+        Self {
+            site,
+            timestamp,
+            address,
+            kind: MessageKind::Siwe(Siwe {
+                signer,
+                uri,
+                chain_id,
+                nonce,
+                expiration_time: timestamp.checked_add_signed(validity),
+                not_before: None,
+            }),
+        }
+    }
+
+    /// Creates EIP-712 typed data message, which is bound to the site and chain.
+    pub fn new_typed_data(site: String, chain_id: i64, address: Address, nonce: String) -> Self {
+        Self {
+            site,
+            // Typed data carries the timestamp in seconds
+            timestamp: NaiveDateTime::from_timestamp_opt(Utc::now().timestamp(), 0)
+                .expect("current time is in range"),
+            address,
+            kind: MessageKind::TypedData(TypedClaim { chain_id, nonce }),
+        }
+    }
+
+    /// Generates a random alphanumeric nonce, which is valid in all message formats.
+    pub fn generate_nonce() -> String {
+        thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(NONCE_LENGTH)
+            .map(char::from)
+            .collect()
+    }
+
+    pub fn nonce(&self) -> Option<&str> {
+        match &self.kind {
+            MessageKind::Legacy { nonce } => nonce.as_deref(),
+            MessageKind::Siwe(siwe) => Some(&siwe.nonce),
+            MessageKind::TypedData(claim) => Some(&claim.nonce),
+        }
+    }
+
+    /// Returns the hash signed by the wallet for `data`, which is the text representation of the message.
+    pub fn signing_hash(&self, data: &str) -> Result<H256, Error> {
+        match &self.kind {
+            MessageKind::Legacy { .. } | MessageKind::Siwe(_) => Ok(hash_message(data)),
+            MessageKind::TypedData(claim) => typed_data::hash(self, claim),
         }
     }
 }
@@ -57,8 +163,22 @@ impl Message {
 impl FromStr for Message {
     type Err = Error;
     fn from_str(value: &str) -> Result<Self, Self::Err> {
-        let (site, timestamp, text, address) =
-            sscanf::sscanf!(value, "[{str}] [{str}] {str} [{str}]")
+        if siwe::is_siwe(value) {
+            return siwe::parse(value);
+        }
+        if typed_data::is_typed_data(value) {
+            return typed_data::parse(value);
+        }
+        let (site, timestamp, text, address, nonce) =
+            sscanf::sscanf!(value, "[{str}] [{str}] {str} [{str}] [{str}]")
+                .map(|(site, timestamp, text, address, nonce)| {
+                    (site, timestamp, text, address, Some(nonce.to_owned()))
+                })
+                .or_else(|_| {
+                    sscanf::sscanf!(value, "[{str}] [{str}] {str} [{str}]").map(
+                        |(site, timestamp, text, address)| (site, timestamp, text, address, None),
+                    )
+                })
                 .map_err(|e| Error::InvalidFormat(e.to_string()))?;
         let site = site.to_owned();
         let timestamp = NaiveDateTime::parse_from_str(timestamp, TS_FORMAT)
@@ -75,12 +195,18 @@ impl FromStr for Message {
             site,
             timestamp,
             address,
+            kind: MessageKind::Legacy { nonce },
         })
     }
 }
 
 impl Display for Message {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let nonce = match &self.kind {
+            MessageKind::Legacy { nonce } => nonce,
+            MessageKind::Siwe(siwe) => return siwe::format(self, siwe, f),
+            MessageKind::TypedData(claim) => return typed_data::format(self, claim, f),
+        };
         write!(
             f,
             "[{}] [{}] {} [{:#x}]",
@@ -88,7 +214,11 @@ impl Display for Message {
             self.timestamp.format(TS_FORMAT),
             Self::text(),
             &self.address
-        )
+        )?;
+        if let Some(nonce) = nonce {
+            write!(f, " [{nonce}]")?;
+        }
+        Ok(())
     }
 }
 
@@ -97,22 +227,36 @@ pub struct ValidatedOwnership {
     pub owner: Address,
     pub contract: Address,
     pub message: ValidatedMessage,
+    /// Set if the signer is an owner of the Safe multisig wallet owning the contract.
+    pub safe: Option<SafeOwners>,
+    /// Set if the signer controls the contract owner through other contracts,
+    /// starting with the contract owner and ending with the signer.
+    pub ownership_chain: Vec<ChainLink>,
 }
 
 impl ValidatedOwnership {
     pub async fn new(
         client: &blockscout::Client,
+        rpc: Option<&rpc::Client>,
+        config: &OwnershipConfig,
         owner: Address,
         contract: Address,
         message: ValidatedMessage,
     ) -> Result<Self, Error> {
-        match try_validate_ownership(client, owner, contract).await {
+        match try_validate_ownership(client, rpc, config, owner, contract).await {
             Ok(ownership_type) => {
                 tracing::info!(owner = ?owner, contract = ?contract, ownership_type = ?ownership_type, "ownership verified");
+                let (safe, ownership_chain) = match ownership_type {
+                    OwnershipType::SafeOwner(safe) => (Some(safe), vec![]),
+                    OwnershipType::ChainOwner(chain) => (None, chain),
+                    _ => (None, vec![]),
+                };
                 Ok(Self {
                     owner,
                     contract,
                     message,
+                    safe,
+                    ownership_chain,
                 })
             }
             Err(err) => {
@@ -122,18 +266,43 @@ impl ValidatedOwnership {
         }
     }
 
+    /// Validates that the message is signed by the owner of the contract.
+    /// `signer` has to be specified for smart contract wallets, whose signatures
+    /// are checked through `rpc` as described in EIP-1271.
+    ///
+    /// `config` defines who is allowed to verify the contract ownership.
+    ///
+    /// The message must be issued within `min_timestamp..=max_timestamp`.
+    /// Sign-In with Ethereum messages must be signed by the account they specify.
+    #[allow(clippy::too_many_arguments)]
     pub async fn validate(
         client: &blockscout::Client,
-        signature: &Signature,
+        rpc: Option<&rpc::Client>,
+        config: &OwnershipConfig,
+        signature: &[u8],
+        signer: Option<Address>,
         data: &str,
         contract: Address,
         site: &str,
+        chain_id: i64,
         min_timestamp: NaiveDateTime,
+        max_timestamp: NaiveDateTime,
     ) -> Result<Self, Error> {
         let message = Message::from_str(data)?;
-        let validated = message.validate(site, min_timestamp, &contract)?;
-        let owner = signature.recover(data)?;
-        Self::new(client, owner, contract, validated).await
+        let validated =
+            message.validate(site, chain_id, min_timestamp, max_timestamp, &contract)?;
+        let message_signer = match &validated.0.kind {
+            MessageKind::Siwe(siwe) => Some(siwe.signer),
+            MessageKind::Legacy { .. } | MessageKind::TypedData(_) => None,
+        };
+        let hash = validated.0.signing_hash(data)?;
+        let owner = verify_signer(rpc, signature, hash, signer.or(message_signer)).await?;
+        if let Some(message_signer) = message_signer.filter(|&expected| expected != owner) {
+            return Err(Error::InvalidValue(format!(
+                "message must be signed by {message_signer:#x}, got {owner:#x}"
+            )));
+        }
+        Self::new(client, rpc, config, owner, contract, validated).await
     }
 }
 
@@ -158,7 +327,9 @@ mod tests {
         message
             .validate(
                 "BlockScout",
+                1,
                 NaiveDateTime::parse_from_str("2022-12-29 23:13:59", TS_FORMAT).unwrap(),
+                NaiveDateTime::parse_from_str("2022-12-31 23:13:59", TS_FORMAT).unwrap(),
                 &get_address(),
             )
             .unwrap()
@@ -174,11 +345,21 @@ mod tests {
                 site: "BlockScout".into(),
                 timestamp: NaiveDateTime::parse_from_str("2022-12-30 23:13:59", TS_FORMAT).unwrap(),
                 address: get_address(),
+                kind: MessageKind::Legacy { nonce: None },
             }
         );
         assert_eq!(data, message.to_string());
     }
 
+    #[test]
+    fn test_parse_with_nonce() {
+        let data = format!("{} [32891756a1b2c3d4e]", get_data());
+        let message = Message::from_str(&data).unwrap();
+        assert_eq!(Some("32891756a1b2c3d4e"), message.nonce());
+        assert_eq!(get_address(), message.address);
+        assert_eq!(data, message.to_string());
+    }
+
     #[test]
     fn test_parse_err() {
         let data = "[BlockScout 2022-12-30 23:13:59] I, hereby verify that I am the owner/creator of the address [0x0000000000000000000000000000000001020304]";
@@ -191,12 +372,15 @@ mod tests {
             site: "BlockScout".into(),
             timestamp: NaiveDateTime::parse_from_str("2022-12-30 23:13:59", TS_FORMAT).unwrap(),
             address: get_address(),
+            kind: MessageKind::Legacy { nonce: None },
         };
         let validated = message
             .clone()
             .validate(
                 "BlockScout",
+                1,
                 NaiveDateTime::parse_from_str("2022-12-29 23:13:59", TS_FORMAT).unwrap(),
+                NaiveDateTime::parse_from_str("2022-12-31 23:13:59", TS_FORMAT).unwrap(),
                 &get_address(),
             )
             .unwrap();
@@ -209,12 +393,15 @@ mod tests {
             site: "BlockScout".into(),
             timestamp: NaiveDateTime::parse_from_str("2022-12-30 23:13:59", TS_FORMAT).unwrap(),
             address: get_address(),
+            kind: MessageKind::Legacy { nonce: None },
         };
         message
             .clone()
             .validate(
                 "Etherscan",
+                1,
                 NaiveDateTime::parse_from_str("2022-12-29 23:13:59", TS_FORMAT).unwrap(),
+                NaiveDateTime::parse_from_str("2022-12-31 23:13:59", TS_FORMAT).unwrap(),
                 &get_address(),
             )
             .unwrap_err();
@@ -222,16 +409,31 @@ mod tests {
             .clone()
             .validate(
                 "BlockScout",
+                1,
                 NaiveDateTime::parse_from_str("2022-12-31 23:13:59", TS_FORMAT).unwrap(),
+                NaiveDateTime::parse_from_str("2023-01-01 23:13:59", TS_FORMAT).unwrap(),
                 &get_address(),
             )
             .unwrap_err();
+        let result = message.clone().validate(
+            "BlockScout",
+            1,
+            NaiveDateTime::parse_from_str("2022-12-29 23:13:59", TS_FORMAT).unwrap(),
+            NaiveDateTime::parse_from_str("2022-12-30 23:08:59", TS_FORMAT).unwrap(),
+            &get_address(),
+        );
+        assert!(
+            matches!(result, Err(Error::FutureTimestamp(_))),
+            "{result:?}"
+        );
         let mut address = get_address();
         address.0[0] = 5;
         message
             .validate(
                 "BlockScout",
+                1,
                 NaiveDateTime::parse_from_str("2022-12-29 23:13:59", TS_FORMAT).unwrap(),
+                NaiveDateTime::parse_from_str("2022-12-31 23:13:59", TS_FORMAT).unwrap(),
                 &address,
             )
             .unwrap_err();
@@ -267,15 +469,22 @@ mod tests {
             blockscout::Client::new(Url::from_str(&blockscout_server.base_url()).unwrap());
 
         let data = get_data();
-        let signature = futures::executor::block_on(wallet.sign_message(data)).unwrap();
+        let signature = futures::executor::block_on(wallet.sign_message(data))
+            .unwrap()
+            .to_vec();
         let contract = Address::from_str("0x0000000000000000000000000000000001020304").unwrap();
         let ownership = ValidatedOwnership::validate(
             &blockscout_client,
+            None,
+            &OwnershipConfig::default(),
             &signature,
+            None,
             data,
             contract,
             "BlockScout",
+            1,
             NaiveDateTime::parse_from_str("2022-12-29 23:13:59", TS_FORMAT).unwrap(),
+            NaiveDateTime::parse_from_str("2022-12-31 23:13:59", TS_FORMAT).unwrap(),
         )
         .await
         .unwrap();
@@ -286,11 +495,85 @@ mod tests {
                 owner: wallet.address(),
                 contract,
                 message: get_validated(),
+                safe: None,
+                ownership_chain: vec![],
             }
         );
         address_handler.assert_hits(1);
     }
 
+    #[tokio::test]
+    async fn test_siwe_ownership() {
+        let wallet = LocalWallet::new(&mut rand::thread_rng());
+
+        let blockscout_server = MockServer::start();
+        blockscout_server.mock(|when, then| {
+            when.method(GET)
+                .path("/api/v2/addresses/0x0000000000000000000000000000000001020304");
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(serde_json::json!({
+                    "creator_address_hash": wallet.address(),
+                    "is_contract": true,
+                    "is_verified": true,
+                }));
+        });
+        blockscout_server.mock(|when, then| {
+            when.method(GET).path(
+                "/api/v2/smart-contracts/0x0000000000000000000000000000000001020304/methods-read",
+            );
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(serde_json::json!([]));
+        });
+
+        let blockscout_client =
+            blockscout::Client::new(Url::from_str(&blockscout_server.base_url()).unwrap());
+
+        let contract = Address::from_str("0x0000000000000000000000000000000001020304").unwrap();
+        let message = Message::new_siwe(
+            "blockscout.com".into(),
+            "https://blockscout.com/".into(),
+            5,
+            contract,
+            wallet.address(),
+            Message::generate_nonce(),
+            chrono::Duration::hours(24),
+        );
+        let data = message.to_string();
+        let validate = |signature: Vec<u8>| {
+            let data = data.clone();
+            let blockscout_client = blockscout_client.clone();
+            async move {
+                ValidatedOwnership::validate(
+                    &blockscout_client,
+                    None,
+                    &OwnershipConfig::default(),
+                    &signature,
+                    None,
+                    &data,
+                    contract,
+                    "blockscout.com",
+                    5,
+                    Utc::now().naive_utc() - chrono::Duration::hours(24),
+                    Utc::now().naive_utc() + chrono::Duration::minutes(5),
+                )
+                .await
+            }
+        };
+
+        let signature = wallet.sign_message(&data).await.unwrap().to_vec();
+        let ownership = validate(signature).await.unwrap();
+        assert_eq!(wallet.address(), ownership.owner);
+        assert_eq!(Message::from_str(&data).unwrap(), ownership.message.0);
+
+        // The message must be signed by the account it specifies
+        let other_wallet = LocalWallet::new(&mut rand::thread_rng());
+        let signature = other_wallet.sign_message(&data).await.unwrap().to_vec();
+        let result = validate(signature).await;
+        assert!(matches!(result, Err(Error::Signature(_))), "{result:?}");
+    }
+
     #[tokio::test]
     async fn test_owner_call_ownership() {
         let wallet = LocalWallet::new(&mut rand::thread_rng());
@@ -349,15 +632,22 @@ mod tests {
             blockscout::Client::new(Url::from_str(&blockscout_server.base_url()).unwrap());
 
         let data = get_data();
-        let signature = futures::executor::block_on(wallet.sign_message(data)).unwrap();
+        let signature = futures::executor::block_on(wallet.sign_message(data))
+            .unwrap()
+            .to_vec();
         let contract = Address::from_str("0x0000000000000000000000000000000001020304").unwrap();
         let ownership = ValidatedOwnership::validate(
             &blockscout_client,
+            None,
+            &OwnershipConfig::default(),
             &signature,
+            None,
             data,
             contract,
             "BlockScout",
+            1,
             NaiveDateTime::parse_from_str("2022-12-29 23:13:59", TS_FORMAT).unwrap(),
+            NaiveDateTime::parse_from_str("2022-12-31 23:13:59", TS_FORMAT).unwrap(),
         )
         .await
         .unwrap();
@@ -368,6 +658,8 @@ mod tests {
                 owner: wallet.address(),
                 contract,
                 message: get_validated(),
+                safe: None,
+                ownership_chain: vec![],
             }
         );
         address_handler.assert_hits(1);
@@ -416,15 +708,22 @@ mod tests {
             blockscout::Client::new(Url::from_str(&blockscout_server.base_url()).unwrap());
 
         let data = get_data();
-        let signature = futures::executor::block_on(wallet.sign_message(data)).unwrap();
+        let signature = futures::executor::block_on(wallet.sign_message(data))
+            .unwrap()
+            .to_vec();
         let contract = Address::from_str("0x0000000000000000000000000000000001020304").unwrap();
         let ownership = ValidatedOwnership::validate(
             &blockscout_client,
+            None,
+            &OwnershipConfig::default(),
             &signature,
+            None,
             data,
             contract,
             "BlockScout",
+            1,
             NaiveDateTime::parse_from_str("2022-12-29 23:13:59", TS_FORMAT).unwrap(),
+            NaiveDateTime::parse_from_str("2022-12-31 23:13:59", TS_FORMAT).unwrap(),
         )
         .await
         .unwrap();
@@ -435,6 +734,8 @@ mod tests {
                 owner: wallet.address(),
                 contract,
                 message: get_validated(),
+                safe: None,
+                ownership_chain: vec![],
             }
         );
         address_handler.assert_hits(1);
@@ -477,15 +778,22 @@ mod tests {
             blockscout::Client::new(Url::from_str(&blockscout_server.base_url()).unwrap());
 
         let data = get_data();
-        let signature = futures::executor::block_on(wallet.sign_message(data)).unwrap();
+        let signature = futures::executor::block_on(wallet.sign_message(data))
+            .unwrap()
+            .to_vec();
         let contract = Address::from_str("0x0000000000000000000000000000000001020304").unwrap();
         let ownership_err = ValidatedOwnership::validate(
             &blockscout_client,
+            None,
+            &OwnershipConfig::default(),
             &signature,
+            None,
             data,
             contract,
             "BlockScout",
+            1,
             NaiveDateTime::parse_from_str("2022-12-29 23:13:59", TS_FORMAT).unwrap(),
+            NaiveDateTime::parse_from_str("2022-12-31 23:13:59", TS_FORMAT).unwrap(),
         )
         .await
         .unwrap_err();
@@ -528,15 +836,22 @@ mod tests {
             blockscout::Client::new(Url::from_str(&blockscout_server.base_url()).unwrap());
 
         let data = get_data();
-        let signature = futures::executor::block_on(wallet.sign_message(data)).unwrap();
+        let signature = futures::executor::block_on(wallet.sign_message(data))
+            .unwrap()
+            .to_vec();
         let contract = Address::from_str("0x0000000000000000000000000000000001020304").unwrap();
         let ownership_err = ValidatedOwnership::validate(
             &blockscout_client,
+            None,
+            &OwnershipConfig::default(),
             &signature,
+            None,
             data,
             contract,
             "BlockScout",
+            1,
             NaiveDateTime::parse_from_str("2022-12-29 23:13:59", TS_FORMAT).unwrap(),
+            NaiveDateTime::parse_from_str("2022-12-31 23:13:59", TS_FORMAT).unwrap(),
         )
         .await
         .unwrap_err();
@@ -553,13 +868,14 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_not_contract() {
+    async fn test_externally_owned_ownership() {
         let wallet = LocalWallet::new(&mut rand::thread_rng());
+        let account = wallet.address();
 
         let blockscout_server = MockServer::start();
         let address_handler = blockscout_server.mock(|when, then| {
             when.method(GET)
-                .path("/api/v2/addresses/0x0000000000000000000000000000000001020304");
+                .path(format!("/api/v2/addresses/{account:#x}"));
             then.status(200)
                 .header("content-type", "application/json")
                 .json_body(serde_json::json!({
@@ -571,29 +887,50 @@ mod tests {
         let blockscout_client =
             blockscout::Client::new(Url::from_str(&blockscout_server.base_url()).unwrap());
 
-        let data = get_data();
-        let signature = futures::executor::block_on(wallet.sign_message(data)).unwrap();
-        let contract = Address::from_str("0x0000000000000000000000000000000001020304").unwrap();
-        let ownership_err = ValidatedOwnership::validate(
-            &blockscout_client,
-            &signature,
-            data,
-            contract,
-            "BlockScout",
-            NaiveDateTime::parse_from_str("2022-12-29 23:13:59", TS_FORMAT).unwrap(),
-        )
-        .await
-        .unwrap_err();
+        let data = format!(
+            "[BlockScout] [2022-12-30 23:13:59] I, hereby verify that I am the owner/creator of the address [{account:#x}]"
+        );
+        let validate = |wallet: LocalWallet| {
+            let blockscout_client = &blockscout_client;
+            let data = &data;
+            async move {
+                let signature = wallet.sign_message(data).await.unwrap().to_vec();
+                ValidatedOwnership::validate(
+                    blockscout_client,
+                    None,
+                    &OwnershipConfig::default(),
+                    &signature,
+                    None,
+                    data,
+                    account,
+                    "BlockScout",
+                    1,
+                    NaiveDateTime::parse_from_str("2022-12-29 23:13:59", TS_FORMAT).unwrap(),
+                    NaiveDateTime::parse_from_str("2022-12-31 23:13:59", TS_FORMAT).unwrap(),
+                )
+                .await
+            }
+        };
+
+        // The account signs as itself
+        let ownership = validate(wallet).await.unwrap();
+        assert_eq!(ownership.owner, account);
+        assert_eq!(ownership.contract, account);
+        assert_eq!(ownership.safe, None);
 
+        // Nobody else can verify the account
+        let ownership_err = validate(LocalWallet::new(&mut rand::thread_rng()))
+            .await
+            .unwrap_err();
         match ownership_err {
-            Error::ContractNotFound(err_contract) => {
-                assert_eq!(err_contract, contract);
+            Error::WrongOwner { contract, .. } => {
+                assert_eq!(contract, account);
             }
             _ => {
-                panic!("expected ContractNotFound error, but got: {ownership_err}");
+                panic!("expected WrongOwner error, but got: {ownership_err}");
             }
         }
-        address_handler.assert_hits(1);
+        address_handler.assert_hits(2);
     }
 
     #[tokio::test]
@@ -613,15 +950,22 @@ mod tests {
         });
 
         let data = get_data();
-        let signature = futures::executor::block_on(wallet.sign_message(data)).unwrap();
+        let signature = futures::executor::block_on(wallet.sign_message(data))
+            .unwrap()
+            .to_vec();
         let contract = Address::from_str("0x0000000000000000000000000000000001020304").unwrap();
         let ownership_err = ValidatedOwnership::validate(
             &blockscout_client,
+            None,
+            &OwnershipConfig::default(),
             &signature,
+            None,
             data,
             contract,
             "BlockScout",
+            1,
             NaiveDateTime::parse_from_str("2022-12-29 23:13:59", TS_FORMAT).unwrap(),
+            NaiveDateTime::parse_from_str("2022-12-31 23:13:59", TS_FORMAT).unwrap(),
         )
         .await
         .unwrap_err();
@@ -659,15 +1003,22 @@ mod tests {
             blockscout::Client::new(Url::from_str(&blockscout_server.base_url()).unwrap());
 
         let data = get_data();
-        let signature = futures::executor::block_on(wallet.sign_message(data)).unwrap();
+        let signature = futures::executor::block_on(wallet.sign_message(data))
+            .unwrap()
+            .to_vec();
         let contract = Address::from_str("0x0000000000000000000000000000000001020304").unwrap();
         let ownership_err = ValidatedOwnership::validate(
             &blockscout_client,
+            None,
+            &OwnershipConfig::default(),
             &signature,
+            None,
             data,
             contract,
             "BlockScout",
+            1,
             NaiveDateTime::parse_from_str("2022-12-29 23:13:59", TS_FORMAT).unwrap(),
+            NaiveDateTime::parse_from_str("2022-12-31 23:13:59", TS_FORMAT).unwrap(),
         )
         .await
         .unwrap_err();
@@ -0,0 +1,392 @@
+use super::validate::OwnershipType;
+use crate::{
+    blockscout,
+    rpc::{self, api::Response, call},
+    verify::Error,
+};
+use ethers::{
+    abi::{ParamType, Token},
+    types::{Address, H256, U256},
+};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Selector of `owner()` of `Ownable` contracts.
+pub(super) const OWNER_SELECTOR: [u8; 4] = [0x8d, 0xa5, 0xcb, 0x5b];
+/// Selector of `getOwner()`.
+const GET_OWNER_SELECTOR: [u8; 4] = [0x89, 0x3d, 0x20, 0xe8];
+/// Selector of `admin()`.
+const ADMIN_SELECTOR: [u8; 4] = [0xf8, 0x51, 0xa4, 0x40];
+/// Selector of `getRoleMemberCount(bytes32)` of `AccessControlEnumerable` contracts.
+const GET_ROLE_MEMBER_COUNT_SELECTOR: [u8; 4] = [0xca, 0x15, 0xc8, 0x73];
+/// Selector of `getRoleMember(bytes32,uint256)` of `AccessControlEnumerable` contracts.
+const GET_ROLE_MEMBER_SELECTOR: [u8; 4] = [0x90, 0x10, 0xd0, 0x7c];
+/// Selector of `hasRole(bytes32,address)` of `AccessControl` contracts.
+const HAS_ROLE_SELECTOR: [u8; 4] = [0x91, 0xd1, 0x48, 0x54];
+/// `DEFAULT_ADMIN_ROLE` of OpenZeppelin `AccessControl`, which can manage all other roles.
+pub(super) const DEFAULT_ADMIN_ROLE: [u8; 32] = [0; 32];
+/// Upper bound of admins enumerated per contract, so that misbehaving
+/// contracts could not make us send an arbitrary number of requests.
+const MAX_ROLE_MEMBERS: u64 = 16;
+/// `bytes32(uint256(keccak256("eip1967.proxy.admin")) - 1)`, as defined in EIP-1967.
+const EIP1967_ADMIN_SLOT: [u8; 32] = [
+    0xb5, 0x31, 0x27, 0x68, 0x4a, 0x56, 0x8b, 0x31, 0x73, 0xae, 0x13, 0xb9, 0xf8, 0xa6, 0x01, 0x6e,
+    0x24, 0x3e, 0x63, 0xb6, 0xe8, 0xee, 0x11, 0x78, 0xd6, 0xa7, 0x17, 0x85, 0x0b, 0x5d, 0x61, 0x03,
+];
+
+/// Resolvers used if none are configured for the chain.
+pub const DEFAULT_OWNERSHIP_RESOLVERS: &[OwnershipResolver] = &[OwnershipResolver::OwnerMethod];
+
+/// Source of accounts allowed to verify the contract ownership
+/// in addition to the contract creator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OwnershipResolver {
+    /// Result of the `owner()` read method.
+    OwnerMethod,
+    /// Result of the `getOwner()` read method.
+    GetOwnerMethod,
+    /// Result of the `admin()` read method.
+    AdminMethod,
+    /// Holders of `DEFAULT_ADMIN_ROLE` of OpenZeppelin `AccessControl` contracts.
+    /// Requires the json-rpc endpoint of the chain.
+    AccessControlAdmin,
+    /// Admin of the upgradeable proxy stored in the EIP-1967 admin slot.
+    /// Requires the json-rpc endpoint of the chain.
+    Eip1967ProxyAdmin,
+}
+
+impl OwnershipResolver {
+    /// Ownership type reported for the accounts found by the resolver.
+    pub fn ownership_type(&self) -> OwnershipType {
+        match self {
+            OwnershipResolver::OwnerMethod => OwnershipType::Owner,
+            OwnershipResolver::GetOwnerMethod => OwnershipType::GetOwner,
+            OwnershipResolver::AdminMethod => OwnershipType::Admin,
+            OwnershipResolver::AccessControlAdmin => OwnershipType::AccessControlAdmin,
+            OwnershipResolver::Eip1967ProxyAdmin => OwnershipType::ProxyAdmin,
+        }
+    }
+
+    /// Name and selector of the read method, if the resolver uses one.
+    pub(super) fn method(&self) -> Option<(&'static str, [u8; 4])> {
+        match self {
+            OwnershipResolver::OwnerMethod => Some(("owner", OWNER_SELECTOR)),
+            OwnershipResolver::GetOwnerMethod => Some(("getOwner", GET_OWNER_SELECTOR)),
+            OwnershipResolver::AdminMethod => Some(("admin", ADMIN_SELECTOR)),
+            OwnershipResolver::AccessControlAdmin | OwnershipResolver::Eip1967ProxyAdmin => None,
+        }
+    }
+
+    /// Returns non-zero accounts found by the resolver. `methods` are read methods
+    /// of the contract as returned by blockscout, which are called through `rpc`
+    /// instead if not set (e.g. if the contract source code is not verified).
+    /// Resolvers which require the json-rpc endpoint find nothing if `rpc` is not set.
+    pub(super) async fn resolve(
+        &self,
+        methods: Option<&[blockscout::api::Method]>,
+        rpc: Option<&rpc::Client>,
+        contract: Address,
+    ) -> Result<Vec<Address>, Error> {
+        let addresses = match (self.method(), methods, rpc) {
+            (Some((name, _)), Some(methods), _) => {
+                method_address(methods, name)?.into_iter().collect()
+            }
+            (Some((_, selector)), None, Some(rpc)) => {
+                let output = call(rpc, contract, selector, &[], ParamType::Address).await?;
+                output.and_then(Token::into_address).into_iter().collect()
+            }
+            (_, _, None) => vec![],
+            (None, _, Some(rpc)) => match self {
+                OwnershipResolver::AccessControlAdmin => {
+                    role_members(rpc, contract, DEFAULT_ADMIN_ROLE).await?
+                }
+                OwnershipResolver::Eip1967ProxyAdmin => vec![proxy_admin(rpc, contract).await?],
+                _ => vec![],
+            },
+        };
+        Ok(addresses
+            .into_iter()
+            .filter(|address| !address.is_zero())
+            .collect())
+    }
+}
+
+fn method_address(
+    methods: &[blockscout::api::Method],
+    name: &str,
+) -> Result<Option<Address>, Error> {
+    methods
+        .iter()
+        .find(|method| method.name == name)
+        .and_then(|method| {
+            method
+                .outputs
+                .iter()
+                .find(|output| output._type == "address")
+        })
+        .and_then(|output| output.value.as_str().map(Address::from_str))
+        .transpose()
+        .map_err(|_| Error::BlockscoutRequest(format!("{name}() address field is invalid")))
+}
+
+/// Enumerates holders of the `AccessControl` role. Returns nothing if the contract
+/// does not implement `AccessControlEnumerable`; holders of roles of such contracts
+/// can only be checked one by one with [`has_role`].
+pub(super) async fn role_members(
+    rpc: &rpc::Client,
+    contract: Address,
+    role: [u8; 32],
+) -> Result<Vec<Address>, Error> {
+    let role = Token::FixedBytes(role.to_vec());
+    let count = match call(
+        rpc,
+        contract,
+        GET_ROLE_MEMBER_COUNT_SELECTOR,
+        std::slice::from_ref(&role),
+        ParamType::Uint(256),
+    )
+    .await?
+    {
+        Some(Token::Uint(count)) => count.min(MAX_ROLE_MEMBERS.into()).as_u64(),
+        _ => return Ok(vec![]),
+    };
+    let mut members = Vec::new();
+    for index in 0..count {
+        let member = call(
+            rpc,
+            contract,
+            GET_ROLE_MEMBER_SELECTOR,
+            &[role.clone(), Token::Uint(U256::from(index))],
+            ParamType::Address,
+        )
+        .await?;
+        if let Some(member) = member.and_then(Token::into_address) {
+            members.push(member);
+        }
+    }
+    Ok(members)
+}
+
+/// Checks whether `account` holds the `AccessControl` role of the contract.
+pub(super) async fn has_role(
+    rpc: &rpc::Client,
+    contract: Address,
+    role: [u8; 32],
+    account: Address,
+) -> Result<bool, Error> {
+    let result = call(
+        rpc,
+        contract,
+        HAS_ROLE_SELECTOR,
+        &[Token::FixedBytes(role.to_vec()), Token::Address(account)],
+        ParamType::Bool,
+    )
+    .await?;
+    Ok(matches!(result, Some(Token::Bool(true))))
+}
+
+async fn proxy_admin(rpc: &rpc::Client, contract: Address) -> Result<Address, Error> {
+    match rpc::api::get_storage_at(rpc, &contract, &H256::from(EIP1967_ADMIN_SLOT))
+        .await
+        .map_err(|e| Error::RpcRequest(e.to_string()))?
+    {
+        Response::Ok(value) => Ok(Address::from(value)),
+        Response::Error(err) => Err(Error::RpcRequest(format!(
+            "eth_getStorageAt failed: {} ({})",
+            err.message, err.code
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::abi;
+    use httpmock::prelude::*;
+    use pretty_assertions::assert_eq;
+    use url::Url;
+
+    fn address(byte: u8) -> Address {
+        Address::from([byte; 20])
+    }
+
+    fn encode(tokens: &[Token]) -> serde_json::Value {
+        format!("0x{}", ethers::utils::hex::encode(abi::encode(tokens))).into()
+    }
+
+    fn mock_call(server: &MockServer, data: String, result: serde_json::Value) {
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .body_contains(format!("\"data\":\"{data}\""));
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(serde_json::json!({"jsonrpc": "2.0", "id": 1, "result": result}));
+        });
+    }
+
+    fn method(name: &str, value: &str) -> blockscout::api::Method {
+        serde_json::from_value(serde_json::json!({
+            "name": name,
+            "outputs": [{"type": "address", "value": value}],
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn resolves_read_methods() {
+        let methods = vec![
+            method("owner", "0x0101010101010101010101010101010101010101"),
+            method("admin", "0x0000000000000000000000000000000000000000"),
+            method("getOwner", "0x0202020202020202020202020202020202020202"),
+        ];
+        let resolve = |resolver: OwnershipResolver| {
+            let methods = &methods;
+            async move {
+                resolver
+                    .resolve(Some(methods), None, address(10))
+                    .await
+                    .unwrap()
+            }
+        };
+
+        assert_eq!(
+            vec![address(1)],
+            resolve(OwnershipResolver::OwnerMethod).await
+        );
+        assert_eq!(
+            vec![address(2)],
+            resolve(OwnershipResolver::GetOwnerMethod).await
+        );
+        // Zero addresses (e.g. renounced ownership) are ignored
+        assert_eq!(
+            Vec::<Address>::new(),
+            resolve(OwnershipResolver::AdminMethod).await
+        );
+        // Resolvers requiring json-rpc find nothing without it
+        assert_eq!(
+            Vec::<Address>::new(),
+            resolve(OwnershipResolver::Eip1967ProxyAdmin).await
+        );
+    }
+
+    #[tokio::test]
+    async fn resolves_unverified_read_methods() {
+        let server = MockServer::start();
+        let rpc = rpc::Client::new(
+            Url::from_str(&server.base_url()).unwrap(),
+            rpc::DEFAULT_RPC_TIMEOUT,
+        );
+        mock_call(
+            &server,
+            "0x8da5cb5b".into(),
+            encode(&[Token::Address(address(1))]),
+        );
+        mock_call(&server, "0x893d20e8".into(), "0x".into());
+
+        let resolve = |resolver: OwnershipResolver| {
+            let rpc = &rpc;
+            async move {
+                resolver
+                    .resolve(None, Some(rpc), address(10))
+                    .await
+                    .unwrap()
+            }
+        };
+        assert_eq!(
+            vec![address(1)],
+            resolve(OwnershipResolver::OwnerMethod).await
+        );
+        // Contracts without the method return no data
+        assert_eq!(
+            Vec::<Address>::new(),
+            resolve(OwnershipResolver::GetOwnerMethod).await
+        );
+    }
+
+    #[tokio::test]
+    async fn resolves_access_control_admins() {
+        let server = MockServer::start();
+        let rpc = rpc::Client::new(
+            Url::from_str(&server.base_url()).unwrap(),
+            rpc::DEFAULT_RPC_TIMEOUT,
+        );
+        let role = Token::FixedBytes(DEFAULT_ADMIN_ROLE.to_vec());
+        let data = |selector: &str, args: &[Token]| {
+            format!(
+                "{selector}{}",
+                ethers::utils::hex::encode(abi::encode(args))
+            )
+        };
+        mock_call(
+            &server,
+            data("0xca15c873", std::slice::from_ref(&role)),
+            encode(&[Token::Uint(2.into())]),
+        );
+        for (index, member) in [address(1), address(2)].into_iter().enumerate() {
+            mock_call(
+                &server,
+                data("0x9010d07c", &[role.clone(), Token::Uint(index.into())]),
+                encode(&[Token::Address(member)]),
+            );
+        }
+
+        let admins = OwnershipResolver::AccessControlAdmin
+            .resolve(Some(&[]), Some(&rpc), address(10))
+            .await
+            .unwrap();
+        assert_eq!(vec![address(1), address(2)], admins);
+
+        mock_call(
+            &server,
+            data("0x91d14854", &[role.clone(), Token::Address(address(3))]),
+            encode(&[Token::Bool(true)]),
+        );
+        mock_call(
+            &server,
+            data("0x91d14854", &[role, Token::Address(address(4))]),
+            encode(&[Token::Bool(false)]),
+        );
+        assert!(has_role(&rpc, address(10), DEFAULT_ADMIN_ROLE, address(3))
+            .await
+            .unwrap());
+        assert!(!has_role(&rpc, address(10), DEFAULT_ADMIN_ROLE, address(4))
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test]
+    async fn resolves_proxy_admin() {
+        let server = MockServer::start();
+        let rpc = rpc::Client::new(
+            Url::from_str(&server.base_url()).unwrap(),
+            rpc::DEFAULT_RPC_TIMEOUT,
+        );
+        server.mock(|when, then| {
+            when.method(POST).path("/").json_body_partial(
+                r#"{
+                    "method": "eth_getStorageAt",
+                    "params": [
+                        "0x0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a",
+                        "0xb53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103",
+                        "latest"
+                    ]
+                }"#,
+            );
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "result": "0x0000000000000000000000000101010101010101010101010101010101010101",
+                }));
+        });
+
+        let admins = OwnershipResolver::Eip1967ProxyAdmin
+            .resolve(Some(&[]), Some(&rpc), address(10))
+            .await
+            .unwrap();
+        assert_eq!(vec![address(1)], admins);
+    }
+}
@@ -0,0 +1,162 @@
+use super::signer::is_contract;
+use crate::{
+    rpc::{self, call},
+    verify::Error,
+};
+use ethers::{
+    abi::{ParamType, Token},
+    types::Address,
+};
+use tracing::instrument;
+
+/// Selector of `getOwners()` of Safe (previously Gnosis Safe) multisig wallets.
+const GET_OWNERS_SELECTOR: [u8; 4] = [0xa0, 0xe6, 0x7e, 0x2b];
+/// Selector of `getThreshold()` of Safe multisig wallets.
+const GET_THRESHOLD_SELECTOR: [u8; 4] = [0xe7, 0x52, 0x35, 0xb8];
+
+/// Owners of a Safe multisig wallet, and the number of owners
+/// required to confirm a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SafeOwners {
+    pub safe: Address,
+    pub owners: Vec<Address>,
+    pub threshold: u64,
+}
+
+/// Returns owners of the Safe wallet at `address`,
+/// or `None` if the address is not a Safe.
+#[instrument(skip(rpc), err, ret, level = "debug")]
+pub async fn safe_owners(rpc: &rpc::Client, address: Address) -> Result<Option<SafeOwners>, Error> {
+    if !is_contract(rpc, address).await? {
+        return Ok(None);
+    }
+    let owners = match call(
+        rpc,
+        address,
+        GET_OWNERS_SELECTOR,
+        &[],
+        ParamType::Array(Box::new(ParamType::Address)),
+    )
+    .await?
+    {
+        Some(Token::Array(owners)) => owners
+            .into_iter()
+            .filter_map(Token::into_address)
+            .collect::<Vec<_>>(),
+        _ => return Ok(None),
+    };
+    let threshold = match call(
+        rpc,
+        address,
+        GET_THRESHOLD_SELECTOR,
+        &[],
+        ParamType::Uint(256),
+    )
+    .await?
+    {
+        Some(Token::Uint(threshold)) => threshold.try_into().unwrap_or(u64::MAX),
+        _ => return Ok(None),
+    };
+    Ok(Some(SafeOwners {
+        safe: address,
+        owners,
+        threshold,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::abi;
+    use httpmock::prelude::*;
+    use pretty_assertions::assert_eq;
+    use std::str::FromStr;
+    use url::Url;
+
+    fn address(byte: u8) -> Address {
+        Address::from([byte; 20])
+    }
+
+    fn encode(tokens: &[Token]) -> String {
+        format!("0x{}", ethers::utils::hex::encode(abi::encode(tokens)))
+    }
+
+    fn mock_call(server: &MockServer, selector: &str, result: serde_json::Value) {
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .body_contains(format!("\"data\":\"{selector}\""));
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(serde_json::json!({"jsonrpc": "2.0", "id": 1, "result": result}));
+        });
+    }
+
+    fn mock_get_code(server: &MockServer, code: &str) {
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .json_body_partial(r#"{"method": "eth_getCode"}"#);
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(serde_json::json!({"jsonrpc": "2.0", "id": 1, "result": code}));
+        });
+    }
+
+    #[tokio::test]
+    async fn fetches_safe_owners() {
+        let server = MockServer::start();
+        let rpc = rpc::Client::new(
+            Url::from_str(&server.base_url()).unwrap(),
+            rpc::DEFAULT_RPC_TIMEOUT,
+        );
+        mock_get_code(&server, "0x6080");
+        mock_call(
+            &server,
+            "0xa0e67e2b",
+            encode(&[Token::Array(vec![
+                Token::Address(address(1)),
+                Token::Address(address(2)),
+                Token::Address(address(3)),
+            ])])
+            .into(),
+        );
+        mock_call(
+            &server,
+            "0xe75235b8",
+            encode(&[Token::Uint(2.into())]).into(),
+        );
+
+        let safe = safe_owners(&rpc, address(10)).await.unwrap();
+        assert_eq!(
+            Some(SafeOwners {
+                safe: address(10),
+                owners: vec![address(1), address(2), address(3)],
+                threshold: 2,
+            }),
+            safe
+        );
+    }
+
+    #[tokio::test]
+    async fn ignores_non_safe_owners() {
+        // EOA
+        let server = MockServer::start();
+        let rpc = rpc::Client::new(
+            Url::from_str(&server.base_url()).unwrap(),
+            rpc::DEFAULT_RPC_TIMEOUT,
+        );
+        mock_get_code(&server, "0x");
+        assert_eq!(None, safe_owners(&rpc, address(10)).await.unwrap());
+
+        // Contract without `getOwners()`
+        let server = MockServer::start();
+        let rpc = rpc::Client::new(
+            Url::from_str(&server.base_url()).unwrap(),
+            rpc::DEFAULT_RPC_TIMEOUT,
+        );
+        mock_get_code(&server, "0x6080");
+        mock_call(&server, "0xa0e67e2b", "0x".into());
+        assert_eq!(None, safe_owners(&rpc, address(10)).await.unwrap());
+    }
+}
@@ -0,0 +1,238 @@
+use crate::{
+    rpc::{self, api::Response},
+    verify::Error,
+};
+use ethers::{
+    abi::{self, Token},
+    types::{Address, Bytes, Signature, H256},
+};
+
+/// Selector of `isValidSignature(bytes32,bytes)`, which is also the magic value
+/// returned by EIP-1271 contracts for valid signatures.
+const EIP1271_MAGIC_VALUE: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
+
+const ECDSA_SIGNATURE_LENGTH: usize = 65;
+
+/// Returns the address which signed the message with the given `hash`.
+///
+/// If `signer` is not specified, it is recovered from the ECDSA signature.
+/// Otherwise, signatures of contract signers (smart contract wallets) are checked
+/// by calling `isValidSignature` of the contract as described in EIP-1271,
+/// and signatures of EOAs are checked to be recovered to the signer.
+pub async fn verify_signer(
+    rpc: Option<&rpc::Client>,
+    signature: &[u8],
+    hash: H256,
+    signer: Option<Address>,
+) -> Result<Address, Error> {
+    let signer = match signer {
+        Some(signer) => signer,
+        None => return Ok(Signature::try_from(signature)?.recover(hash)?),
+    };
+    let rpc = match rpc {
+        Some(rpc) => rpc,
+        // Contract signatures cannot be verified, but the signer can still be an EOA
+        None if signature.len() == ECDSA_SIGNATURE_LENGTH => {
+            Signature::try_from(signature)?.verify(hash, signer)?;
+            return Ok(signer);
+        }
+        None => return Err(Error::ContractSignaturesNotSupported),
+    };
+
+    if !is_contract(rpc, signer).await? {
+        Signature::try_from(signature)?.verify(hash, signer)?;
+        return Ok(signer);
+    }
+    if is_valid_contract_signature(rpc, signer, hash, signature).await? {
+        Ok(signer)
+    } else {
+        Err(Error::InvalidContractSignature(signer))
+    }
+}
+
+pub(super) async fn is_contract(rpc: &rpc::Client, address: Address) -> Result<bool, Error> {
+    match rpc::api::get_code(rpc, &address)
+        .await
+        .map_err(|e| Error::RpcRequest(e.to_string()))?
+    {
+        Response::Ok(code) => Ok(!code.is_empty()),
+        Response::Error(err) => Err(Error::RpcRequest(format!(
+            "eth_getCode failed: {} ({})",
+            err.message, err.code
+        ))),
+    }
+}
+
+async fn is_valid_contract_signature(
+    rpc: &rpc::Client,
+    signer: Address,
+    hash: H256,
+    signature: &[u8],
+) -> Result<bool, Error> {
+    let call_data: Bytes = [
+        EIP1271_MAGIC_VALUE.as_slice(),
+        &abi::encode(&[
+            Token::FixedBytes(hash.as_bytes().to_vec()),
+            Token::Bytes(signature.to_vec()),
+        ]),
+    ]
+    .concat()
+    .into();
+    match rpc::api::call(rpc, &signer, &call_data)
+        .await
+        .map_err(|e| Error::RpcRequest(e.to_string()))?
+    {
+        // `bytes4` is returned left-aligned in a 32-byte word
+        Response::Ok(output) => Ok(output.starts_with(&EIP1271_MAGIC_VALUE)),
+        // Contracts are allowed to revert instead of returning a non-magic value
+        Response::Error(err) => {
+            tracing::debug!(signer = ?signer, err = ?err, "isValidSignature call failed");
+            Ok(false)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::{
+        signers::{LocalWallet, Signer},
+        utils::hash_message,
+    };
+    use httpmock::prelude::*;
+    use pretty_assertions::assert_eq;
+    use std::str::FromStr;
+    use url::Url;
+
+    const DATA: &str = "[BlockScout] [2022-12-30 23:13:59] I, hereby verify that I am the owner/creator of the address [0x0000000000000000000000000000000001020304]";
+
+    fn hash() -> H256 {
+        hash_message(DATA)
+    }
+
+    fn wallet_contract() -> Address {
+        Address::from_str("0x5afe5afe5afe5afe5afe5afe5afe5afe5afe5afe").unwrap()
+    }
+
+    fn rpc_client(server: &MockServer) -> rpc::Client {
+        rpc::Client::new(
+            Url::from_str(&server.base_url()).unwrap(),
+            rpc::DEFAULT_RPC_TIMEOUT,
+        )
+    }
+
+    fn mock_rpc<'a>(
+        server: &'a MockServer,
+        method: &str,
+        response: serde_json::Value,
+    ) -> httpmock::Mock<'a> {
+        let mut body = serde_json::json!({"jsonrpc": "2.0", "id": 1});
+        body.as_object_mut()
+            .unwrap()
+            .extend(response.as_object().unwrap().clone());
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .json_body_partial(serde_json::json!({ "method": method }).to_string());
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(body);
+        })
+    }
+
+    #[tokio::test]
+    async fn recovers_eoa_signer() {
+        let wallet = LocalWallet::new(&mut rand::thread_rng());
+        let signature = wallet.sign_message(DATA).await.unwrap().to_vec();
+
+        let signer = verify_signer(None, &signature, hash(), None).await.unwrap();
+        assert_eq!(wallet.address(), signer);
+        let signer = verify_signer(None, &signature, hash(), Some(wallet.address()))
+            .await
+            .unwrap();
+        assert_eq!(wallet.address(), signer);
+
+        let other = LocalWallet::new(&mut rand::thread_rng());
+        let result = verify_signer(None, &signature, hash(), Some(other.address())).await;
+        assert!(matches!(result, Err(Error::Signature(_))), "{result:?}");
+        let result = verify_signer(None, &signature[..64], hash(), None).await;
+        assert!(matches!(result, Err(Error::Signature(_))), "{result:?}");
+    }
+
+    #[tokio::test]
+    async fn verifies_contract_signature() {
+        let server = MockServer::start();
+        let rpc = rpc_client(&server);
+        let get_code = mock_rpc(
+            &server,
+            "eth_getCode",
+            serde_json::json!({"result": "0x6080"}),
+        );
+        let call = mock_rpc(
+            &server,
+            "eth_call",
+            serde_json::json!({
+                "result": "0x1626ba7e00000000000000000000000000000000000000000000000000000000"
+            }),
+        );
+
+        // Signatures of smart contract wallets are not limited to 65 bytes
+        let signature = vec![1u8; 130];
+        let signer = verify_signer(Some(&rpc), &signature, hash(), Some(wallet_contract()))
+            .await
+            .unwrap();
+        assert_eq!(wallet_contract(), signer);
+        get_code.assert_hits(1);
+        call.assert_hits(1);
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_contract_signature() {
+        let signature = vec![1u8; 130];
+        for response in [
+            serde_json::json!({
+                "result": "0xffffffff00000000000000000000000000000000000000000000000000000000"
+            }),
+            serde_json::json!({"error": {"code": 3, "message": "execution reverted"}}),
+        ] {
+            let server = MockServer::start();
+            let rpc = rpc_client(&server);
+            mock_rpc(
+                &server,
+                "eth_getCode",
+                serde_json::json!({"result": "0x6080"}),
+            );
+            mock_rpc(&server, "eth_call", response);
+
+            let result =
+                verify_signer(Some(&rpc), &signature, hash(), Some(wallet_contract())).await;
+            assert!(
+                matches!(result, Err(Error::InvalidContractSignature(signer)) if signer == wallet_contract()),
+                "{result:?}"
+            );
+        }
+
+        // Without json-rpc endpoint only ECDSA signatures can be checked
+        let result = verify_signer(None, &signature, hash(), Some(wallet_contract())).await;
+        assert!(
+            matches!(result, Err(Error::ContractSignaturesNotSupported)),
+            "{result:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn verifies_eoa_signer_with_rpc() {
+        let wallet = LocalWallet::new(&mut rand::thread_rng());
+        let signature = wallet.sign_message(DATA).await.unwrap().to_vec();
+        let server = MockServer::start();
+        let rpc = rpc_client(&server);
+        mock_rpc(&server, "eth_getCode", serde_json::json!({"result": "0x"}));
+        let call = mock_rpc(&server, "eth_call", serde_json::json!({"result": "0x"}));
+
+        let signer = verify_signer(Some(&rpc), &signature, hash(), Some(wallet.address()))
+            .await
+            .unwrap();
+        assert_eq!(wallet.address(), signer);
+        call.assert_hits(0);
+    }
+}
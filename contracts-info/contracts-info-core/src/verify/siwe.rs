@@ -0,0 +1,283 @@
+//! Sign-In with Ethereum (EIP-4361) verification messages.
+
+use super::{Error, Message, MessageKind};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use ethers::{types::Address, utils::to_checksum};
+use std::{
+    fmt,
+    iter::Peekable,
+    str::{FromStr, Split},
+};
+use url::Url;
+
+const HEADER_SUFFIX: &str = " wants you to sign in with your Ethereum account:";
+const VERSION: &str = "1";
+const NONCE_MIN_LENGTH: usize = 8;
+const SIWE_TS_FORMAT: &str = "%Y-%m-%dT%H:%M:%SZ";
+
+/// Fields of EIP-4361 messages, which are not present in the legacy format.
+/// The domain, the issuance time and the contract address (put into the statement)
+/// are stored in the [`Message`] itself.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Siwe {
+    /// Account which is expected to sign the message.
+    pub signer: Address,
+    pub uri: String,
+    pub chain_id: i64,
+    pub nonce: String,
+    pub expiration_time: Option<NaiveDateTime>,
+    pub not_before: Option<NaiveDateTime>,
+}
+
+impl Siwe {
+    pub(super) fn validate(&self, site: &str, chain_id: i64) -> Result<(), Error> {
+        let uri_host = Url::parse(&self.uri)
+            .map_err(|e| Error::InvalidValue(format!("invalid uri: {e}")))?
+            .host_str()
+            .map(str::to_string);
+        if uri_host.as_deref() != Some(site) {
+            return Err(Error::InvalidValue(format!(
+                "expected uri of {site}, got {}",
+                self.uri
+            )));
+        }
+        if self.chain_id != chain_id {
+            return Err(Error::InvalidValue(format!(
+                "expected chain id {chain_id}, got {}",
+                self.chain_id
+            )));
+        }
+        let now = Utc::now().naive_utc();
+        if self.expiration_time.is_some_and(|time| time <= now) {
+            return Err(Error::Expired);
+        }
+        if self.not_before.is_some_and(|time| time > now) {
+            return Err(Error::InvalidValue("message is not valid yet".into()));
+        }
+        Ok(())
+    }
+}
+
+pub(super) fn is_siwe(value: &str) -> bool {
+    value
+        .split('\n')
+        .next()
+        .is_some_and(|line| line.ends_with(HEADER_SUFFIX))
+}
+
+pub(super) fn format(message: &Message, siwe: &Siwe, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, "{}{HEADER_SUFFIX}", message.site)?;
+    writeln!(f, "{}", to_checksum(&siwe.signer, None))?;
+    writeln!(f)?;
+    writeln!(f, "{} {:#x}", Message::text(), message.address)?;
+    writeln!(f)?;
+    writeln!(f, "URI: {}", siwe.uri)?;
+    writeln!(f, "Version: {VERSION}")?;
+    writeln!(f, "Chain ID: {}", siwe.chain_id)?;
+    writeln!(f, "Nonce: {}", siwe.nonce)?;
+    write!(f, "Issued At: {}", message.timestamp.format(SIWE_TS_FORMAT))?;
+    if let Some(expiration_time) = siwe.expiration_time {
+        write!(
+            f,
+            "\nExpiration Time: {}",
+            expiration_time.format(SIWE_TS_FORMAT)
+        )?;
+    }
+    if let Some(not_before) = siwe.not_before {
+        write!(f, "\nNot Before: {}", not_before.format(SIWE_TS_FORMAT))?;
+    }
+    Ok(())
+}
+
+pub(super) fn parse(value: &str) -> Result<Message, Error> {
+    let mut lines = value.split('\n').peekable();
+    let site = next_line(&mut lines, "domain")?
+        .strip_suffix(HEADER_SUFFIX)
+        .ok_or_else(|| Error::InvalidFormat("invalid header".into()))?
+        .to_string();
+    let signer = parse_address(next_line(&mut lines, "address")?)?;
+    expect_empty_line(&mut lines)?;
+    let statement = next_line(&mut lines, "statement")?;
+    let address = statement
+        .strip_prefix(Message::text())
+        .and_then(|address| address.strip_prefix(' '))
+        .ok_or_else(|| {
+            Error::InvalidValue(format!(
+                "expected statement {} <address>, got {statement}",
+                Message::text()
+            ))
+        })
+        .and_then(parse_address)?;
+    expect_empty_line(&mut lines)?;
+
+    let uri = field(&mut lines, "URI")?.to_string();
+    let version = field(&mut lines, "Version")?;
+    if version != VERSION {
+        return Err(Error::InvalidValue(format!(
+            "expected version {VERSION}, got {version}"
+        )));
+    }
+    let chain_id = field(&mut lines, "Chain ID")?
+        .parse()
+        .map_err(|e| Error::InvalidValue(format!("invalid chain id: {e}")))?;
+    let nonce = field(&mut lines, "Nonce")?;
+    if nonce.len() < NONCE_MIN_LENGTH || !nonce.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(Error::InvalidValue(format!(
+            "nonce must be at least {NONCE_MIN_LENGTH} alphanumeric characters long"
+        )));
+    }
+    let nonce = nonce.to_string();
+    let timestamp = parse_timestamp(field(&mut lines, "Issued At")?)?;
+    let expiration_time = optional_field(&mut lines, "Expiration Time")
+        .map(parse_timestamp)
+        .transpose()?;
+    let not_before = optional_field(&mut lines, "Not Before")
+        .map(parse_timestamp)
+        .transpose()?;
+    if let Some(line) = lines.next() {
+        return Err(Error::InvalidFormat(format!("unexpected line: {line}")));
+    }
+
+    Ok(Message {
+        site,
+        timestamp,
+        address,
+        kind: MessageKind::Siwe(Siwe {
+            signer,
+            uri,
+            chain_id,
+            nonce,
+            expiration_time,
+            not_before,
+        }),
+    })
+}
+
+type Lines<'a> = Peekable<Split<'a, char>>;
+
+fn next_line<'a>(lines: &mut Lines<'a>, name: &str) -> Result<&'a str, Error> {
+    lines
+        .next()
+        .ok_or_else(|| Error::InvalidFormat(format!("{name} is missing")))
+}
+
+fn expect_empty_line(lines: &mut Lines<'_>) -> Result<(), Error> {
+    match lines.next() {
+        Some("") => Ok(()),
+        _ => Err(Error::InvalidFormat("expected empty line".into())),
+    }
+}
+
+fn field<'a>(lines: &mut Lines<'a>, key: &str) -> Result<&'a str, Error> {
+    optional_field(lines, key).ok_or_else(|| Error::InvalidFormat(format!("{key} is missing")))
+}
+
+fn optional_field<'a>(lines: &mut Lines<'a>, key: &str) -> Option<&'a str> {
+    let value = lines
+        .peek()?
+        .strip_prefix(key)
+        .and_then(|line| line.strip_prefix(": "))?;
+    lines.next();
+    Some(value)
+}
+
+fn parse_address(value: &str) -> Result<Address, Error> {
+    Address::from_str(value).map_err(|e| Error::InvalidValue(format!("invalid address: {e}")))
+}
+
+fn parse_timestamp(value: &str) -> Result<NaiveDateTime, Error> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|timestamp| timestamp.naive_utc())
+        .map_err(|e| Error::InvalidValue(format!("invalid timestamp {value}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const DATA: &str = "blockscout.com wants you to sign in with your Ethereum account:
+0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed
+
+I, hereby verify that I am the owner/creator of the address 0x0000000000000000000000000000000001020304
+
+URI: https://blockscout.com/
+Version: 1
+Chain ID: 5
+Nonce: 32891756a1b2c3d4e
+Issued At: 2022-12-30T23:13:59Z
+Expiration Time: 2022-12-31T23:13:59Z";
+
+    fn timestamp(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    fn message() -> Message {
+        Message {
+            site: "blockscout.com".into(),
+            timestamp: timestamp("2022-12-30 23:13:59"),
+            address: Address::from_str("0x0000000000000000000000000000000001020304").unwrap(),
+            kind: MessageKind::Siwe(Siwe {
+                signer: Address::from_str("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap(),
+                uri: "https://blockscout.com/".into(),
+                chain_id: 5,
+                nonce: "32891756a1b2c3d4e".into(),
+                expiration_time: Some(timestamp("2022-12-31 23:13:59")),
+                not_before: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn parse_and_format() {
+        assert!(is_siwe(DATA));
+        let message = Message::from_str(DATA).unwrap();
+        assert_eq!(self::message(), message);
+        assert_eq!(DATA, message.to_string());
+
+        // Timestamps with offsets are converted to UTC
+        let data = DATA.replace("2022-12-30T23:13:59Z", "2022-12-31T01:13:59+02:00");
+        assert_eq!(self::message(), Message::from_str(&data).unwrap());
+    }
+
+    #[test]
+    fn parse_errors() {
+        for data in [
+            DATA.replace("Version: 1", "Version: 2"),
+            DATA.replace("Nonce: 32891756a1b2c3d4e", "Nonce: 123"),
+            DATA.replace("the address 0x", "the address 1x"),
+            DATA.replace("Chain ID: 5", "Chain: 5"),
+            DATA.replace("\n\nURI", "\nURI"),
+            format!("{DATA}\nRequest ID: 1"),
+        ] {
+            Message::from_str(&data).unwrap_err();
+        }
+    }
+
+    fn siwe() -> Siwe {
+        match message().kind {
+            MessageKind::Siwe(siwe) => siwe,
+            kind => panic!("unexpected message kind: {kind:?}"),
+        }
+    }
+
+    #[test]
+    fn validate() {
+        let mut siwe = siwe();
+        siwe.expiration_time = None;
+        siwe.validate("blockscout.com", 5).unwrap();
+
+        let result = siwe.validate("eth.blockscout.com", 5);
+        assert!(matches!(result, Err(Error::InvalidValue(_))), "{result:?}");
+        let result = siwe.validate("blockscout.com", 1);
+        assert!(matches!(result, Err(Error::InvalidValue(_))), "{result:?}");
+
+        siwe.not_before = Some(Utc::now().naive_utc() + chrono::Duration::hours(1));
+        let result = siwe.validate("blockscout.com", 5);
+        assert!(matches!(result, Err(Error::InvalidValue(_))), "{result:?}");
+
+        let siwe = self::siwe();
+        let result = siwe.validate("blockscout.com", 5);
+        assert!(matches!(result, Err(Error::Expired)), "{result:?}");
+    }
+}
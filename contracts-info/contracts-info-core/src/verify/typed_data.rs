@@ -0,0 +1,201 @@
+//! EIP-712 typed data verification messages.
+
+use super::{Error, Message, MessageKind};
+use chrono::NaiveDateTime;
+use ethers::types::{
+    transaction::eip712::{Eip712, TypedData},
+    Address, H256,
+};
+use std::{fmt, str::FromStr};
+
+const PRIMARY_TYPE: &str = "AddressOwnershipClaim";
+const DOMAIN_VERSION: &str = "1";
+
+/// Fields of EIP-712 messages, which are not present in the legacy format.
+/// The site, the issuance time and the contract address are stored in the [`Message`] itself.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TypedClaim {
+    pub chain_id: i64,
+    pub nonce: String,
+}
+
+pub(super) fn is_typed_data(value: &str) -> bool {
+    value.trim_start().starts_with('{')
+}
+
+/// Typed data to be signed with `eth_signTypedData_v4`. The domain is bound
+/// to the Blockscout host and the chain id, so that the signature cannot be
+/// reused at other instances.
+fn to_json(message: &Message, claim: &TypedClaim) -> serde_json::Value {
+    serde_json::json!({
+        "types": {
+            "EIP712Domain": [
+                { "name": "name", "type": "string" },
+                { "name": "version", "type": "string" },
+                { "name": "chainId", "type": "uint256" },
+            ],
+            PRIMARY_TYPE: [
+                { "name": "contract", "type": "address" },
+                { "name": "site", "type": "string" },
+                { "name": "issuedAt", "type": "uint256" },
+                { "name": "nonce", "type": "string" },
+            ],
+        },
+        "primaryType": PRIMARY_TYPE,
+        "domain": {
+            "name": message.site,
+            "version": DOMAIN_VERSION,
+            "chainId": claim.chain_id,
+        },
+        "message": {
+            "contract": format!("{:#x}", message.address),
+            "site": message.site,
+            "issuedAt": message.timestamp.timestamp(),
+            "nonce": claim.nonce,
+        },
+    })
+}
+
+pub(super) fn format(
+    message: &Message,
+    claim: &TypedClaim,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    write!(f, "{}", to_json(message, claim))
+}
+
+fn to_typed_data(message: &Message, claim: &TypedClaim) -> Result<TypedData, Error> {
+    serde_json::from_value(to_json(message, claim)).map_err(|e| Error::InvalidFormat(e.to_string()))
+}
+
+/// Returns the hash signed by the wallet, as defined in EIP-712.
+pub(super) fn hash(message: &Message, claim: &TypedClaim) -> Result<H256, Error> {
+    to_typed_data(message, claim)?
+        .encode_eip712()
+        .map(H256::from)
+        .map_err(|e| Error::InvalidValue(format!("typed data encoding failed: {e}")))
+}
+
+pub(super) fn parse(value: &str) -> Result<Message, Error> {
+    let typed_data: TypedData =
+        serde_json::from_str(value).map_err(|e| Error::InvalidFormat(e.to_string()))?;
+    if typed_data.primary_type != PRIMARY_TYPE {
+        return Err(Error::InvalidValue(format!(
+            "expected primary type {PRIMARY_TYPE}, got {}",
+            typed_data.primary_type
+        )));
+    }
+
+    let field = |name: &str| {
+        typed_data
+            .message
+            .get(name)
+            .ok_or_else(|| Error::InvalidFormat(format!("{name} is missing")))
+    };
+    let string_field = |name: &str| {
+        field(name)?
+            .as_str()
+            .ok_or_else(|| Error::InvalidValue(format!("{name} must be a string")))
+    };
+    let site = string_field("site")?.to_string();
+    let address = Address::from_str(string_field("contract")?)
+        .map_err(|e| Error::InvalidValue(format!("invalid contract: {e}")))?;
+    let issued_at = field("issuedAt")?;
+    let timestamp = issued_at
+        .as_i64()
+        .or_else(|| issued_at.as_str().and_then(|value| value.parse().ok()))
+        .and_then(|timestamp| NaiveDateTime::from_timestamp_opt(timestamp, 0))
+        .ok_or_else(|| Error::InvalidValue("invalid issuedAt".into()))?;
+    let nonce = string_field("nonce")?.to_string();
+    let chain_id = typed_data
+        .domain
+        .chain_id
+        .and_then(|chain_id| u64::try_from(chain_id).ok())
+        .and_then(|chain_id| i64::try_from(chain_id).ok())
+        .ok_or_else(|| Error::InvalidValue("invalid domain chain id".into()))?;
+
+    let claim = TypedClaim { chain_id, nonce };
+    let message = Message {
+        site,
+        timestamp,
+        address,
+        kind: MessageKind::TypedData(claim.clone()),
+    };
+    // Types and domain must be exactly the ones the message has been issued with,
+    // so that the signed hash could be rebuilt from the message fields
+    let expected = to_typed_data(&message, &claim)?;
+    if definition(&typed_data) != definition(&expected) {
+        return Err(Error::InvalidValue(format!(
+            "typed data does not match the {PRIMARY_TYPE} definition"
+        )));
+    }
+    Ok(message)
+}
+
+fn definition(typed_data: &TypedData) -> Option<serde_json::Value> {
+    Some(serde_json::json!({
+        "types": serde_json::to_value(&typed_data.types).ok()?,
+        "domain": serde_json::to_value(&typed_data.domain).ok()?,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::{
+        signers::{LocalWallet, Signer},
+        types::Signature,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn message() -> Message {
+        Message {
+            site: "blockscout.com".into(),
+            timestamp: NaiveDateTime::from_timestamp_opt(1672441439, 0).unwrap(),
+            address: Address::from_str("0x0000000000000000000000000000000001020304").unwrap(),
+            kind: MessageKind::TypedData(TypedClaim {
+                chain_id: 5,
+                nonce: "32891756a1b2c3d4e".into(),
+            }),
+        }
+    }
+
+    #[test]
+    fn parse_and_format() {
+        let data = message().to_string();
+        assert!(is_typed_data(&data));
+        assert_eq!(message(), Message::from_str(&data).unwrap());
+
+        // Numbers may be passed as strings
+        let data = data.replace("1672441439", "\"1672441439\"");
+        assert_eq!(message(), Message::from_str(&data).unwrap());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let data = message().to_string();
+        for data in [
+            data.replace("AddressOwnershipClaim", "Claim"),
+            data.replace(
+                "\"name\":\"nonce\",\"type\":\"string\"",
+                "\"name\":\"nonce\",\"type\":\"bytes32\"",
+            ),
+            data.replace("\"version\":\"1\"", "\"version\":\"2\""),
+            data.replace("\"name\":\"blockscout.com\"", "\"name\":\"etherscan.io\""),
+            data.replace("\"nonce\":\"32891756a1b2c3d4e\"", "\"nonce\":1"),
+            "{}".into(),
+        ] {
+            Message::from_str(&data).unwrap_err();
+        }
+    }
+
+    #[tokio::test]
+    async fn hash_matches_wallet_signature() {
+        let wallet = LocalWallet::new(&mut rand::thread_rng());
+        let typed_data: TypedData = serde_json::from_str(&message().to_string()).unwrap();
+        let signature: Signature = wallet.sign_typed_data(&typed_data).await.unwrap();
+
+        let hash = message().signing_hash(&message().to_string()).unwrap();
+        assert_eq!(wallet.address(), signature.recover(hash).unwrap());
+    }
+}
@@ -1,4 +1,9 @@
-use crate::{blockscout, verify::Error};
+use super::{
+    chain::{find_ownership_chain, ChainLink},
+    resolvers::{has_role, OwnershipResolver, DEFAULT_ADMIN_ROLE, DEFAULT_OWNERSHIP_RESOLVERS},
+    safe::{safe_owners, SafeOwners},
+};
+use crate::{blockscout, rpc, verify::Error};
 use ethers::types::Address;
 use futures::{
     future,
@@ -9,6 +14,7 @@ use std::{collections::BTreeSet, str::FromStr};
 use tracing::instrument;
 
 lazy_static! {
+    /// Deployer factories used if none are configured for the chain.
     pub static ref SINGLETON_FACTORY_ADDRESSES: Vec<Address> = vec![
         // https://eips.ethereum.org/EIPS/eip-2470
         Address::from_str("0xce0042B868300000d44A59004Da54A005ffdcf9f").unwrap(),
@@ -17,41 +23,123 @@ lazy_static! {
     ];
 }
 
-#[derive(Debug, Clone)]
+/// Rules defining who is allowed to verify the contract ownership.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OwnershipConfig {
+    /// Sources of accounts allowed to verify contracts besides their creators.
+    pub resolvers: Vec<OwnershipResolver>,
+    /// Factories deploying contracts on behalf of the sender of the creation
+    /// transaction, who is considered to be the creator instead.
+    pub deployer_factories: Vec<Address>,
+    /// If set, the sender of the creation transaction is considered to be the creator
+    /// of any contract created by another contract, not only by the deployer factories.
+    pub resolve_contract_creators: bool,
+    /// How many contracts may be between the contract owner and the signer,
+    /// e.g. a timelock owning the contract and a multisig being its proposer.
+    /// Following the ownership chains requires the json-rpc endpoint of the chain.
+    pub max_chain_depth: usize,
+}
+
+impl Default for OwnershipConfig {
+    fn default() -> Self {
+        Self {
+            resolvers: DEFAULT_OWNERSHIP_RESOLVERS.to_vec(),
+            deployer_factories: SINGLETON_FACTORY_ADDRESSES.clone(),
+            resolve_contract_creators: false,
+            max_chain_depth: 0,
+        }
+    }
+}
+
+/// Rule the contract creator has been determined by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CreatorResolution {
+    /// The creator has created the contract itself.
+    Sender,
+    /// The contract has been deployed through the deployer factory,
+    /// so the sender of the creation transaction is the creator.
+    DeployerFactory(Address),
+    /// The contract has been created by another contract
+    /// and the sender of the creation transaction is the creator.
+    ContractDeployer(Address),
+    /// The address is an externally owned account,
+    /// which is considered to be created by itself.
+    ExternallyOwned,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum OwnershipType {
     Creator,
+    /// The verified address is an externally owned account, which has signed as itself.
+    SelfOwner,
     Owner,
+    /// Result of the `getOwner()` read method.
+    GetOwner,
+    /// Result of the `admin()` read method.
+    Admin,
+    /// Holder of `DEFAULT_ADMIN_ROLE` of an `AccessControl` contract.
+    AccessControlAdmin,
+    /// Admin of the EIP-1967 upgradeable proxy.
+    ProxyAdmin,
+    /// One of the owners of the Safe multisig wallet, which is the contract owner.
+    SafeOwner(SafeOwners),
+    /// Controls the contract owner through other contracts. The chain starts
+    /// with the owner found by the resolvers and ends with the signer.
+    ChainOwner(Vec<ChainLink>),
+}
+
+/// Account found by one of the ownership resolvers.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ResolvedOwner {
+    pub address: Address,
+    pub ownership_type: OwnershipType,
 }
 
 #[derive(Debug)]
 pub struct OwnershipOptions {
     pub creator: Address,
+    pub creator_resolution: CreatorResolution,
+    /// Result of the `owner()` read method, if it is among the resolvers.
     pub owner: Option<Address>,
+    /// Set if the contract owner is a Safe multisig wallet.
+    pub safe: Option<SafeOwners>,
+    /// Accounts found by the resolvers, in the order the resolvers are configured.
+    pub resolved: Vec<ResolvedOwner>,
 }
 
 impl OwnershipOptions {
     fn get(&self, address: Address) -> Option<OwnershipType> {
         if address == self.creator {
-            Some(OwnershipType::Creator)
-        } else {
-            self.owner
-                .filter(|owner| &address == owner)
-                .map(|_| OwnershipType::Owner)
+            return match self.creator_resolution {
+                CreatorResolution::ExternallyOwned => Some(OwnershipType::SelfOwner),
+                _ => Some(OwnershipType::Creator),
+            };
+        }
+        if let Some(resolved) = self.resolved.iter().find(|owner| owner.address == address) {
+            return Some(resolved.ownership_type.clone());
         }
+        self.safe
+            .as_ref()
+            .filter(|safe| safe.owners.contains(&address))
+            .map(|safe| OwnershipType::SafeOwner(safe.clone()))
     }
 
     fn to_vec(&self) -> Vec<Address> {
         let mut options = BTreeSet::new();
         options.insert(self.creator);
-        if let Some(owner) = self.owner {
-            options.insert(owner);
+        options.extend(self.resolved.iter().map(|owner| owner.address));
+        if let Some(safe) = &self.safe {
+            options.extend(safe.owners.iter().copied());
         }
         options.into_iter().collect()
     }
 }
+
 #[instrument(skip_all, err, ret, level = "debug")]
 pub async fn ownership_options(
     client: &blockscout::Client,
+    rpc: Option<&rpc::Client>,
+    config: &OwnershipConfig,
     contract: Address,
 ) -> Result<OwnershipOptions, Error> {
     let address = blockscout::api::address(client, &contract)
@@ -60,10 +148,19 @@ pub async fn ownership_options(
         .and_then(|address| map_blockscout_response(address, contract))?;
 
     if !address.is_contract {
-        return Err(Error::ContractNotFound(contract));
+        // Externally owned accounts can only be verified by signing as themselves
+        return Ok(OwnershipOptions {
+            creator: contract,
+            creator_resolution: CreatorResolution::ExternallyOwned,
+            owner: None,
+            safe: None,
+            resolved: vec![],
+        });
     }
 
-    if !address.is_verified {
+    // Read methods of contracts without verified sources are not available
+    // through blockscout, so they have to be called through json-rpc
+    if !address.is_verified && rpc.is_none() {
         return Err(Error::ContractNotVerified(contract));
     }
 
@@ -74,42 +171,82 @@ pub async fn ownership_options(
         }
     };
 
-    let creator = if SINGLETON_FACTORY_ADDRESSES.contains(&msg_sender) {
-        let tx_hash = address.creation_tx_hash.ok_or_else(|| {
-            Error::BlockscoutRequest(format!(
-                "contract {contract:#x} has no creation_tx_hash field"
-            ))
-        })?;
-        let transaction = blockscout::api::transaction(client, &tx_hash)
-            .await
-            .map_err(|e| Error::BlockscoutRequest(e.to_string()))?;
-        // tx.origin
-        transaction.from.hash
-    } else {
-        msg_sender
-    };
+    let (creator, creator_resolution) =
+        resolve_creator(client, config, &address, msg_sender, contract).await?;
 
-    let owner = if address.is_contract {
+    let resolvers = &config.resolvers;
+    let methods = if !address.is_verified {
+        None
+    } else if resolvers.iter().any(|r| r.method().is_some()) {
         let try_fetch_proxy = address.implementation_address.is_some();
-        let methods = get_all_contract_methods(client, &contract, try_fetch_proxy).await?;
-        let maybe_owner = methods
-            .into_iter()
-            .find(|method| method.name == "owner")
-            .and_then(|owner_method| {
-                owner_method
-                    .outputs
-                    .into_iter()
-                    .find(|output| output._type == "address")
-            })
-            .and_then(|output| output.value.as_str().map(Address::from_str))
-            .transpose()
-            .map_err(|_| Error::BlockscoutRequest("owner() address field is invalid".into()))?;
-        maybe_owner.and_then(|owner| (!owner.is_zero()).then_some(owner))
+        Some(get_all_contract_methods(client, &contract, try_fetch_proxy).await?)
     } else {
-        None
+        Some(vec![])
     };
+    let mut resolved = Vec::new();
+    for resolver in resolvers {
+        let addresses = resolver.resolve(methods.as_deref(), rpc, contract).await?;
+        resolved.extend(addresses.into_iter().map(|address| ResolvedOwner {
+            address,
+            ownership_type: resolver.ownership_type(),
+        }));
+    }
+    let owner = resolved
+        .iter()
+        .find(|owner| owner.ownership_type == OwnershipType::Owner)
+        .map(|owner| owner.address);
 
-    Ok(OwnershipOptions { creator, owner })
+    // Nobody can sign for the Safe itself, so its owners are allowed to sign instead
+    let safe = match (rpc, owner) {
+        (Some(rpc), Some(owner)) => safe_owners(rpc, owner).await?,
+        _ => None,
+    };
+
+    Ok(OwnershipOptions {
+        creator,
+        creator_resolution,
+        owner,
+        safe,
+        resolved,
+    })
+}
+
+/// Returns the account considered to be the creator of the contract
+/// created by `msg_sender`, and the rule it has been determined by.
+async fn resolve_creator(
+    client: &blockscout::Client,
+    config: &OwnershipConfig,
+    address: &blockscout::api::Address,
+    msg_sender: Address,
+    contract: Address,
+) -> Result<(Address, CreatorResolution), Error> {
+    let is_factory = config.deployer_factories.contains(&msg_sender);
+    if !is_factory && !config.resolve_contract_creators {
+        return Ok((msg_sender, CreatorResolution::Sender));
+    }
+    let tx_hash = match address.creation_tx_hash {
+        Some(tx_hash) => tx_hash,
+        None if is_factory => {
+            return Err(Error::BlockscoutRequest(format!(
+                "contract {contract:#x} has no creation_tx_hash field"
+            )))
+        }
+        None => return Ok((msg_sender, CreatorResolution::Sender)),
+    };
+    let transaction = blockscout::api::transaction(client, &tx_hash)
+        .await
+        .map_err(|e| Error::BlockscoutRequest(e.to_string()))?;
+    // tx.origin
+    let origin = transaction.from.hash;
+    let resolution = if is_factory {
+        CreatorResolution::DeployerFactory(msg_sender)
+    } else if origin != msg_sender {
+        // Only contracts can create contracts in transactions sent by other accounts
+        CreatorResolution::ContractDeployer(msg_sender)
+    } else {
+        CreatorResolution::Sender
+    };
+    Ok((origin, resolution))
 }
 
 async fn get_all_contract_methods(
@@ -153,16 +290,187 @@ fn map_blockscout_response<T>(
 #[instrument(skip_all, err, ret, level = "debug")]
 pub async fn try_validate_ownership(
     client: &blockscout::Client,
+    rpc: Option<&rpc::Client>,
+    config: &OwnershipConfig,
     owner: Address,
     contract: Address,
 ) -> Result<OwnershipType, Error> {
-    let options = ownership_options(client, contract).await?;
-    match options.get(owner) {
-        Some(ownership_type) => Ok(ownership_type),
-        None => Err(Error::WrongOwner {
-            contract,
-            suggested_owner: owner,
-            possible_owners: options.to_vec(),
-        }),
+    let options = ownership_options(client, rpc, config, contract).await?;
+    if let Some(ownership_type) = options.get(owner) {
+        return Ok(ownership_type);
+    }
+    let is_contract = options.creator_resolution != CreatorResolution::ExternallyOwned;
+    // Admins of contracts which cannot enumerate them are checked individually
+    let access_control = is_contract
+        && config
+            .resolvers
+            .contains(&OwnershipResolver::AccessControlAdmin);
+    if let Some(rpc) = rpc.filter(|_| access_control) {
+        if has_role(rpc, contract, DEFAULT_ADMIN_ROLE, owner).await? {
+            return Ok(OwnershipType::AccessControlAdmin);
+        }
+    }
+    if let Some(rpc) = rpc.filter(|_| is_contract && config.max_chain_depth > 0) {
+        let chain =
+            find_ownership_chain(rpc, &options.resolved, owner, config.max_chain_depth).await?;
+        if let Some(chain) = chain {
+            return Ok(OwnershipType::ChainOwner(chain));
+        }
+    }
+    Err(Error::WrongOwner {
+        contract,
+        suggested_owner: owner,
+        possible_owners: options.to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use url::Url;
+
+    const CONTRACT: &str = "0x0000000000000000000000000000000001020304";
+    const CREATION_TX_HASH: &str =
+        "0x1234567812345678123456781234567812345678123456781234567812345678";
+
+    fn address(byte: u8) -> Address {
+        Address::from([byte; 20])
+    }
+
+    #[rstest]
+    #[case::sender(vec![], false, address(1), (address(1), CreatorResolution::Sender))]
+    #[case::default_factory(
+        vec![],
+        false,
+        SINGLETON_FACTORY_ADDRESSES[0],
+        (address(2), CreatorResolution::DeployerFactory(SINGLETON_FACTORY_ADDRESSES[0]))
+    )]
+    #[case::configured_factory(
+        vec![address(1)],
+        false,
+        address(1),
+        (address(2), CreatorResolution::DeployerFactory(address(1)))
+    )]
+    #[case::contract_deployer(
+        vec![],
+        true,
+        address(1),
+        (address(2), CreatorResolution::ContractDeployer(address(1)))
+    )]
+    #[case::eoa_deployer(vec![], true, address(2), (address(2), CreatorResolution::Sender))]
+    #[tokio::test]
+    async fn resolves_creator(
+        #[case] extra_factories: Vec<Address>,
+        #[case] resolve_contract_creators: bool,
+        #[case] msg_sender: Address,
+        #[case] expected: (Address, CreatorResolution),
+    ) {
+        let blockscout_server = MockServer::start();
+        blockscout_server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/api/v2/addresses/{CONTRACT}"));
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(serde_json::json!({
+                    "creator_address_hash": msg_sender,
+                    "is_contract": true,
+                    "is_verified": true,
+                    "creation_tx_hash": CREATION_TX_HASH,
+                }));
+        });
+        blockscout_server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/api/v2/smart-contracts/{CONTRACT}/methods-read"));
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(serde_json::json!([]));
+        });
+        blockscout_server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/api/v2/transactions/{CREATION_TX_HASH}"));
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(serde_json::json!({"from": {"hash": address(2)}}));
+        });
+        let client = blockscout::Client::new(Url::from_str(&blockscout_server.base_url()).unwrap());
+        let mut config = OwnershipConfig {
+            resolve_contract_creators,
+            ..Default::default()
+        };
+        config.deployer_factories.extend(extra_factories);
+
+        let options =
+            ownership_options(&client, None, &config, Address::from_str(CONTRACT).unwrap())
+                .await
+                .unwrap();
+        assert_eq!(expected, (options.creator, options.creator_resolution));
+    }
+
+    #[tokio::test]
+    async fn resolves_unverified_contract_owner() {
+        let blockscout_server = MockServer::start();
+        blockscout_server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/api/v2/addresses/{CONTRACT}"));
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(serde_json::json!({
+                    "creator_address_hash": address(1),
+                    "is_contract": true,
+                    "is_verified": false,
+                }));
+        });
+        let methods_handler = blockscout_server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/api/v2/smart-contracts/{CONTRACT}/methods-read"));
+            then.status(404);
+        });
+        let client = blockscout::Client::new(Url::from_str(&blockscout_server.base_url()).unwrap());
+        let contract = Address::from_str(CONTRACT).unwrap();
+        let config = OwnershipConfig::default();
+
+        // Source code is required to find the owner without json-rpc
+        let err = ownership_options(&client, None, &config, contract)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::ContractNotVerified(c) if c == contract));
+
+        let rpc_server = MockServer::start();
+        rpc_server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .body_contains(format!("\"to\":\"{CONTRACT}\""))
+                .body_contains("\"data\":\"0x8da5cb5b\"");
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "result": format!("0x{}", ethers::utils::hex::encode(ethers::types::H256::from(address(2)))),
+                }));
+        });
+        rpc_server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .json_body_partial(r#"{"method": "eth_getCode"}"#);
+            then.status(200)
+                .header("content-type", "application/json")
+                .json_body(serde_json::json!({"jsonrpc": "2.0", "id": 1, "result": "0x"}));
+        });
+        let rpc = rpc::Client::new(
+            Url::from_str(&rpc_server.base_url()).unwrap(),
+            rpc::DEFAULT_RPC_TIMEOUT,
+        );
+
+        let options = ownership_options(&client, Some(&rpc), &config, contract)
+            .await
+            .unwrap();
+        assert_eq!(Some(address(2)), options.owner);
+        assert_eq!(Some(OwnershipType::Owner), options.get(address(2)));
+        assert_eq!(Some(OwnershipType::Creator), options.get(address(1)));
+        methods_handler.assert_hits(0);
     }
 }
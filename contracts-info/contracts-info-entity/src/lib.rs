@@ -2,5 +2,8 @@
 
 pub mod prelude;
 
+pub mod idempotency_keys;
+pub mod safe_confirmations;
 pub mod token_infos;
+pub mod verification_nonces;
 pub mod verified_addresses;
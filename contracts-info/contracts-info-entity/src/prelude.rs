@@ -1,5 +1,7 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.11.2
 
 pub use super::{
-    token_infos::Entity as TokenInfos, verified_addresses::Entity as VerifiedAddresses,
+    idempotency_keys::Entity as IdempotencyKeys, safe_confirmations::Entity as SafeConfirmations,
+    token_infos::Entity as TokenInfos, verification_nonces::Entity as VerificationNonces,
+    verified_addresses::Entity as VerifiedAddresses,
 };
@@ -34,6 +34,8 @@ pub struct Model {
     pub is_user_submitted: bool,
     pub token_name: Option<String>,
     pub token_symbol: Option<String>,
+    #[sea_orm(column_type = "JsonBinary")]
+    pub localized_descriptions: Json,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -14,6 +14,9 @@ pub struct Model {
     pub verified_manually: bool,
     pub token_name: Option<String>,
     pub token_symbol: Option<String>,
+    pub owner_id: Option<String>,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub ownership_chain: Option<Json>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
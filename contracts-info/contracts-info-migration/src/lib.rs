@@ -7,6 +7,12 @@ mod m20230412_110103_add_token_symbol;
 mod m20230510_091802_add_is_user_submitted;
 mod m20230922_130122_token_infos_add_token_name;
 mod m20230922_130123_token_infos_add_token_symbol;
+mod m20231018_101500_verified_addresses_add_owner_id;
+mod m20231028_090000_create_table_idempotency_keys;
+mod m20231030_100000_token_infos_add_localized_descriptions;
+mod m20231101_090000_create_table_safe_confirmations;
+mod m20231103_120000_create_table_verification_nonces;
+mod m20231105_100000_verified_addresses_add_ownership_chain;
 
 pub struct Migrator;
 
@@ -21,6 +27,12 @@ impl MigratorTrait for Migrator {
             Box::new(m20230510_091802_add_is_user_submitted::Migration),
             Box::new(m20230922_130122_token_infos_add_token_name::Migration),
             Box::new(m20230922_130123_token_infos_add_token_symbol::Migration),
+            Box::new(m20231018_101500_verified_addresses_add_owner_id::Migration),
+            Box::new(m20231028_090000_create_table_idempotency_keys::Migration),
+            Box::new(m20231030_100000_token_infos_add_localized_descriptions::Migration),
+            Box::new(m20231101_090000_create_table_safe_confirmations::Migration),
+            Box::new(m20231103_120000_create_table_verification_nonces::Migration),
+            Box::new(m20231105_100000_verified_addresses_add_ownership_chain::Migration),
         ]
     }
 }
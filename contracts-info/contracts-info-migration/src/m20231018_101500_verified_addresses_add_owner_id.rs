@@ -0,0 +1,59 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds the Blockscout user id of the owner. Existing rows keep `NULL` there:
+/// the id is not known to the database, so they are backfilled
+/// when the owner is authenticated next time (see `handlers::reconcile_user`).
+/// Addresses of owners who do not sign in anymore are backfilled with
+/// the `BackfillUserIdsAdmin` rpc (see `handlers::backfill_user_ids`).
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                sea_query::Table::alter()
+                    .table(VerifiedAddresses::Table)
+                    .add_column(ColumnDef::new(VerifiedAddresses::OwnerId).string())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("verified_addresses_owner_id_chain_id_index")
+                    .table(VerifiedAddresses::Table)
+                    .col(VerifiedAddresses::OwnerId)
+                    .col(VerifiedAddresses::ChainId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("verified_addresses_owner_id_chain_id_index")
+                    .table(VerifiedAddresses::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                sea_query::Table::alter()
+                    .table(VerifiedAddresses::Table)
+                    .drop_column(VerifiedAddresses::OwnerId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum VerifiedAddresses {
+    Table,
+    OwnerId,
+    ChainId,
+}
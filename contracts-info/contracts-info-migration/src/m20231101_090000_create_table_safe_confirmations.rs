@@ -0,0 +1,83 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SafeConfirmations::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SafeConfirmations::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SafeConfirmations::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(SimpleExpr::Custom("CURRENT_TIMESTAMP".into())),
+                    )
+                    .col(
+                        ColumnDef::new(SafeConfirmations::ChainId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SafeConfirmations::Address)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SafeConfirmations::SafeAddress)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SafeConfirmations::Signer)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(SafeConfirmations::OwnerId)
+                            .string()
+                            .not_null(),
+                    )
+                    .index(
+                        Index::create()
+                            .unique()
+                            .name("safe_confirmations_chain_id_address_owner_id_signer_index")
+                            .col(SafeConfirmations::ChainId)
+                            .col(SafeConfirmations::Address)
+                            .col(SafeConfirmations::OwnerId)
+                            .col(SafeConfirmations::Signer),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SafeConfirmations::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum SafeConfirmations {
+    Table,
+    Id,
+    CreatedAt,
+    ChainId,
+    Address,
+    SafeAddress,
+    Signer,
+    OwnerId,
+}
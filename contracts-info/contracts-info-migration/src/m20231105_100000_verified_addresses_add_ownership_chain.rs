@@ -0,0 +1,37 @@
+use sea_orm_migration::prelude::*;
+
+/// Adds the chain of contracts through which the owner controls the verified
+/// contract, kept for review. It is `NULL` if the owner controls the contract directly.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                sea_query::Table::alter()
+                    .table(VerifiedAddresses::Table)
+                    .add_column(ColumnDef::new(VerifiedAddresses::OwnershipChain).json_binary())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                sea_query::Table::alter()
+                    .table(VerifiedAddresses::Table)
+                    .drop_column(VerifiedAddresses::OwnershipChain)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(Iden)]
+enum VerifiedAddresses {
+    Table,
+    OwnershipChain,
+}
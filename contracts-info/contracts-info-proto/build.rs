@@ -8,9 +8,12 @@ fn compile(
     includes: &[impl AsRef<Path>],
     generator: Box<dyn ServiceGenerator>,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR")?);
     let mut config = Config::new();
     config
         .service_generator(generator)
+        // Used by the gRPC server reflection service
+        .file_descriptor_set_path(out_dir.join("contracts_info_descriptor.bin"))
         .compile_well_known_types()
         .protoc_arg("--openapiv2_out=swagger")
         .protoc_arg("--openapiv2_opt")
@@ -18,6 +21,14 @@ fn compile(
         .bytes(["."])
         .btree_map(["."])
         .type_attribute(".", "#[actix_prost_macros::serde]")
+        .field_attribute(
+            ".blockscout.contractsInfo.v1.TokenInfo.localized_descriptions",
+            "#[serde(default)]"
+        )
+        .field_attribute(
+            ".blockscout.contractsInfo.v1.PrepareAddressRequest.format",
+            "#[serde(default)]"
+        )
         .field_attribute(
             ".blockscout.contractsInfo.v1.HealthCheckRequest.service",
             "#[serde(default)]"
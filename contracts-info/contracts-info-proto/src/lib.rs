@@ -6,3 +6,7 @@ pub mod blockscout {
         }
     }
 }
+
+/// Encoded file descriptor set of all the service protos.
+pub const FILE_DESCRIPTOR_SET: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/contracts_info_descriptor.bin"));
@@ -23,6 +23,16 @@ impl Client {
         })
     }
 
+    /// Returns the configured name of the api key the request has been made with.
+    pub fn get_key_name_from_request<T>(&self, request: &tonic::Request<T>) -> Option<&str> {
+        request.metadata().get(API_KEY_NAME).and_then(|api_key| {
+            let api_key = api_key.to_str().expect("http header is always ascii");
+            self.api_keys
+                .iter()
+                .find_map(|(name, key)| key.key.eq(api_key).then_some(name.as_str()))
+        })
+    }
+
     pub fn get_access_level(&self, api_key: &str) -> Option<TokenInfoProviderLevel> {
         self.api_keys
             .values()
@@ -1,12 +1,18 @@
-use tonic::{metadata::MetadataMap, Status};
+use crate::errors::{self, ErrorBuilder, Reason};
+use std::sync::Arc;
+use tonic::{metadata::MetadataMap, Code, Status};
 use tracing::instrument;
 use url::Url;
 
+pub type AuthCache = service_common::auth::AuthCache<AuthenticatedUser>;
+
 pub struct Client {
     endpoint: Url,
     api_key: Option<String>,
+    cache: Arc<AuthCache>,
 }
 
+#[derive(Debug, Clone)]
 pub struct AuthenticatedUser {
     pub id: String,
     pub email: String,
@@ -21,9 +27,19 @@ impl From<blockscout_auth::AuthSuccess> for AuthenticatedUser {
     }
 }
 
+impl From<AuthenticatedUser> for contracts_info_core::User {
+    fn from(value: AuthenticatedUser) -> Self {
+        Self::new(value.id, value.email)
+    }
+}
+
 impl Client {
-    pub fn new(endpoint: Url, api_key: Option<String>) -> Self {
-        Self { endpoint, api_key }
+    pub fn new(endpoint: Url, api_key: Option<String>, cache: Arc<AuthCache>) -> Self {
+        Self {
+            endpoint,
+            api_key,
+            cache,
+        }
     }
 
     pub fn endpoint(&self) -> &Url {
@@ -40,24 +56,42 @@ impl Client {
         metadata: &MetadataMap,
         is_method_safe: bool,
     ) -> Result<AuthenticatedUser, Status> {
-        let endpoint = self.endpoint();
-        let api_key = self.api_key();
-        let success =
-            blockscout_auth::auth_from_metadata(metadata, is_method_safe, endpoint, api_key)
-                .await
-                .map_err(map_auth_error)?;
-        Ok(success.into())
+        service_common::auth::authenticate(
+            &self.cache,
+            metadata,
+            is_method_safe,
+            self.endpoint(),
+            self.api_key(),
+            map_auth_error,
+        )
+        .await
     }
 }
 
 fn map_auth_error(err: blockscout_auth::Error) -> Status {
+    let message = err.to_string();
     match err {
-        blockscout_auth::Error::Unauthorized(_) => Status::unauthenticated(err.to_string()),
-        blockscout_auth::Error::Forbidden(_) => Status::permission_denied(err.to_string()),
-        blockscout_auth::Error::InvalidCsrfToken(_) | blockscout_auth::Error::InvalidJwt(_) => {
-            Status::invalid_argument(err.to_string())
+        blockscout_auth::Error::Unauthorized(_) => {
+            ErrorBuilder::new(Code::Unauthenticated, Reason::InvalidCredentials, message).build()
+        }
+        blockscout_auth::Error::Forbidden(_) => {
+            ErrorBuilder::new(Code::PermissionDenied, Reason::Forbidden, message).build()
+        }
+        blockscout_auth::Error::InvalidCsrfToken(_) => {
+            ErrorBuilder::new(Code::InvalidArgument, Reason::InvalidCsrfToken, &message)
+                .field_violation("x-csrf-token", message)
+                .build()
+        }
+        blockscout_auth::Error::InvalidJwt(_) => {
+            ErrorBuilder::new(Code::InvalidArgument, Reason::InvalidCredentials, message).build()
         }
-        blockscout_auth::Error::BlockscoutApi(_) => Status::unauthenticated(err.to_string()),
-        _ => Status::internal(err.to_string()),
+        blockscout_auth::Error::BlockscoutApi(_) => ErrorBuilder::new(
+            Code::Unauthenticated,
+            Reason::BlockscoutUnavailable,
+            message,
+        )
+        .retryable()
+        .build(),
+        _ => errors::internal(message),
     }
 }
@@ -0,0 +1,68 @@
+use crate::{
+    proto::health_check_response::ServingStatus,
+    services::{HealthService, Statuses, DATABASE_COMPONENT},
+    settings::HealthCheckSettings,
+};
+use contracts_info_core::Client;
+use futures::future::join_all;
+use sea_orm::{ConnectionTrait, DatabaseConnection, Statement};
+use std::sync::Arc;
+
+/// Periodically checks the database and blockscout instances
+/// of the configured chains and reports their statuses to the health service.
+pub async fn watch_dependencies(
+    health: Arc<HealthService>,
+    db: Arc<DatabaseConnection>,
+    chain_clients: Vec<(i64, Client)>,
+    settings: HealthCheckSettings,
+) {
+    let timeout = settings.timeout();
+    let mut interval = tokio::time::interval(settings.interval());
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+
+        let chain_checks = chain_clients.iter().map(|(chain_id, client)| async move {
+            let status = match client.check_blockscout_health(timeout).await {
+                Ok(()) => ServingStatus::Serving,
+                Err(err) => {
+                    tracing::warn!(
+                        chain_id = chain_id,
+                        err = ?err,
+                        "blockscout health check failed"
+                    );
+                    ServingStatus::NotServing
+                }
+            };
+            (format!("chains/{chain_id}/blockscout"), status)
+        });
+        let (database, chain_statuses) = futures::join!(
+            tokio::time::timeout(timeout, check_database(&db)),
+            join_all(chain_checks)
+        );
+
+        let mut statuses: Statuses = chain_statuses.into_iter().collect();
+        let database = match database {
+            Ok(Ok(())) => ServingStatus::Serving,
+            Ok(Err(err)) => {
+                tracing::warn!(err = ?err, "database health check failed");
+                ServingStatus::NotServing
+            }
+            Err(_) => {
+                tracing::warn!(timeout = ?timeout, "database health check timed out");
+                ServingStatus::NotServing
+            }
+        };
+        statuses.insert(DATABASE_COMPONENT.to_string(), database);
+        health.update(statuses);
+    }
+}
+
+async fn check_database(db: &DatabaseConnection) -> Result<(), sea_orm::DbErr> {
+    db.execute(Statement::from_string(
+        db.get_database_backend(),
+        "SELECT 1".to_string(),
+    ))
+    .await?;
+    Ok(())
+}
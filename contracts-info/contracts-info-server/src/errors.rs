@@ -0,0 +1,81 @@
+use service_common::errors;
+use tonic::Status;
+
+pub use service_common::errors::unavailable;
+
+pub type ErrorBuilder = errors::ErrorBuilder<Reason>;
+
+/// Reasons of the errors returned by the service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reason {
+    InvalidArgument,
+    InvalidApiKey,
+    InsufficientAccessLevel,
+    InvalidCredentials,
+    InvalidCsrfToken,
+    Forbidden,
+    UnknownChain,
+    AddressAlreadyVerified,
+    VerifiedAddressesLimit,
+    ContractNotFound,
+    ContractNotVerified,
+    NoContractOwner,
+    InvalidMessage,
+    InvalidNonce,
+    InvalidSigner,
+    InvalidSignature,
+    MessageExpired,
+    SafeConfirmationsPending,
+    VerifiedAddressNotFound,
+    BlockscoutUnavailable,
+    RpcUnavailable,
+    ContractSignaturesNotSupported,
+    IdempotencyKeyReused,
+    IdempotencyKeyInProgress,
+    Internal,
+}
+
+impl errors::Reason for Reason {
+    const DOMAIN: &'static str = "contracts-info.blockscout.com";
+    const INVALID_ARGUMENT: Self = Self::InvalidArgument;
+    const INTERNAL: Self = Self::Internal;
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::InvalidArgument => "INVALID_ARGUMENT",
+            Self::InvalidApiKey => "INVALID_API_KEY",
+            Self::InsufficientAccessLevel => "INSUFFICIENT_ACCESS_LEVEL",
+            Self::InvalidCredentials => "INVALID_CREDENTIALS",
+            Self::InvalidCsrfToken => "INVALID_CSRF_TOKEN",
+            Self::Forbidden => "FORBIDDEN",
+            Self::UnknownChain => "UNKNOWN_CHAIN",
+            Self::AddressAlreadyVerified => "ADDRESS_ALREADY_VERIFIED",
+            Self::VerifiedAddressesLimit => "VERIFIED_ADDRESSES_LIMIT",
+            Self::ContractNotFound => "CONTRACT_NOT_FOUND",
+            Self::ContractNotVerified => "CONTRACT_NOT_VERIFIED",
+            Self::NoContractOwner => "NO_CONTRACT_OWNER",
+            Self::InvalidMessage => "INVALID_MESSAGE",
+            Self::InvalidNonce => "INVALID_NONCE",
+            Self::InvalidSigner => "INVALID_SIGNER",
+            Self::InvalidSignature => "INVALID_SIGNATURE",
+            Self::MessageExpired => "MESSAGE_EXPIRED",
+            Self::SafeConfirmationsPending => "SAFE_CONFIRMATIONS_PENDING",
+            Self::VerifiedAddressNotFound => "VERIFIED_ADDRESS_NOT_FOUND",
+            Self::BlockscoutUnavailable => "BLOCKSCOUT_UNAVAILABLE",
+            Self::RpcUnavailable => "RPC_UNAVAILABLE",
+            Self::ContractSignaturesNotSupported => "CONTRACT_SIGNATURES_NOT_SUPPORTED",
+            Self::IdempotencyKeyReused => "IDEMPOTENCY_KEY_REUSED",
+            Self::IdempotencyKeyInProgress => "IDEMPOTENCY_KEY_IN_PROGRESS",
+            Self::Internal => "INTERNAL",
+        }
+    }
+}
+
+/// Returns `INVALID_ARGUMENT` status with the field violation attached.
+pub fn invalid_argument(field: &str, description: impl Into<String>) -> Status {
+    errors::invalid_argument::<Reason>(field, description)
+}
+
+pub fn internal(message: impl Into<String>) -> Status {
+    errors::internal::<Reason>(message)
+}
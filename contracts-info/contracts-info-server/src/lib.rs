@@ -1,4 +1,7 @@
 mod clients;
+mod dependencies;
+mod errors;
+mod idempotency;
 mod proto;
 mod server;
 mod services;
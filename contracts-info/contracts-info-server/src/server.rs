@@ -1,5 +1,6 @@
 use crate::{
-    clients,
+    clients, dependencies,
+    idempotency::{map_idempotency_error, Idempotency},
     proto::{
         contracts_info_actix::route_contracts_info, contracts_info_server::ContractsInfoServer,
         health_actix::route_health, health_server::HealthServer,
@@ -9,6 +10,7 @@ use crate::{
 };
 use blockscout_service_launcher::LaunchSettings;
 use contracts_info_core::Client;
+use contracts_info_proto::FILE_DESCRIPTOR_SET;
 use migration::{Migrator, MigratorTrait};
 use sea_orm::ConnectOptions;
 use std::sync::Arc;
@@ -23,9 +25,14 @@ struct Router {
 
 impl Router {
     pub fn grpc_router(&self) -> tonic::transport::server::Router {
+        let reflection = tonic_reflection::server::Builder::configure()
+            .register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
+            .build()
+            .expect("file descriptor set should be valid");
         tonic::transport::Server::builder()
             .add_service(HealthServer::from_arc(self.health.clone()))
             .add_service(ContractsInfoServer::from_arc(self.contracts_info.clone()))
+            .add_service(reflection)
     }
 }
 
@@ -46,28 +53,43 @@ pub async fn run(settings: Settings) -> Result<(), anyhow::Error> {
         Migrator::up(db_connection.as_ref(), None).await?;
     }
 
-    let chain_clients =
-        settings
-            .chains_config
-            .networks
-            .into_iter()
-            .map(|(chain_id, chain_config)| {
-                let core_client = Client::new_arc(
-                    db_connection.clone(),
-                    chain_config.url.clone(),
-                    chain_config.api_key.clone(),
-                    settings.max_verified_addresses,
-                );
-                let auth_client =
-                    clients::blockscout_auth::Client::new(chain_config.url, chain_config.api_key);
-                (
-                    chain_id,
-                    ChainClients {
-                        core_client,
-                        auth_client,
-                    },
-                )
-            });
+    let auth_cache = Arc::new(clients::blockscout_auth::AuthCache::new(
+        settings.auth_cache,
+    ));
+    let chain_clients = settings
+        .chains_config
+        .networks
+        .into_iter()
+        .map(|(chain_id, chain_config)| {
+            let core_client = Client::new_arc(
+                db_connection.clone(),
+                chain_config.url.clone(),
+                chain_config.api_key.clone(),
+                settings.max_verified_addresses,
+            )
+            .with_rpc_url(chain_config.rpc_url.clone(), chain_config.rpc_timeout())
+            .with_message_validity(chain_config.message_validity())
+            .with_max_clock_skew(chain_config.max_clock_skew())
+            .with_ownership_resolvers(chain_config.ownership_resolvers.clone())
+            .with_deployer_factories(chain_config.deployer_factories.clone())
+            .with_resolve_contract_creators(chain_config.resolve_contract_creators)
+            .with_max_ownership_chain_depth(chain_config.max_ownership_chain_depth)
+            .with_require_safe_threshold(settings.require_safe_threshold)
+            .with_accept_messages_without_nonce(settings.accept_messages_without_nonce);
+            let auth_client = clients::blockscout_auth::Client::new(
+                chain_config.url,
+                chain_config.api_key,
+                auth_cache.clone(),
+            );
+            (
+                chain_id,
+                ChainClients {
+                    core_client,
+                    auth_client,
+                },
+            )
+        })
+        .collect::<Vec<_>>();
     let api_keys_debug = settings
         .api_keys
         .iter()
@@ -75,12 +97,28 @@ pub async fn run(settings: Settings) -> Result<(), anyhow::Error> {
         .collect::<Vec<_>>();
     tracing::info!(parsed_keys =? api_keys_debug, "parsed api_keys");
     let api_key_auth_client = clients::api_key_auth::Client::new(settings.api_keys);
+    let health = Arc::new(HealthService::default());
+    tokio::spawn(dependencies::watch_dependencies(
+        health.clone(),
+        db_connection.clone(),
+        chain_clients
+            .iter()
+            .map(|(chain_id, clients)| (*chain_id, clients.core_client.clone()))
+            .collect(),
+        settings.health_check,
+    ));
+
+    let idempotency = Idempotency::new(
+        db_connection.clone(),
+        settings.idempotency,
+        map_idempotency_error,
+    );
     let contracts_info = Arc::new(ContractsInfoService::new(
         chain_clients,
         api_key_auth_client,
+        idempotency,
     ));
 
-    let health = Arc::new(HealthService::default());
     let router = Router {
         contracts_info,
         health,
@@ -1,24 +1,41 @@
 use super::contracts_info_validators as validators;
 use crate::{
+    errors::{self, ErrorBuilder, Reason},
     proto::{
         contracts_info_server::ContractsInfo, prepare_address_response, verify_address_response,
-        AddressMetadata, GetTokenInfoRequest, GetVerifiedAddressOwnerAdminRequest,
-        ImportTokenInfoAdminRequest, ListTokenInfosResponse, ListUserTokenInfosRequest,
-        ListUserVerifiedAddressesRequest, ListUserVerifiedAddressesResponse, PrepareAddressRequest,
-        PrepareAddressResponse, TokenInfo, VerifiedAddress, VerifiedAddressOwner,
-        VerifyAddressRequest, VerifyAddressResponse,
+        AddressMetadata, BackfillUserIdsAdminRequest, BackfillUserIdsAdminResponse, ContractOwner,
+        CreatorResolution, EraseUserDataAdminRequest, EraseUserDataAllChainsAdminRequest,
+        EraseUserDataRequest, EraseUserDataResponse, ExportUserDataAdminRequest,
+        ExportUserDataAllChainsAdminRequest, ExportUserDataRequest, GetTokenInfoRequest,
+        GetVerifiedAddressOwnerAdminRequest, ImportTokenInfoAdminRequest, ListTokenInfosResponse,
+        ListUserTokenInfosRequest, ListUserVerifiedAddressesRequest,
+        ListUserVerifiedAddressesResponse, LocalizedDescription, OwnershipChainLink, OwnershipType,
+        PrepareAddressRequest, PrepareAddressResponse, SafeOwners, TokenInfo, UserData,
+        VerifiedAddress, VerifiedAddressOwner, VerifyAddressRequest, VerifyAddressResponse,
     },
     types::token_info_from_proto,
 };
 use blockscout_display_bytes::Bytes as DisplayBytes;
-use contracts_info_core::{handlers, Error, VerificationError};
+use chrono::{DateTime, SecondsFormat, Utc};
+use contracts_info_core::{
+    handlers::{self, DataSubject},
+    Error, User, VerificationError,
+};
 use std::str::FromStr;
-use tonic::{Request, Response, Status};
+use tonic::{Code, Request, Response, Status};
 use tracing::instrument;
 
+const ACCEPT_LANGUAGE_HEADER: &str = "accept-language";
+
 mod service {
-    use crate::clients;
+    use crate::{
+        clients,
+        errors::{ErrorBuilder, Reason},
+        idempotency::Idempotency,
+    };
+    use contracts_info_core::TokenInfoProviderLevel;
     use std::collections::HashMap;
+    use tonic::Code;
 
     pub struct ChainClients {
         pub core_client: contracts_info_core::Client,
@@ -28,28 +45,86 @@ mod service {
     pub struct ContractsInfoService {
         pub chain_clients: HashMap<i64, ChainClients>,
         pub api_key_auth_client: clients::api_key_auth::Client,
+        pub idempotency: Idempotency,
     }
 
     impl ContractsInfoService {
         pub fn new(
             chain_clients: impl IntoIterator<Item = (i64, ChainClients)>,
             api_key_auth_client: clients::api_key_auth::Client,
+            idempotency: Idempotency,
         ) -> Self {
             Self {
                 chain_clients: chain_clients.into_iter().collect(),
                 api_key_auth_client,
+                idempotency,
             }
         }
 
         pub fn try_clients(&self, chain_id: &i64) -> Result<&ChainClients, tonic::Status> {
-            self.chain_clients
-                .get(chain_id)
-                .ok_or(tonic::Status::invalid_argument("Unknown chain id"))
+            self.chain_clients.get(chain_id).ok_or_else(|| {
+                ErrorBuilder::new(
+                    Code::InvalidArgument,
+                    Reason::UnknownChain,
+                    "Unknown chain id",
+                )
+                .metadata("chain_id", chain_id)
+                .field_violation("chain_id", "chain is not supported")
+                .build()
+            })
+        }
+
+        /// Clients of all supported chains ordered by chain id.
+        pub fn sorted_chain_clients(&self) -> Vec<(i64, &ChainClients)> {
+            let mut chain_clients: Vec<_> = self
+                .chain_clients
+                .iter()
+                .map(|(chain_id, clients)| (*chain_id, clients))
+                .collect();
+            chain_clients.sort_by_key(|(chain_id, _)| *chain_id);
+            chain_clients
+        }
+
+        /// Checks that the request has been made by the admin service.
+        pub fn authorize_admin<T>(&self, request: &tonic::Request<T>) -> Result<(), tonic::Status> {
+            match self
+                .api_key_auth_client
+                .get_access_level_from_request(request)
+            {
+                Some(TokenInfoProviderLevel::AdminService) => Ok(()),
+                Some(_) => Err(ErrorBuilder::new(
+                    Code::PermissionDenied,
+                    Reason::InsufficientAccessLevel,
+                    "api key has no access to the method",
+                )
+                .build()),
+                None => Err(ErrorBuilder::new(
+                    Code::Unauthenticated,
+                    Reason::InvalidApiKey,
+                    "invalid api key",
+                )
+                .build()),
+            }
+        }
+
+        /// Idempotency keys of the requests made with api keys are scoped by the key names.
+        pub fn api_key_caller<T>(&self, request: &tonic::Request<T>) -> String {
+            let name = self
+                .api_key_auth_client
+                .get_key_name_from_request(request)
+                .unwrap_or_default();
+            format!("api_keys/{name}")
         }
     }
 }
 pub use service::{ChainClients, ContractsInfoService};
 
+/// Idempotency keys of the requests made on behalf of users are scoped by the users,
+/// so that users cannot replay responses of each other.
+fn user_caller(chain_id: i64, user: &User) -> String {
+    format!("chains/{chain_id}/users/{}", user.id)
+}
+
 #[async_trait::async_trait]
 impl ContractsInfo for ContractsInfoService {
     #[instrument(skip_all, err, level = "info")]
@@ -57,10 +132,13 @@ impl ContractsInfo for ContractsInfoService {
         &self,
         request: Request<GetTokenInfoRequest>,
     ) -> Result<Response<TokenInfo>, Status> {
-        let request = request.into_inner();
+        let (metadata, _, request) = request.into_parts();
 
         let token_address = DisplayBytes::from_str(&request.token_address).map_err(|err| {
-            Status::invalid_argument(format!("Token address must be valid hex: {err}"))
+            errors::invalid_argument(
+                "token_address",
+                format!("Token address must be valid hex: {err}"),
+            )
         })?;
         let chain_id = validators::validate_chain_id(request.chain_id)?;
 
@@ -70,9 +148,21 @@ impl ContractsInfo for ContractsInfoService {
             chain_id,
         )
         .await;
+        // The request field takes precedence over the header
+        let accept_language = request.accept_language.or_else(|| {
+            metadata
+                .get(ACCEPT_LANGUAGE_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        });
         match result {
             Ok(None) => Ok(Response::new(TokenInfo::default())),
-            Ok(Some(info)) => Ok(Response::new(convert_token_info(info))),
+            Ok(Some(mut info)) => {
+                let language = accept_language.and_then(|value| info.localize(&value));
+                let mut info = convert_token_info(info);
+                info.language = language;
+                Ok(Response::new(info))
+            }
             Err(err) => Err(process_error(err)),
         }
     }
@@ -88,14 +178,14 @@ impl ContractsInfo for ContractsInfoService {
 
         let clients = self.try_clients(&chain_id)?;
 
-        let user_email = clients
+        let user = clients
             .auth_client
             .authenticate(&metadata, true)
             .await?
-            .email;
+            .into();
 
         let token_infos: Vec<_> =
-            handlers::list_user_token_infos(&clients.core_client, user_email, chain_id)
+            handlers::list_user_token_infos(&clients.core_client, user, chain_id)
                 .await
                 .map_err(process_error)?
                 .into_iter()
@@ -112,18 +202,34 @@ impl ContractsInfo for ContractsInfoService {
         let access_level = self
             .api_key_auth_client
             .get_access_level_from_request(&request)
-            .ok_or_else(|| Status::unauthenticated("invalid api key"))?;
+            .ok_or_else(|| {
+                ErrorBuilder::new(
+                    Code::Unauthenticated,
+                    Reason::InvalidApiKey,
+                    "invalid api key",
+                )
+                .build()
+            })?;
+        let caller = self.api_key_caller(&request);
+        let (metadata, _, request) = request.into_parts();
         let token_info_proto = request
-            .into_inner()
             .token_info
-            .ok_or_else(|| Status::invalid_argument("no token_info data"))?;
+            .clone()
+            .ok_or_else(|| errors::invalid_argument("token_info", "value is required"))?;
         let token_info = token_info_from_proto(token_info_proto.clone())?;
         let clients = self.try_clients(&token_info.chain_id)?;
-        handlers::import_token_info(&clients.core_client, token_info, access_level)
-            .await
-            .map_err(process_error)?;
+        let method = "ImportTokenInfoAdmin";
+        let response = self
+            .idempotency
+            .run(&metadata, &caller, method, request, |_| async move {
+                handlers::import_token_info(&clients.core_client, token_info, access_level)
+                    .await
+                    .map_err(process_error)?;
+                Ok(token_info_proto)
+            })
+            .await?;
 
-        Ok(Response::new(token_info_proto))
+        Ok(Response::new(response))
     }
 
     #[instrument(skip_all, err, level = "info")]
@@ -135,97 +241,101 @@ impl ContractsInfo for ContractsInfoService {
 
         let chain_id = validators::validate_chain_id(request.chain_id)?;
         let contract_address = validators::validate_contract_address(&request.contract_address)?;
-        let message = request.message;
-        let signature = validators::validate_signature(&request.signature)?;
+        let signer = validators::validate_signer(request.signer.as_deref())?;
+        let signature = validators::validate_signature(&request.signature, signer.is_some())?;
 
         let clients = self.try_clients(&chain_id)?;
 
-        let user = clients.auth_client.authenticate(&metadata, false).await?;
+        let user: User = clients
+            .auth_client
+            .authenticate(&metadata, false)
+            .await?
+            .into();
+        let caller = user_caller(chain_id, &user);
+        let response = self
+            .idempotency
+            .run(
+                &metadata,
+                &caller,
+                "VerifyAddress",
+                request,
+                |request| async move {
+                    let message = request.message;
+                    handlers::reconcile_user(&clients.core_client, &user)
+                        .await
+                        .map_err(process_error)?;
 
-        let result = handlers::verify_address(
-            &clients.core_client,
-            user.email,
-            chain_id,
-            contract_address,
-            message,
-            signature,
-        )
-        .await;
+                    let result = handlers::verify_address(
+                        &clients.core_client,
+                        user,
+                        chain_id,
+                        contract_address,
+                        message,
+                        signature,
+                        signer,
+                    )
+                    .await;
 
-        let response = match result {
-            Ok(res) => {
-                let verified_address = VerifiedAddress {
-                    user_id: res.user_email,
-                    chain_id: res.chain_id as u64,
-                    contract_address: res.contract_address.to_string(),
-                    verified_date: res.verified_date.date().to_string(),
-                    metadata: Some(AddressMetadata {
-                        token_name: res.metadata.token_name,
-                        token_symbol: res.metadata.token_symbol,
-                    }),
-                };
-                verify_address_response_conversions::success(verify_address_response::Success {
-                    verified_address: Some(verified_address),
-                })
-            }
-            Err(Error::AddressIsVerified(_)) => {
-                return Err(Status::invalid_argument(
-                    "Contract address ownership has already been verified",
-                ))
-            }
-            Err(Error::MaxVerifiedAddressesLimit(_)) => {
-                return Err(Status::failed_precondition(
-                    "max number of verified addresses has been reached",
-                ))
-            }
-            Err(Error::SignatureVerification { kind }) => match kind {
-                VerificationError::ContractNotFound(_) => {
-                    return Err(Status::invalid_argument("Contract not found"))
-                }
-                VerificationError::ContractNotVerified(_) => {
-                    return Err(Status::invalid_argument(
-                        "Contract source code has not been verified",
-                    ))
-                }
-                VerificationError::WrongOwner {
-                    suggested_owner,
-                    possible_owners,
-                    ..
-                } => verify_address_response_conversions::invalid_signer(
-                    verify_address_response::InvalidSignerError {
-                        signer: format!("{suggested_owner:#?}"),
-                        valid_addresses: possible_owners
-                            .into_iter()
-                            .map(|addr| format!("{addr:#?}"))
-                            .collect(),
-                    },
-                ),
-                VerificationError::InvalidFormat(e) => {
-                    return Err(Status::invalid_argument(format!(
-                        "Invalid message format: {e}"
-                    )))
-                }
-                VerificationError::InvalidValue(e) => {
-                    return Err(Status::invalid_argument(format!("Invalid message: {e}")))
-                }
-                VerificationError::Expired => {
-                    verify_address_response_conversions::validity_expired()
-                }
-                VerificationError::Signature(_) => {
-                    verify_address_response_conversions::invalid_signature()
-                }
-                VerificationError::NoOwner(_) => {
-                    return Err(Status::invalid_argument(
-                        "Specified address is not a contract",
-                    ))
-                }
-                VerificationError::BlockscoutRequest(_) => {
-                    tracing::error!("internal error: during address verifitation: {kind}");
-                    return Err(Status::internal(""));
-                }
-            },
-            Err(err) => return Err(process_error(err)),
-        };
+                    let response = match result {
+                        Ok(res) => verify_address_response_conversions::success(
+                            verify_address_response::Success {
+                                verified_address: Some(convert_verified_address(res)),
+                            },
+                        ),
+                        Err(Error::AddressIsVerified { .. }) => {
+                            return Err(ErrorBuilder::new(
+                                Code::InvalidArgument,
+                                Reason::AddressAlreadyVerified,
+                                "Contract address ownership has already been verified",
+                            )
+                            .metadata("contract_address", &request.contract_address)
+                            .build())
+                        }
+                        Err(Error::InvalidNonce) => {
+                            verify_address_response_conversions::invalid_nonce()
+                        }
+                        Err(Error::SafeConfirmationsPending {
+                            safe,
+                            confirmed_signers,
+                        }) => verify_address_response_conversions::confirmations_pending(
+                            verify_address_response::PendingConfirmations {
+                                safe: Some(convert_safe_owners(safe)),
+                                confirmed_signers: confirmed_signers
+                                    .into_iter()
+                                    .map(|addr| format!("{addr:#?}"))
+                                    .collect(),
+                            },
+                        ),
+                        Err(Error::SignatureVerification { kind }) => match kind {
+                            VerificationError::WrongOwner {
+                                suggested_owner,
+                                possible_owners,
+                                ..
+                            } => verify_address_response_conversions::invalid_signer(
+                                verify_address_response::InvalidSignerError {
+                                    signer: format!("{suggested_owner:#?}"),
+                                    valid_addresses: possible_owners
+                                        .into_iter()
+                                        .map(|addr| format!("{addr:#?}"))
+                                        .collect(),
+                                },
+                            ),
+                            VerificationError::Expired => {
+                                verify_address_response_conversions::validity_expired()
+                            }
+                            VerificationError::Signature(_)
+                            | VerificationError::InvalidContractSignature(_) => {
+                                verify_address_response_conversions::invalid_signature()
+                            }
+                            kind => return Err(process_verification_error(kind)),
+                        },
+                        Err(err) => return Err(process_error(err)),
+                    };
+
+                    Ok(response)
+                },
+            )
+            .await?;
 
         Ok(Response::new(response))
     }
@@ -238,15 +348,23 @@ impl ContractsInfo for ContractsInfoService {
         let (metadata, _, request) = request.into_parts();
         let chain_id = validators::validate_chain_id(request.chain_id)?;
         let contract_address = validators::validate_contract_address(&request.contract_address)?;
+        let format =
+            validators::validate_message_format(request.format(), request.signer.as_deref())?;
         let clients = self.try_clients(&chain_id)?;
-        let user_email = clients
+        let user: User = clients
             .auth_client
             .authenticate(&metadata, true)
             .await?
-            .email;
+            .into();
 
-        let result =
-            handlers::prepare_address(&clients.core_client, chain_id, contract_address).await;
+        let result = handlers::prepare_address(
+            &clients.core_client,
+            user.clone(),
+            chain_id,
+            contract_address,
+            format,
+        )
+        .await;
         let response = match result {
             Ok(prepared_address) => {
                 prepare_address_response_conversions::success(prepare_address_response::Success {
@@ -255,14 +373,33 @@ impl ContractsInfo for ContractsInfoService {
                     contract_owner: prepared_address
                         .contract_owner
                         .map(|addr| format!("{addr:#?}")),
+                    contract_owner_safe: prepared_address
+                        .contract_owner_safe
+                        .map(convert_safe_owners),
+                    expires_at: DateTime::<Utc>::from_utc(prepared_address.expires_at, Utc)
+                        .to_rfc3339_opts(SecondsFormat::Secs, true),
+                    contract_owners: prepared_address
+                        .resolved_owners
+                        .into_iter()
+                        .map(convert_resolved_owner)
+                        .collect(),
+                    contract_creator_resolution: convert_creator_resolution(
+                        prepared_address.contract_creator_resolution,
+                    )
+                    .into(),
+                    contract_deployer: contract_deployer(
+                        prepared_address.contract_creator_resolution,
+                    )
+                    .map(|addr| format!("{addr:#?}")),
                 })
             }
-            Err(Error::AddressIsVerified(verified_user_email))
-                if verified_user_email == user_email =>
-            {
+            Err(Error::AddressIsVerified {
+                owner_id,
+                owner_email,
+            }) if user.is_owner(owner_id.as_deref(), &owner_email) => {
                 prepare_address_response_conversions::is_already_owner()
             }
-            Err(Error::AddressIsVerified(_)) => {
+            Err(Error::AddressIsVerified { .. }) => {
                 prepare_address_response_conversions::ownership_already_verified()
             }
             Err(Error::SignatureVerification { kind }) => match kind {
@@ -272,10 +409,7 @@ impl ContractsInfo for ContractsInfoService {
                 VerificationError::ContractNotVerified(_) => {
                     prepare_address_response_conversions::source_code_not_verified()
                 }
-                _ => {
-                    tracing::error!("unexpected error: {kind}");
-                    return Err(Status::internal(""));
-                }
+                kind => return Err(process_verification_error(kind)),
             },
             Err(err) => return Err(process_error(err)),
         };
@@ -294,14 +428,14 @@ impl ContractsInfo for ContractsInfoService {
 
         let clients = self.try_clients(&chain_id)?;
 
-        let user_email = clients
+        let user = clients
             .auth_client
             .authenticate(&metadata, true)
             .await?
-            .email;
+            .into();
 
         let verified_addresses: Vec<_> =
-            handlers::list_user_verified_addresses(&clients.core_client, user_email, chain_id)
+            handlers::list_user_verified_addresses(&clients.core_client, user, chain_id)
                 .await
                 .map_err(process_error)?
                 .into_iter()
@@ -322,7 +456,7 @@ impl ContractsInfo for ContractsInfoService {
         let request = request.into_inner();
 
         let address = DisplayBytes::from_str(&request.address).map_err(|err| {
-            Status::invalid_argument(format!("Address must be a valid hex: {err}"))
+            errors::invalid_argument("address", format!("Address must be a valid hex: {err}"))
         })?;
         let chain_id = validators::validate_chain_id(request.chain_id)?;
 
@@ -334,12 +468,234 @@ impl ContractsInfo for ContractsInfoService {
                 .map_err(process_error)?;
 
         match verified_address {
-            None => Err(Status::not_found("")),
+            None => Err(ErrorBuilder::new(
+                Code::NotFound,
+                Reason::VerifiedAddressNotFound,
+                "address has not been verified",
+            )
+            .metadata("chain_id", chain_id)
+            .metadata("address", &request.address)
+            .build()),
             Some(address) => Ok(Response::new(VerifiedAddressOwner {
                 user_email: address.user_email,
+                user_id: address.user_id,
             })),
         }
     }
+
+    #[instrument(skip_all, err, level = "info")]
+    async fn export_user_data(
+        &self,
+        request: Request<ExportUserDataRequest>,
+    ) -> Result<Response<UserData>, Status> {
+        let (metadata, _, request) = request.into_parts();
+
+        let chain_id = validators::validate_chain_id(request.chain_id)?;
+
+        let clients = self.try_clients(&chain_id)?;
+
+        let user: User = clients
+            .auth_client
+            .authenticate(&metadata, true)
+            .await?
+            .into();
+
+        let user_data = handlers::export_user_data(&clients.core_client, &user.into(), chain_id)
+            .await
+            .map_err(process_error)?;
+
+        Ok(Response::new(convert_user_data(user_data)))
+    }
+
+    #[instrument(skip_all, err, level = "info")]
+    async fn erase_user_data(
+        &self,
+        request: Request<EraseUserDataRequest>,
+    ) -> Result<Response<EraseUserDataResponse>, Status> {
+        let (metadata, _, request) = request.into_parts();
+
+        let chain_id = validators::validate_chain_id(request.chain_id)?;
+
+        let clients = self.try_clients(&chain_id)?;
+
+        let user: User = clients
+            .auth_client
+            .authenticate(&metadata, false)
+            .await?
+            .into();
+        let caller = user_caller(chain_id, &user);
+        let response = self
+            .idempotency
+            .run(
+                &metadata,
+                &caller,
+                "EraseUserData",
+                request,
+                |_| async move {
+                    handlers::reconcile_user(&clients.core_client, &user)
+                        .await
+                        .map_err(process_error)?;
+
+                    let anonymized_verified_addresses =
+                        handlers::erase_user_data(&clients.core_client, &user.into(), chain_id)
+                            .await
+                            .map_err(process_error)?;
+
+                    Ok(EraseUserDataResponse {
+                        anonymized_verified_addresses,
+                    })
+                },
+            )
+            .await?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(skip_all, err, level = "info")]
+    async fn export_user_data_admin(
+        &self,
+        request: Request<ExportUserDataAdminRequest>,
+    ) -> Result<Response<UserData>, Status> {
+        self.authorize_admin(&request)?;
+        let request = request.into_inner();
+
+        let chain_id = validators::validate_chain_id(request.chain_id)?;
+        let subject = validators::validate_data_subject(request.user_id, request.user_email)?;
+
+        let clients = self.try_clients(&chain_id)?;
+
+        let user_data = handlers::export_user_data(&clients.core_client, &subject, chain_id)
+            .await
+            .map_err(process_error)?;
+
+        Ok(Response::new(convert_user_data(user_data)))
+    }
+
+    #[instrument(skip_all, err, level = "info")]
+    async fn erase_user_data_admin(
+        &self,
+        request: Request<EraseUserDataAdminRequest>,
+    ) -> Result<Response<EraseUserDataResponse>, Status> {
+        self.authorize_admin(&request)?;
+        let caller = self.api_key_caller(&request);
+        let (metadata, _, request) = request.into_parts();
+
+        let chain_id = validators::validate_chain_id(request.chain_id)?;
+        let clients = self.try_clients(&chain_id)?;
+
+        let response = self
+            .idempotency
+            .run(
+                &metadata,
+                &caller,
+                "EraseUserDataAdmin",
+                request,
+                |request| async move {
+                    let subject =
+                        validators::validate_data_subject(request.user_id, request.user_email)?;
+                    if let DataSubject::User(user) = &subject {
+                        handlers::reconcile_user(&clients.core_client, user)
+                            .await
+                            .map_err(process_error)?;
+                    }
+                    let anonymized_verified_addresses =
+                        handlers::erase_user_data(&clients.core_client, &subject, chain_id)
+                            .await
+                            .map_err(process_error)?;
+
+                    Ok(EraseUserDataResponse {
+                        anonymized_verified_addresses,
+                    })
+                },
+            )
+            .await?;
+
+        Ok(Response::new(response))
+    }
+
+    #[instrument(skip_all, err, level = "info")]
+    async fn export_user_data_all_chains_admin(
+        &self,
+        request: Request<ExportUserDataAllChainsAdminRequest>,
+    ) -> Result<Response<UserData>, Status> {
+        self.authorize_admin(&request)?;
+        let request = request.into_inner();
+
+        let subject = validators::validate_user_email(request.user_email)?;
+
+        let mut user_data = UserData::default();
+        for (chain_id, clients) in self.sorted_chain_clients() {
+            let chain_user_data =
+                handlers::export_user_data(&clients.core_client, &subject, chain_id)
+                    .await
+                    .map_err(process_error)?;
+            let chain_user_data = convert_user_data(chain_user_data);
+            user_data
+                .verified_addresses
+                .extend(chain_user_data.verified_addresses);
+            user_data.token_infos.extend(chain_user_data.token_infos);
+        }
+
+        Ok(Response::new(user_data))
+    }
+
+    #[instrument(skip_all, err, level = "info")]
+    async fn erase_user_data_all_chains_admin(
+        &self,
+        request: Request<EraseUserDataAllChainsAdminRequest>,
+    ) -> Result<Response<EraseUserDataResponse>, Status> {
+        self.authorize_admin(&request)?;
+        let caller = self.api_key_caller(&request);
+        let (metadata, _, request) = request.into_parts();
+
+        let chain_clients = self.sorted_chain_clients();
+        let response = self
+            .idempotency
+            .run(
+                &metadata,
+                &caller,
+                "EraseUserDataAllChainsAdmin",
+                request,
+                |request| async move {
+                    let subject = validators::validate_user_email(request.user_email)?;
+                    let mut anonymized_verified_addresses = 0;
+                    for (chain_id, clients) in &chain_clients {
+                        anonymized_verified_addresses +=
+                            handlers::erase_user_data(&clients.core_client, &subject, *chain_id)
+                                .await
+                                .map_err(process_error)?;
+                    }
+
+                    Ok(EraseUserDataResponse {
+                        anonymized_verified_addresses,
+                    })
+                },
+            )
+            .await?;
+
+        Ok(Response::new(response))
+    }
+
+    async fn backfill_user_ids_admin(
+        &self,
+        request: Request<BackfillUserIdsAdminRequest>,
+    ) -> Result<Response<BackfillUserIdsAdminResponse>, Status> {
+        self.authorize_admin(&request)?;
+        let request = request.into_inner();
+
+        let chain_id = validators::validate_chain_id(request.chain_id)?;
+        let users = validators::validate_blockscout_users(request.users)?;
+        let clients = self.try_clients(&chain_id)?;
+
+        let updated_verified_addresses =
+            handlers::backfill_user_ids(&clients.core_client, chain_id, &users)
+                .await
+                .map_err(process_error)?;
+
+        Ok(Response::new(BackfillUserIdsAdminResponse {
+            updated_verified_addresses,
+        }))
+    }
 }
 
 fn convert_token_info(token_info: contracts_info_core::TokenInfo) -> TokenInfo {
@@ -369,6 +725,16 @@ fn convert_token_info(token_info: contracts_info_core::TokenInfo) -> TokenInfo {
         defi_llama_ticker: token_info.defi_llama_ticker,
         token_name: token_info.token_name,
         token_symbol: token_info.token_symbol,
+        localized_descriptions: token_info
+            .localized_descriptions
+            .into_iter()
+            .map(|localized| LocalizedDescription {
+                language: localized.language,
+                project_name: localized.project_name,
+                project_description: localized.project_description,
+            })
+            .collect(),
+        language: None,
     }
 }
 
@@ -384,17 +750,238 @@ fn convert_verified_address(
             token_name: verified_address.metadata.token_name,
             token_symbol: verified_address.metadata.token_symbol,
         }),
+        ownership_chain: verified_address
+            .ownership_chain
+            .into_iter()
+            .map(convert_chain_link)
+            .collect(),
+    }
+}
+
+fn convert_chain_link(link: contracts_info_core::verify::ChainLink) -> OwnershipChainLink {
+    use contracts_info_core::verify::ControlRelation;
+    let relation = match link.relation {
+        ControlRelation::Owner => OwnershipType::Owner,
+        ControlRelation::GetOwner => OwnershipType::GetOwner,
+        ControlRelation::Admin => OwnershipType::Admin,
+        ControlRelation::AccessControlAdmin => OwnershipType::AccessControlAdmin,
+        ControlRelation::ProxyAdmin => OwnershipType::ProxyAdmin,
+        ControlRelation::Proposer => OwnershipType::Proposer,
+    };
+    OwnershipChainLink {
+        address: format!("{:#?}", link.address),
+        relation: relation.into(),
+    }
+}
+
+fn convert_safe_owners(safe: contracts_info_core::verify::SafeOwners) -> SafeOwners {
+    SafeOwners {
+        address: format!("{:#?}", safe.safe),
+        owners: safe
+            .owners
+            .into_iter()
+            .map(|addr| format!("{addr:#?}"))
+            .collect(),
+        threshold: safe.threshold,
+    }
+}
+
+fn convert_creator_resolution(
+    resolution: contracts_info_core::verify::CreatorResolution,
+) -> CreatorResolution {
+    use contracts_info_core::verify::CreatorResolution as CoreCreatorResolution;
+    match resolution {
+        CoreCreatorResolution::Sender => CreatorResolution::Sender,
+        CoreCreatorResolution::DeployerFactory(_) => CreatorResolution::DeployerFactory,
+        CoreCreatorResolution::ContractDeployer(_) => CreatorResolution::ContractDeployer,
+        CoreCreatorResolution::ExternallyOwned => CreatorResolution::ExternallyOwned,
+    }
+}
+
+fn contract_deployer(
+    resolution: contracts_info_core::verify::CreatorResolution,
+) -> Option<ethers::types::Address> {
+    use contracts_info_core::verify::CreatorResolution as CoreCreatorResolution;
+    match resolution {
+        CoreCreatorResolution::Sender | CoreCreatorResolution::ExternallyOwned => None,
+        CoreCreatorResolution::DeployerFactory(deployer)
+        | CoreCreatorResolution::ContractDeployer(deployer) => Some(deployer),
+    }
+}
+
+fn convert_resolved_owner(owner: contracts_info_core::verify::ResolvedOwner) -> ContractOwner {
+    use contracts_info_core::verify::OwnershipType as CoreOwnershipType;
+    let ownership_type = match owner.ownership_type {
+        CoreOwnershipType::Owner => OwnershipType::Owner,
+        CoreOwnershipType::GetOwner => OwnershipType::GetOwner,
+        CoreOwnershipType::Admin => OwnershipType::Admin,
+        CoreOwnershipType::AccessControlAdmin => OwnershipType::AccessControlAdmin,
+        CoreOwnershipType::ProxyAdmin => OwnershipType::ProxyAdmin,
+        // Not reported by the resolvers
+        CoreOwnershipType::Creator
+        | CoreOwnershipType::SelfOwner
+        | CoreOwnershipType::SafeOwner(_)
+        | CoreOwnershipType::ChainOwner(_) => OwnershipType::UnknownOwnershipType,
+    };
+    ContractOwner {
+        address: format!("{:#?}", owner.address),
+        ownership_type: ownership_type.into(),
+    }
+}
+
+fn convert_user_data(user_data: handlers::UserData) -> UserData {
+    UserData {
+        verified_addresses: user_data
+            .verified_addresses
+            .into_iter()
+            .map(convert_verified_address)
+            .collect(),
+        token_infos: user_data
+            .token_infos
+            .into_iter()
+            .map(convert_token_info)
+            .collect(),
     }
 }
 
 fn process_error(err: Error) -> Status {
     match err {
-        Error::Db(_) => Status::internal(err.to_string()),
-        Error::BlockscoutRequest(_) => Status::internal(err.to_string()),
-        Error::Unexpected(_) => Status::internal("Unknown system error"),
-        Error::SignatureVerification { .. } => Status::internal("Should be unreachable"),
-        Error::AddressIsVerified(_) => Status::internal("Should be unreachable"),
-        Error::MaxVerifiedAddressesLimit(_) => Status::internal("Should be unreachable"),
+        Error::Db(_) => errors::internal(err.to_string()),
+        Error::BlockscoutRequest(_) => {
+            errors::unavailable(Reason::BlockscoutUnavailable, err.to_string())
+        }
+        Error::Unexpected(_) => errors::internal("Unknown system error"),
+        Error::SignatureVerification { kind } => process_verification_error(kind),
+        Error::AddressIsVerified { .. } => ErrorBuilder::new(
+            Code::InvalidArgument,
+            Reason::AddressAlreadyVerified,
+            "Contract address ownership has already been verified",
+        )
+        .build(),
+        Error::MaxVerifiedAddressesLimit(limit) => ErrorBuilder::new(
+            Code::FailedPrecondition,
+            Reason::VerifiedAddressesLimit,
+            "max number of verified addresses has been reached",
+        )
+        .metadata("limit", limit)
+        .precondition_violation(
+            "LIMIT",
+            "verified_addresses",
+            format!("user cannot have more than {limit} verified addresses"),
+        )
+        .build(),
+        Error::SafeConfirmationsPending {
+            ref safe,
+            ref confirmed_signers,
+        } => ErrorBuilder::new(
+            Code::FailedPrecondition,
+            Reason::SafeConfirmationsPending,
+            "Not enough safe owners have signed the verification message",
+        )
+        .metadata("threshold", safe.threshold)
+        .metadata("confirmed_signers", confirmed_signers.len())
+        .precondition_violation("CONFIRMATIONS", "safe_owners", err.to_string())
+        .build(),
+        Error::InvalidNonce => ErrorBuilder::new(
+            Code::InvalidArgument,
+            Reason::InvalidNonce,
+            "Verification message nonce is unknown, expired or has already been used",
+        )
+        .field_violation("message", err.to_string())
+        .build(),
+    }
+}
+
+/// Maps verification errors which are not reported in the response body.
+fn process_verification_error(kind: VerificationError) -> Status {
+    let message = kind.to_string();
+    match kind {
+        VerificationError::ContractNotFound(address) => ErrorBuilder::new(
+            Code::InvalidArgument,
+            Reason::ContractNotFound,
+            "Contract not found",
+        )
+        .metadata("contract_address", format!("{address:#x}"))
+        .field_violation("contract_address", message)
+        .build(),
+        VerificationError::ContractNotVerified(address) => ErrorBuilder::new(
+            Code::InvalidArgument,
+            Reason::ContractNotVerified,
+            "Contract source code has not been verified",
+        )
+        .metadata("contract_address", format!("{address:#x}"))
+        .field_violation("contract_address", message)
+        .build(),
+        VerificationError::NoOwner(address) => ErrorBuilder::new(
+            Code::InvalidArgument,
+            Reason::NoContractOwner,
+            "Specified address is not a contract",
+        )
+        .metadata("contract_address", format!("{address:#x}"))
+        .field_violation("contract_address", message)
+        .build(),
+        VerificationError::InvalidFormat(e) => ErrorBuilder::new(
+            Code::InvalidArgument,
+            Reason::InvalidMessage,
+            format!("Invalid message format: {e}"),
+        )
+        .field_violation("message", e)
+        .build(),
+        VerificationError::InvalidValue(e) => ErrorBuilder::new(
+            Code::InvalidArgument,
+            Reason::InvalidMessage,
+            format!("Invalid message: {e}"),
+        )
+        .field_violation("message", e)
+        .build(),
+        VerificationError::FutureTimestamp(_) => ErrorBuilder::new(
+            Code::InvalidArgument,
+            Reason::InvalidMessage,
+            "Message timestamp is too far in the future",
+        )
+        .field_violation("message", message)
+        .build(),
+        VerificationError::ContractSignaturesNotSupported => ErrorBuilder::new(
+            Code::FailedPrecondition,
+            Reason::ContractSignaturesNotSupported,
+            "Smart contract signatures are not supported for the chain",
+        )
+        .precondition_violation("CONFIGURATION", "rpc_url", message)
+        .build(),
+        VerificationError::BlockscoutRequest(_) => {
+            tracing::error!("internal error: during address verification: {message}");
+            errors::unavailable(Reason::BlockscoutUnavailable, message)
+        }
+        VerificationError::RpcRequest(_) => {
+            tracing::error!("internal error: during address verification: {message}");
+            errors::unavailable(Reason::RpcUnavailable, message)
+        }
+        VerificationError::WrongOwner {
+            suggested_owner, ..
+        } => ErrorBuilder::new(
+            Code::InvalidArgument,
+            Reason::InvalidSigner,
+            "Message signer is not an owner of the contract",
+        )
+        .metadata("signer", format!("{suggested_owner:#x}"))
+        .field_violation("message", message)
+        .build(),
+        VerificationError::Expired => ErrorBuilder::new(
+            Code::InvalidArgument,
+            Reason::MessageExpired,
+            "Message has expired",
+        )
+        .field_violation("message", message)
+        .build(),
+        VerificationError::Signature(_) | VerificationError::InvalidContractSignature(_) => {
+            ErrorBuilder::new(
+                Code::InvalidArgument,
+                Reason::InvalidSignature,
+                "Invalid signature",
+            )
+            .field_violation("signature", message)
+            .build()
+        }
     }
 }
 
@@ -426,6 +1013,17 @@ mod verify_address_response_conversions {
     pub fn invalid_signature() -> VerifyAddressResponse {
         response(Status::InvalidSignatureError, None)
     }
+
+    pub fn invalid_nonce() -> VerifyAddressResponse {
+        response(Status::InvalidNonceError, None)
+    }
+
+    pub fn confirmations_pending(pending: PendingConfirmations) -> VerifyAddressResponse {
+        response(
+            Status::ConfirmationsPending,
+            Some(Details::PendingConfirmations(pending)),
+        )
+    }
 }
 
 mod prepare_address_response_conversions {
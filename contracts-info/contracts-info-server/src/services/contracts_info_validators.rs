@@ -1,19 +1,32 @@
+use crate::{
+    errors::{self, ErrorBuilder, Reason},
+    proto::{BlockscoutUser, MessageFormat},
+};
 use blockscout_display_bytes::Bytes as DisplayBytes;
+use contracts_info_core::{handlers, handlers::DataSubject, User};
 use std::str::FromStr;
-use tonic::Status;
+use tonic::{Code, Status};
+
+/// Limits the size of smart contract wallet signatures, e.g. concatenated multisig signatures.
+const MAX_SIGNATURE_LENGTH: usize = 8192;
 
 pub fn validate_chain_id(chain_id: u64) -> Result<i64, Status> {
     i64::try_from(chain_id).map_err(|err| {
-        Status::invalid_argument(format!("Chain id must be less or equal to 2^63-1: {err}"))
+        errors::invalid_argument(
+            "chain_id",
+            format!("Chain id must be less or equal to 2^63-1: {err}"),
+        )
     })
 }
 
 pub fn validate_contract_address(contract_address: &str) -> Result<[u8; 20], Status> {
-    let err_message = "Invalid contract address";
     let bytes = DisplayBytes::from_str(contract_address)
-        .map_err(|_err| Status::invalid_argument(err_message))?;
+        .map_err(|_err| errors::invalid_argument("contract_address", "must be valid hex"))?;
     if bytes.len() != 20 {
-        return Err(Status::invalid_argument(err_message));
+        return Err(errors::invalid_argument(
+            "contract_address",
+            "must be 20 bytes long",
+        ));
     }
     let fixed = bytes
         .to_vec()
@@ -22,16 +35,114 @@ pub fn validate_contract_address(contract_address: &str) -> Result<[u8; 20], Sta
     Ok(fixed)
 }
 
-pub fn validate_signature(signature: &str) -> Result<[u8; 65], Status> {
-    let err_message = "Invalid signature";
-    let bytes =
-        DisplayBytes::from_str(signature).map_err(|_err| Status::invalid_argument(err_message))?;
-    if bytes.len() != 65 {
-        return Err(Status::invalid_argument(err_message));
+/// Signatures of smart contract wallets (EIP-1271) are only accepted along with
+/// an explicit signer and may be of any length; otherwise 65-byte ECDSA signatures are expected.
+pub fn validate_signature(signature: &str, has_signer: bool) -> Result<Vec<u8>, Status> {
+    let bytes = DisplayBytes::from_str(signature)
+        .map_err(|_err| errors::invalid_argument("signature", "must be valid hex"))?;
+    if has_signer {
+        if bytes.is_empty() || bytes.len() > MAX_SIGNATURE_LENGTH {
+            return Err(errors::invalid_argument(
+                "signature",
+                format!("must be from 1 to {MAX_SIGNATURE_LENGTH} bytes long"),
+            ));
+        }
+    } else if bytes.len() != 65 {
+        return Err(errors::invalid_argument(
+            "signature",
+            "must be 65 bytes long",
+        ));
     }
+    Ok(bytes.to_vec())
+}
+
+pub fn validate_signer(signer: Option<&str>) -> Result<Option<[u8; 20]>, Status> {
+    let signer = match signer.filter(|signer| !signer.is_empty()) {
+        Some(signer) => signer,
+        None => return Ok(None),
+    };
+    let bytes = DisplayBytes::from_str(signer)
+        .map_err(|_err| errors::invalid_argument("signer", "must be valid hex"))?;
     let fixed = bytes
         .to_vec()
         .try_into()
-        .expect("Input is exactly 65 bytes");
-    Ok(fixed)
+        .map_err(|_err| errors::invalid_argument("signer", "must be 20 bytes long"))?;
+    Ok(Some(fixed))
+}
+
+pub fn validate_message_format(
+    format: MessageFormat,
+    signer: Option<&str>,
+) -> Result<handlers::MessageFormat, Status> {
+    match format {
+        MessageFormat::Legacy => Ok(handlers::MessageFormat::Legacy),
+        MessageFormat::Siwe => match validate_signer(signer)? {
+            Some(signer) => Ok(handlers::MessageFormat::Siwe {
+                signer: signer.into(),
+            }),
+            None => Err(errors::invalid_argument(
+                "signer",
+                "must be set for SIWE messages",
+            )),
+        },
+        MessageFormat::Eip712 => Ok(handlers::MessageFormat::TypedData),
+    }
+}
+
+/// Users are identified by the email in requests across all chains,
+/// as Blockscout user ids are specific to the chain.
+pub fn validate_user_email(user_email: String) -> Result<DataSubject, Status> {
+    if user_email.is_empty() {
+        return Err(errors::invalid_argument(
+            "user_email",
+            "User email must be specified",
+        ));
+    }
+    Ok(DataSubject::Email(user_email))
+}
+
+pub fn validate_blockscout_users(users: Vec<BlockscoutUser>) -> Result<Vec<User>, Status> {
+    if users.is_empty() {
+        return Err(errors::invalid_argument(
+            "users",
+            "At least one user must be specified",
+        ));
+    }
+    users
+        .into_iter()
+        .enumerate()
+        .map(|(i, user)| {
+            for (field, value) in [("id", &user.id), ("email", &user.email)] {
+                if value.is_empty() {
+                    return Err(errors::invalid_argument(
+                        &format!("users[{i}].{field}"),
+                        "Must not be empty",
+                    ));
+                }
+            }
+            Ok(User::new(user.id, user.email))
+        })
+        .collect()
+}
+
+pub fn validate_data_subject(
+    user_id: Option<String>,
+    user_email: Option<String>,
+) -> Result<DataSubject, Status> {
+    let user_id = user_id.filter(|id| !id.is_empty());
+    let user_email = user_email.filter(|email| !email.is_empty());
+    match (user_id, user_email) {
+        (Some(id), Some(email)) => Ok(DataSubject::User(User::new(id, email))),
+        (Some(id), None) => Ok(DataSubject::Id(id)),
+        (None, Some(email)) => Ok(DataSubject::Email(email)),
+        (None, None) => {
+            let message = "Either user id or user email must be specified";
+            Err(
+                ErrorBuilder::new(Code::InvalidArgument, Reason::InvalidArgument, message)
+                    .field_violation("user_id", message)
+                    .field_violation("user_email", message)
+                    .build(),
+            )
+        }
+    }
 }
@@ -3,4 +3,4 @@ mod contracts_info_validators;
 mod health;
 
 pub use contracts_info::{ChainClients, ContractsInfoService};
-pub use health::HealthService;
+pub use health::{HealthService, Statuses, DATABASE_COMPONENT};
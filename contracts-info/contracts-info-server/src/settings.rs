@@ -2,11 +2,17 @@ use blockscout_service_launcher::{
     JaegerSettings, MetricsSettings, ServerSettings, TracingSettings,
 };
 use config::{Config, File};
-use contracts_info_core::TokenInfoProviderLevel;
+use contracts_info_core::{
+    verify::{OwnershipResolver, DEFAULT_OWNERSHIP_RESOLVERS, SINGLETON_FACTORY_ADDRESSES},
+    TokenInfoProviderLevel, DEFAULT_RPC_TIMEOUT,
+};
+use ethers::types::Address;
 use serde::{de, Deserialize};
+pub use service_common::{auth::AuthCacheSettings, idempotency::IdempotencySettings};
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
+    time::Duration,
 };
 use url::Url;
 
@@ -46,9 +52,29 @@ pub struct Settings {
     #[serde(default = "default_max_verified_addresses")]
     pub max_verified_addresses: u64,
 
+    /// Require the threshold of Safe owners to sign the verification message
+    /// of contracts owned by Safe wallets, instead of any single owner.
+    #[serde(default)]
+    pub require_safe_threshold: bool,
+
+    /// Accept legacy verification messages without nonces, so that clients
+    /// which have not switched to messages with nonces keep working.
+    /// Deprecated, will be disabled by default and then removed.
+    #[serde(default = "default_accept_messages_without_nonce")]
+    pub accept_messages_without_nonce: bool,
+
     #[serde(default)]
     pub api_keys: HashMap<String, ApiKey>,
 
+    #[serde(default)]
+    pub auth_cache: AuthCacheSettings,
+
+    #[serde(default)]
+    pub health_check: HealthCheckSettings,
+
+    #[serde(default)]
+    pub idempotency: IdempotencySettings,
+
     // Is required as we deny unknown fields, but allow users provide
     // path to config through PREFIX__CONFIG env variable. If removed,
     // the setup would fail with `unknown field `config`, expected one of...`
@@ -68,6 +94,46 @@ pub struct DatabaseSettings {
 pub struct ChainConfig {
     pub url: Url,
     pub api_key: Option<String>,
+    /// JSON-RPC endpoint of the chain, required to verify
+    /// signatures of smart contract wallets (EIP-1271)
+    /// and owners of contracts without verified source code.
+    #[serde(default)]
+    pub rpc_url: Option<Url>,
+    // How long a single request to the JSON-RPC endpoint may take
+    #[serde(default = "default_rpc_timeout_secs")]
+    pub rpc_timeout_secs: u64,
+    // How long signing messages are valid since they have been issued
+    #[serde(default = "default_message_validity_secs")]
+    pub message_validity_secs: u64,
+    // How far in the future message timestamps may be due to clock differences
+    #[serde(default = "default_max_clock_skew_secs")]
+    pub max_clock_skew_secs: u64,
+    // Sources of accounts allowed to verify contracts besides their creators
+    #[serde(default = "default_ownership_resolvers")]
+    pub ownership_resolvers: Vec<OwnershipResolver>,
+    // Factories deploying contracts on behalf of the creation transaction sender
+    #[serde(default = "default_deployer_factories")]
+    pub deployer_factories: Vec<Address>,
+    // Treat the creation transaction sender as the creator of contracts created by contracts
+    #[serde(default)]
+    pub resolve_contract_creators: bool,
+    // How many contracts (e.g. timelocks) may be between the contract owner and the signer
+    #[serde(default)]
+    pub max_ownership_chain_depth: usize,
+}
+
+impl ChainConfig {
+    pub fn rpc_timeout(&self) -> Duration {
+        Duration::from_secs(self.rpc_timeout_secs)
+    }
+
+    pub fn message_validity(&self) -> Duration {
+        Duration::from_secs(self.message_validity_secs)
+    }
+
+    pub fn max_clock_skew(&self) -> Duration {
+        Duration::from_secs(self.max_clock_skew_secs)
+    }
 }
 
 #[derive(Debug, Default, Clone, Deserialize, PartialEq, Eq)]
@@ -97,6 +163,34 @@ pub struct ApiKey {
     pub level: TokenInfoProviderLevel,
 }
 
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields, default)]
+pub struct HealthCheckSettings {
+    // How often the database and blockscout instances are checked
+    pub interval_secs: u64,
+    // How long a single dependency check may take before it is considered failed
+    pub timeout_secs: u64,
+}
+
+impl Default for HealthCheckSettings {
+    fn default() -> Self {
+        Self {
+            interval_secs: 30,
+            timeout_secs: 5,
+        }
+    }
+}
+
+impl HealthCheckSettings {
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_secs)
+    }
+
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs)
+    }
+}
+
 fn default_chains_config_path() -> PathBuf {
     "./config/networks.json".try_into().unwrap()
 }
@@ -105,6 +199,30 @@ fn default_max_verified_addresses() -> u64 {
     100
 }
 
+fn default_accept_messages_without_nonce() -> bool {
+    true
+}
+
+fn default_rpc_timeout_secs() -> u64 {
+    DEFAULT_RPC_TIMEOUT.as_secs()
+}
+
+fn default_message_validity_secs() -> u64 {
+    24 * 60 * 60
+}
+
+fn default_max_clock_skew_secs() -> u64 {
+    5 * 60
+}
+
+fn default_ownership_resolvers() -> Vec<OwnershipResolver> {
+    DEFAULT_OWNERSHIP_RESOLVERS.to_vec()
+}
+
+fn default_deployer_factories() -> Vec<Address> {
+    SINGLETON_FACTORY_ADDRESSES.clone()
+}
+
 fn default_auth_key_level() -> TokenInfoProviderLevel {
     TokenInfoProviderLevel::Extractor
 }
@@ -139,9 +257,14 @@ impl Settings {
             },
             chains_config_path: Default::default(),
             max_verified_addresses: default_max_verified_addresses(),
+            require_safe_threshold: false,
+            accept_messages_without_nonce: default_accept_messages_without_nonce(),
             chains_config: Default::default(),
             config_path: Default::default(),
             api_keys: Default::default(),
+            auth_cache: Default::default(),
+            health_check: Default::default(),
+            idempotency: Default::default(),
         }
     }
 }
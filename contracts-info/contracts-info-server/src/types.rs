@@ -1,16 +1,36 @@
 use std::str::FromStr;
 
+use crate::errors;
 use blockscout_display_bytes::Bytes as DisplayBytes;
-use contracts_info_core::TokenInfo;
+use contracts_info_core::{localization, LocalizedDescription, TokenInfo};
 
 pub fn token_info_from_proto(proto: crate::proto::TokenInfo) -> Result<TokenInfo, tonic::Status> {
     let token_address = DisplayBytes::from_str(&proto.token_address)
-        .map_err(|e| tonic::Status::invalid_argument(e.to_string()))?;
+        .map_err(|e| errors::invalid_argument("token_info.token_address", e.to_string()))?;
     if token_address.len() != 20 {
-        return Err(tonic::Status::invalid_argument(
+        return Err(errors::invalid_argument(
+            "token_info.token_address",
             "invalid address length, expected 20",
         ));
     };
+    let localized_descriptions = proto
+        .localized_descriptions
+        .into_iter()
+        .enumerate()
+        .map(|(i, localized)| {
+            if !localization::is_language_tag(&localized.language) {
+                return Err(errors::invalid_argument(
+                    &format!("token_info.localized_descriptions[{i}].language"),
+                    format!("{} is not a valid BCP-47 language tag", localized.language),
+                ));
+            }
+            Ok(LocalizedDescription {
+                language: localized.language,
+                project_name: localized.project_name,
+                project_description: localized.project_description,
+            })
+        })
+        .collect::<Result<_, _>>()?;
     Ok(TokenInfo {
         token_address,
         chain_id: proto.chain_id as i64,
@@ -37,5 +57,6 @@ pub fn token_info_from_proto(proto: crate::proto::TokenInfo) -> Result<TokenInfo
         defi_llama_ticker: proto.defi_llama_ticker,
         token_name: proto.token_name,
         token_symbol: proto.token_symbol,
+        localized_descriptions,
     })
 }
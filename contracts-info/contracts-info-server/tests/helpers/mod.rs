@@ -3,5 +3,6 @@ mod server_initializer;
 
 pub use database_helpers::init_db;
 pub use server_initializer::{
-    blockscout_server, expect_blockscout_auth_mock, init_contracts_info_server, USER_EMAIL,
+    blockscout_server, expect_blockscout_auth_mock, init_contracts_info_server, mock_user_id,
+    USER_EMAIL,
 };
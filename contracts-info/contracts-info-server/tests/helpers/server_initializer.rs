@@ -68,6 +68,13 @@ pub async fn blockscout_server() -> MockServer {
 
 pub const USER_EMAIL: &str = "user@gmail.com";
 
+/// Blockscout user id of the mocked user. Derived from the email,
+/// so that different mocked users have different ids.
+pub fn mock_user_id(user_email: &str) -> u32 {
+    let digest = md5::compute(user_email);
+    u32::from_le_bytes(digest.0[..4].try_into().unwrap())
+}
+
 pub async fn expect_blockscout_auth_mock(
     mock_server: &MockServer,
     users: impl IntoIterator<Item = MockUser>,
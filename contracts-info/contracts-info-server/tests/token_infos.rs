@@ -7,6 +7,7 @@ const TEST_SUITE_NAME: &str = "token_infos";
 
 mod common {
     use super::*;
+    use crate::helpers::mock_user_id;
     use blockscout_auth::MockUser;
     use blockscout_display_bytes::Bytes as DisplayBytes;
     use entity::{token_infos, verified_addresses};
@@ -96,6 +97,8 @@ mod common {
             defi_llama_ticker: Some("defi_llama_ticker".to_string()),
             token_name: Some("token_name".into()),
             token_symbol: Some("token_symbol".into()),
+            localized_descriptions: vec![],
+            language: None,
         }
     }
 
@@ -123,7 +126,7 @@ mod common {
 
     pub fn mock_user(user_email: &str, chain_id: i64) -> MockUser {
         MockUser {
-            id: 0,
+            id: mock_user_id(user_email).into(),
             email: user_email.to_string(),
             chain_id,
             jwt: user_email.into(),
@@ -136,6 +139,7 @@ mod get_token_info {
     use super::{common::*, *};
     use crate::helpers::init_contracts_info_server;
     use const_format::concatcp;
+    use sea_orm::ActiveValue::Set;
 
     const ROUTE_TEMPLATE: &str = "/api/v1/chains/{CHAIN_ID}/token-infos/{TOKEN_ADDRESS}";
 
@@ -192,6 +196,88 @@ mod get_token_info {
         assert_eq!(expected, token_info, "Invalid token info returned");
     }
 
+    #[tokio::test]
+    async fn selects_localized_description() {
+        let db = init_db(MOD_TEST_SUITE_NAME, "selects_localized_description").await;
+        let db_url = db.db_url();
+
+        let chain_id = 1;
+        let token_address = "0xcafecafecafecafecafecafecafecafecafecafe";
+        let project_name = "project 1";
+
+        let contracts_info_base = init_contracts_info_server(db_url, [chain_id], None).await;
+
+        let mut token_info =
+            init_token_info_active_model(token_address, chain_id, project_name.into());
+        token_info.localized_descriptions = Set(serde_json::json!([
+            {
+                "language": "de",
+                "projectName": "Projekt 1",
+                "projectDescription": "Projektbeschreibung",
+            },
+            {
+                "language": "pt-BR",
+                "projectDescription": "Descrição do projeto",
+            },
+        ]));
+        fill_database(db.client().as_ref(), [], [token_info]).await;
+
+        let route = ROUTE_TEMPLATE
+            .replace("{CHAIN_ID}", &format!("{chain_id}"))
+            .replace("{TOKEN_ADDRESS}", token_address);
+        let get = |accept_language: &str, query: &str| {
+            reqwest::Client::new()
+                .get(
+                    contracts_info_base
+                        .join(&format!("{route}{query}"))
+                        .unwrap(),
+                )
+                .header("accept-language", accept_language)
+                .send()
+        };
+
+        let mut expected = expected_token_info(token_address, chain_id as u64, project_name.into());
+        expected.localized_descriptions = vec![
+            contracts_info_v1::LocalizedDescription {
+                language: "de".into(),
+                project_name: Some("Projekt 1".into()),
+                project_description: "Projektbeschreibung".into(),
+            },
+            contracts_info_v1::LocalizedDescription {
+                language: "pt-BR".into(),
+                project_name: None,
+                project_description: "Descrição do projeto".into(),
+            },
+        ];
+
+        // No translation matches the header
+        let response = get("fr, it;q=0.8", "")
+            .await
+            .expect("Failed to send request");
+        let token_info = validate_response(response).await;
+        assert_eq!(expected, token_info, "Invalid default token info");
+
+        let response = get("de-CH, en;q=0.5", "")
+            .await
+            .expect("Failed to send request");
+        let token_info = validate_response(response).await;
+        let mut german = expected.clone();
+        german.project_name = Some("Projekt 1".into());
+        german.project_description = "Projektbeschreibung".into();
+        german.language = Some("de".into());
+        assert_eq!(german, token_info, "Invalid german token info");
+
+        // The request parameter takes precedence over the header
+        let response = get("de", "?acceptLanguage=pt")
+            .await
+            .expect("Failed to send request");
+        let token_info = validate_response(response).await;
+        let mut portuguese = expected;
+        portuguese.project_description = "Descrição do projeto".into();
+        portuguese.language = Some("pt-BR".into());
+        assert_eq!(portuguese, token_info, "Invalid portuguese token info");
+    }
+
     #[tokio::test]
     async fn returns_empty_if_not_found() {
         let db = init_db(MOD_TEST_SUITE_NAME, "returns_empty_if_not_found").await;
@@ -493,6 +579,38 @@ mod list_user_token_infos {
         assert_eq!(expected, token_infos[0], "Invalid token info");
     }
 
+    #[tokio::test]
+    async fn authentication_result_is_cached() {
+        let db = init_db(MOD_TEST_SUITE_NAME, "authentication_result_is_cached").await;
+        let db_url = db.db_url();
+        let blockscout_server = blockscout_server().await;
+
+        let chain_id = 1;
+        let user_id = "1";
+        let contracts_info_base =
+            init_contracts_info_server(db_url, [chain_id], Some(&blockscout_server.uri())).await;
+
+        for _ in 0..3 {
+            make_request(
+                &blockscout_server,
+                contracts_info_base.clone(),
+                chain_id,
+                user_id,
+            )
+            .await;
+        }
+
+        let auth_requests = blockscout_server
+            .received_requests()
+            .await
+            .expect("Request recording is enabled by default");
+        assert_eq!(
+            1,
+            auth_requests.len(),
+            "Blockscout should be requested only once"
+        );
+    }
+
     #[tokio::test]
     async fn request_without_authentication_token_fails() {
         let db = init_db(
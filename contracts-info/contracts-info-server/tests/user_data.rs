@@ -0,0 +1,246 @@
+mod helpers;
+
+use crate::helpers::{
+    blockscout_server, expect_blockscout_auth_mock, init_contracts_info_server, init_db,
+    mock_user_id,
+};
+use blockscout_auth::MockUser;
+use blockscout_display_bytes::Bytes as DisplayBytes;
+use contracts_info_core::handlers::ANONYMIZED_EMAIL;
+use contracts_info_proto::blockscout::contracts_info::v1 as contracts_info_v1;
+use entity::{token_infos, verified_addresses};
+use reqwest::{Response, StatusCode};
+use sea_orm::{ActiveValue::Set, DbConn, EntityTrait};
+use std::str::FromStr;
+use url::Url;
+use wiremock::MockServer;
+
+const TEST_SUITE_NAME: &str = "user_data";
+
+const CSRF_TOKEN: &str = "CSRF_TOKEN";
+
+const EXPORT_ROUTE_TEMPLATE: &str = "/api/v1/chains/{CHAIN_ID}/user-data";
+const ERASE_ROUTE_TEMPLATE: &str = "/api/v1/chains/{CHAIN_ID}/user-data:erase";
+const EXPORT_ADMIN_ROUTE_TEMPLATE: &str = "/api/v1/chains/{CHAIN_ID}/admin/user-data:export";
+const EXPORT_ALL_CHAINS_ADMIN_ROUTE: &str = "/api/v1/admin/user-data:export";
+const ERASE_ALL_CHAINS_ADMIN_ROUTE: &str = "/api/v1/admin/user-data:erase";
+
+fn mock_user(user_email: &str, chain_id: i64) -> MockUser {
+    MockUser {
+        id: mock_user_id(user_email).into(),
+        email: user_email.to_string(),
+        chain_id,
+        jwt: user_email.into(),
+        csrf_token: CSRF_TOKEN.into(),
+    }
+}
+
+async fn fill_database(db: &DbConn, chain_id: i64, data: &[(&str, &str)]) {
+    let verified_addresses = data.iter().map(|(address, owner_email)| {
+        let address = DisplayBytes::from_str(address).expect("Address is invalid hex");
+        verified_addresses::ActiveModel {
+            address: Set(address.to_string()),
+            chain_id: Set(chain_id),
+            owner_email: Set(owner_email.to_string()),
+            ..Default::default()
+        }
+    });
+    verified_addresses::Entity::insert_many(verified_addresses)
+        .exec(db)
+        .await
+        .expect("Predefined verified addresses insertion failed");
+
+    let token_infos = data.iter().map(|(address, _)| {
+        let address = DisplayBytes::from_str(address).expect("Address is invalid hex");
+        token_infos::ActiveModel {
+            address: Set(address.to_string()),
+            chain_id: Set(chain_id),
+            project_website: Set("project-website.com".into()),
+            project_email: Set("project_email@mail.com".into()),
+            icon_url: Set("icon.com".into()),
+            project_description: Set("Project description".into()),
+            ..Default::default()
+        }
+    });
+    token_infos::Entity::insert_many(token_infos)
+        .exec(db)
+        .await
+        .expect("Predefined token infos insertion failed");
+}
+
+async fn assert_success(response: Response) -> Response {
+    if !response.status().is_success() {
+        let status = response.status();
+        let message = response.text().await.expect("Read body as text");
+        panic!("Invalid status code (success expected). Status: {status}. Message: {message}")
+    }
+    response
+}
+
+async fn export_user_data(
+    blockscout_server: &MockServer,
+    contracts_info_base: &Url,
+    chain_id: i64,
+    user_email: &str,
+) -> contracts_info_v1::UserData {
+    expect_blockscout_auth_mock(blockscout_server, [mock_user(user_email, chain_id)]).await;
+
+    let route = EXPORT_ROUTE_TEMPLATE.replace("{CHAIN_ID}", &format!("{chain_id}"));
+    let response = reqwest::Client::new()
+        .get(contracts_info_base.join(route.as_str()).unwrap())
+        .header("cookie", &format!("_explorer_key={user_email}"))
+        .send()
+        .await
+        .expect("Failed to send request");
+
+    assert_success(response)
+        .await
+        .json()
+        .await
+        .expect("Response deserialization failed")
+}
+
+async fn erase_user_data(
+    blockscout_server: &MockServer,
+    contracts_info_base: &Url,
+    chain_id: i64,
+    user_email: &str,
+) -> contracts_info_v1::EraseUserDataResponse {
+    expect_blockscout_auth_mock(blockscout_server, [mock_user(user_email, chain_id)]).await;
+
+    let route = ERASE_ROUTE_TEMPLATE.replace("{CHAIN_ID}", &format!("{chain_id}"));
+    let response = reqwest::Client::new()
+        .post(contracts_info_base.join(route.as_str()).unwrap())
+        .json(&serde_json::json!({}))
+        .header("cookie", &format!("_explorer_key={user_email}"))
+        .header("x-csrf-token", CSRF_TOKEN)
+        .send()
+        .await
+        .expect("Failed to send request");
+
+    assert_success(response)
+        .await
+        .json()
+        .await
+        .expect("Response deserialization failed")
+}
+
+#[tokio::test]
+async fn export_and_erase_user_data() {
+    let db = init_db(TEST_SUITE_NAME, "export_and_erase_user_data").await;
+    let db_url = db.db_url();
+    let blockscout_server = blockscout_server().await;
+
+    let chain_id = 1;
+    let user_email = "user@gmail.com";
+    let another_user_email = "another@gmail.com";
+
+    let contracts_info_base =
+        init_contracts_info_server(db_url, [chain_id], Some(&blockscout_server.uri())).await;
+
+    fill_database(
+        db.client().as_ref(),
+        chain_id,
+        &[
+            ("0xcafecafecafecafecafecafecafecafecafeca01", user_email),
+            (
+                "0xcafecafecafecafecafecafecafecafecafeca02",
+                another_user_email,
+            ),
+            ("0xcafecafecafecafecafecafecafecafecafeca03", user_email),
+        ],
+    )
+    .await;
+
+    let user_data = export_user_data(
+        &blockscout_server,
+        &contracts_info_base,
+        chain_id,
+        user_email,
+    )
+    .await;
+    assert_eq!(
+        2,
+        user_data.verified_addresses.len(),
+        "Invalid number of exported verified addresses"
+    );
+    assert_eq!(
+        2,
+        user_data.token_infos.len(),
+        "Invalid number of exported token infos"
+    );
+    assert!(user_data
+        .verified_addresses
+        .iter()
+        .all(|address| address.user_id == user_email));
+
+    let response = erase_user_data(
+        &blockscout_server,
+        &contracts_info_base,
+        chain_id,
+        user_email,
+    )
+    .await;
+    assert_eq!(
+        2, response.anonymized_verified_addresses,
+        "Invalid number of anonymized verified addresses"
+    );
+
+    // Addresses are kept, but the owner email is anonymized
+    let user_data = export_user_data(
+        &blockscout_server,
+        &contracts_info_base,
+        chain_id,
+        user_email,
+    )
+    .await;
+    assert_eq!(2, user_data.verified_addresses.len());
+    assert!(user_data
+        .verified_addresses
+        .iter()
+        .all(|address| address.user_id == ANONYMIZED_EMAIL));
+
+    // Another user data is untouched
+    let another_user_data = export_user_data(
+        &blockscout_server,
+        &contracts_info_base,
+        chain_id,
+        another_user_email,
+    )
+    .await;
+    assert_eq!(1, another_user_data.verified_addresses.len());
+    assert_eq!(
+        another_user_email,
+        another_user_data.verified_addresses[0].user_id
+    );
+}
+
+#[tokio::test]
+async fn admin_request_without_api_key_fails() {
+    let db = init_db(TEST_SUITE_NAME, "admin_request_without_api_key_fails").await;
+    let db_url = db.db_url();
+
+    let chain_id = 1;
+    let contracts_info_base = init_contracts_info_server(db_url, [chain_id], None).await;
+
+    for route in [
+        EXPORT_ADMIN_ROUTE_TEMPLATE.replace("{CHAIN_ID}", &format!("{chain_id}")),
+        EXPORT_ALL_CHAINS_ADMIN_ROUTE.to_string(),
+        ERASE_ALL_CHAINS_ADMIN_ROUTE.to_string(),
+    ] {
+        let response = reqwest::Client::new()
+            .post(contracts_info_base.join(route.as_str()).unwrap())
+            .json(&serde_json::json!({ "userEmail": "user@gmail.com" }))
+            .send()
+            .await
+            .expect("Failed to send request");
+
+        if response.status() != StatusCode::UNAUTHORIZED {
+            let status = response.status();
+            let message = response.text().await.expect("Read body as text");
+            panic!(
+                "Invalid status code for {route} (unauthorized expected). Status: {status}. Message: {message}"
+            )
+        }
+    }
+}
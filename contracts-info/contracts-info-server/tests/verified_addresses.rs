@@ -9,7 +9,7 @@ const TEST_SUITE_NAME: &str = "verified_addresses";
 
 mod common {
     use super::*;
-    use crate::helpers::expect_blockscout_auth_mock;
+    use crate::helpers::{expect_blockscout_auth_mock, mock_user_id};
     use blockscout_auth::MockUser;
     use chrono::{DateTime, Utc};
     use contracts_info_v1::AddressMetadata;
@@ -20,8 +20,6 @@ mod common {
     use url::Url;
     use wiremock::{matchers, Mock, MockServer};
 
-    pub const TS_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
-
     pub const CSRF_TOKEN: &str = "CSRF_TOKEN";
 
     const VERIFY_ADDRESS_ROUTE_TEMPLATE: &str =
@@ -95,9 +93,28 @@ mod common {
         LocalWallet::new(&mut rand::thread_rng())
     }
 
-    pub fn generate_message(timestamp: DateTime<Utc>, contract_address_display: &str) -> String {
-        let timestamp_display = timestamp.format(TS_FORMAT).to_string();
-        format!("[127.0.0.1] [{timestamp_display}] I, hereby verify that I am the owner/creator of the address [{contract_address_display}]")
+    /// Returns the signing message with a nonce issued to the user.
+    pub async fn prepare_message(
+        blockscout_server: &MockServer,
+        contracts_info_base: &Url,
+        chain_id: u64,
+        contract_address: &str,
+        user_email: &str,
+    ) -> String {
+        let prepare_address_response = make_prepare_request(
+            blockscout_server,
+            contracts_info_base,
+            chain_id as i64,
+            contract_address,
+            user_email,
+        )
+        .await;
+        match prepare_address_response.details {
+            Some(contracts_info_v1::prepare_address_response::Details::Result(result)) => {
+                result.signing_message
+            }
+            _ => panic!("Invalid details"),
+        }
     }
 
     pub fn expected_verified_address(
@@ -115,6 +132,7 @@ mod common {
                 token_name: Some("Some name".into()),
                 token_symbol: None,
             }),
+            ownership_chain: vec![],
         }
     }
 
@@ -168,6 +186,7 @@ mod common {
             contract_address,
             message,
             signature,
+            signer: None,
         };
 
         make_post_request(
@@ -215,7 +234,7 @@ mod common {
 
     pub fn mock_user(user_email: &str, chain_id: i64) -> MockUser {
         MockUser {
-            id: 0,
+            id: mock_user_id(user_email).into(),
             email: user_email.into(),
             chain_id,
             jwt: user_email.into(),
@@ -229,6 +248,7 @@ mod prepare_address {
 
     use super::{common::*, *};
     use crate::helpers::blockscout_server;
+    use chrono::DateTime;
     use const_format::concatcp;
     use contracts_info_core::verify::Message;
     use ethers::{signers::Signer, types::Address};
@@ -271,11 +291,25 @@ mod prepare_address {
                 let msg = Message::from_str(&result.signing_message).expect("Invalid message");
                 assert_eq!(msg.address, Address::from_str(contract_address).unwrap());
                 assert_eq!(msg.site, "127.0.0.1");
+                let expires_at = DateTime::parse_from_rfc3339(&result.expires_at)
+                    .expect("Invalid expiration time")
+                    .naive_utc();
+                assert_eq!(
+                    msg.timestamp.timestamp() + 24 * 60 * 60,
+                    expires_at.timestamp()
+                );
 
                 let expected_result = contracts_info_v1::prepare_address_response::Success {
                     signing_message: result.signing_message.clone(),
                     contract_creator: signer.to_string(),
                     contract_owner: None,
+                    contract_owner_safe: None,
+                    expires_at: result.expires_at.clone(),
+                    contract_owners: vec![],
+                    contract_creator_resolution: <i32>::from(
+                        contracts_info_v1::CreatorResolution::Sender,
+                    ),
+                    contract_deployer: None,
                 };
                 assert_eq!(&expected_result, result, "Invalid result");
             }
@@ -317,7 +351,14 @@ mod verify_address {
                 .await;
 
         let timestamp = Utc::now();
-        let message = generate_message(timestamp, contract_address);
+        let message = prepare_message(
+            &blockscout_server,
+            &contracts_info_base,
+            chain_id,
+            contract_address,
+            user_email,
+        )
+        .await;
         let response = insert_verify_address(
             &blockscout_server,
             contracts_info_base,
@@ -450,6 +491,75 @@ mod verify_address {
         assert_eq!(prepare_address_response.details, None);
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn replayed_nonce_error(wallet: Wallet<SigningKey>) {
+        let contract_address = "0xcafecafecafecafecafecafecafecafecafecafe";
+        let signer = DisplayBytes::from(wallet.address().to_fixed_bytes());
+
+        let db = init_db(MOD_TEST_SUITE_NAME, "replayed_nonce_error").await;
+        let db_url = db.db_url();
+        let blockscout_server = blockscout_server().await;
+        init_blockscout_mock(
+            &blockscout_server,
+            [(contract_address, signer.to_string().as_str())],
+        )
+        .await;
+        let user_email = "1";
+        let chain_id = wallet.chain_id();
+        let contracts_info_base =
+            init_contracts_info_server(db_url, [chain_id as i64], Some(&blockscout_server.uri()))
+                .await;
+
+        let message = prepare_message(
+            &blockscout_server,
+            &contracts_info_base,
+            chain_id,
+            contract_address,
+            user_email,
+        )
+        .await;
+        let verify = |user_email: &str| {
+            insert_verify_address(
+                &blockscout_server,
+                contracts_info_base.clone(),
+                wallet.clone(),
+                user_email.to_string(),
+                chain_id,
+                contract_address.to_string(),
+                message.clone(),
+            )
+        };
+
+        // Another user cannot use the message signed for the first one
+        let another_user_email = "2";
+        let verify_address_response: contracts_info_v1::VerifyAddressResponse =
+            verify(another_user_email)
+                .await
+                .json()
+                .await
+                .expect("Response deserialization failed");
+        assert_eq!(
+            contracts_info_v1::verify_address_response::Status::InvalidNonceError,
+            verify_address_response.status.try_into().unwrap(),
+            "Invalid status"
+        );
+
+        let response = verify(user_email).await;
+        assert!(response.status().is_success());
+
+        let verify_address_response: contracts_info_v1::VerifyAddressResponse = verify(user_email)
+            .await
+            .json()
+            .await
+            .expect("Response deserialization failed");
+        assert_eq!(
+            contracts_info_v1::verify_address_response::Status::InvalidNonceError,
+            verify_address_response.status.try_into().unwrap(),
+            "Invalid status"
+        );
+    }
+
     #[rstest]
     #[tokio::test]
     async fn test_invalid_signer_error(wallet: Wallet<SigningKey>) {
@@ -474,7 +584,14 @@ mod verify_address {
 
         let fake_wallet = common::wallet();
         let timestamp = Utc::now();
-        let message = generate_message(timestamp, contract_address);
+        let message = prepare_message(
+            &blockscout_server,
+            &contracts_info_base,
+            chain_id,
+            contract_address,
+            user_email,
+        )
+        .await;
         let response = insert_verify_address(
             &blockscout_server,
             contracts_info_base,
@@ -568,7 +685,14 @@ mod list_user_verified_addresses {
                 .await;
 
         let timestamp = Utc::now();
-        let message = generate_message(timestamp, contract_address_1);
+        let message = prepare_message(
+            &blockscout_server,
+            &contracts_info_base,
+            chain_id,
+            contract_address_1,
+            user_email,
+        )
+        .await;
         let _response = insert_verify_address(
             &blockscout_server,
             contracts_info_base.clone(),
@@ -579,7 +703,14 @@ mod list_user_verified_addresses {
             message,
         )
         .await;
-        let message = generate_message(timestamp, contract_address_2);
+        let message = prepare_message(
+            &blockscout_server,
+            &contracts_info_base,
+            chain_id,
+            contract_address_2,
+            another_user_email,
+        )
+        .await;
         let _response = insert_verify_address(
             &blockscout_server,
             contracts_info_base.clone(),
@@ -590,7 +721,14 @@ mod list_user_verified_addresses {
             message,
         )
         .await;
-        let message = generate_message(timestamp, contract_address_3);
+        let message = prepare_message(
+            &blockscout_server,
+            &contracts_info_base,
+            chain_id,
+            contract_address_3,
+            user_email,
+        )
+        .await;
         let _response = insert_verify_address(
             &blockscout_server,
             contracts_info_base.clone(),
@@ -718,7 +856,14 @@ mod get_verified_address_owner_admin {
                 .await;
 
         let timestamp = Utc::now();
-        let message = generate_message(timestamp, contract_address);
+        let message = prepare_message(
+            &blockscout_server,
+            &contracts_info_base,
+            chain_id,
+            contract_address,
+            user_email,
+        )
+        .await;
         let _response = insert_verify_address(
             &blockscout_server,
             contracts_info_base.clone(),
@@ -788,7 +933,14 @@ mod get_verified_address_owner_admin {
         .await;
 
         let timestamp = Utc::now();
-        let message = generate_message(timestamp, contract_address);
+        let message = prepare_message(
+            &blockscout_server,
+            &contracts_info_base,
+            chain_id,
+            contract_address,
+            user_email,
+        )
+        .await;
         let _response = insert_verify_address(
             &blockscout_server,
             contracts_info_base.clone(),
@@ -67,6 +67,8 @@ impl TokenInfo {
             // token_symbol: self.token_symbol,
             token_name: None,
             token_symbol: None,
+            localized_descriptions: vec![],
+            language: None,
         }
     }
 
@@ -0,0 +1,355 @@
+use lazy_static::lazy_static;
+use prometheus::{register_int_counter_vec, IntCounterVec};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use tonic::{metadata::MetadataMap, Code, Status};
+use url::Url;
+
+lazy_static! {
+    static ref AUTH_CACHE_REQUESTS: IntCounterVec = register_int_counter_vec!(
+        "blockscout_auth_cache_requests_total",
+        "number of blockscout authentication cache lookups by result",
+        &["result"]
+    )
+    .unwrap();
+}
+
+/// Headers that carry user credentials (session cookie, jwt and csrf token).
+const CREDENTIAL_HEADERS: [&str; 3] = ["cookie", "authorization", "x-csrf-token"];
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields, default)]
+pub struct AuthCacheSettings {
+    pub enabled: bool,
+    // How long successful authentication results are cached
+    pub ttl_secs: u64,
+    // How long rejected credentials are cached
+    pub negative_ttl_secs: u64,
+    pub max_entries: usize,
+}
+
+impl Default for AuthCacheSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl_secs: 60,
+            negative_ttl_secs: 10,
+            max_entries: 10_000,
+        }
+    }
+}
+
+impl AuthCacheSettings {
+    pub fn ttl(&self) -> Duration {
+        Duration::from_secs(self.ttl_secs)
+    }
+
+    pub fn negative_ttl(&self) -> Duration {
+        Duration::from_secs(self.negative_ttl_secs)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CacheKey([u8; 32]);
+
+impl CacheKey {
+    /// Hashes user credentials together with the blockscout instance they are
+    /// checked against. Returns `None` if the request contains no credentials.
+    pub fn new(metadata: &MetadataMap, is_method_safe: bool, endpoint: &Url) -> Option<Self> {
+        let mut hasher = Sha256::new();
+        hasher.update(endpoint.as_str());
+        hasher.update([is_method_safe as u8]);
+        let mut has_credentials = false;
+        for header in CREDENTIAL_HEADERS {
+            for value in metadata.get_all(header) {
+                has_credentials = true;
+                hasher.update(header);
+                hasher.update(value.as_bytes());
+                // Separator, so that values of different headers cannot be mixed up
+                hasher.update([0u8]);
+            }
+        }
+        has_credentials.then(|| Self(hasher.finalize().into()))
+    }
+}
+
+#[derive(Debug, Clone)]
+enum CachedResult<U> {
+    Authenticated(U),
+    Rejected {
+        code: Code,
+        message: String,
+        // Encoded `google.rpc.Status` details
+        details: Vec<u8>,
+    },
+}
+
+#[derive(Debug)]
+struct CacheEntry<U> {
+    result: CachedResult<U>,
+    expires_at: Instant,
+}
+
+/// In-memory cache of blockscout authentication results, so that every request
+/// does not require a round-trip to blockscout. Successful results are stored
+/// for `ttl`, rejected credentials for `negative_ttl`.
+pub struct AuthCache<U> {
+    settings: AuthCacheSettings,
+    entries: Mutex<HashMap<CacheKey, CacheEntry<U>>>,
+}
+
+impl<U: Clone> AuthCache<U> {
+    pub fn new(settings: AuthCacheSettings) -> Self {
+        Self {
+            settings,
+            entries: Default::default(),
+        }
+    }
+
+    pub fn get(&self, key: &CacheKey) -> Option<Result<U, Status>> {
+        if !self.settings.enabled {
+            return None;
+        }
+
+        let now = Instant::now();
+        let mut entries = self.entries.lock().expect("auth cache lock is poisoned");
+        let result = match entries.get(key) {
+            Some(entry) if entry.expires_at > now => entry.result.clone(),
+            Some(_) => {
+                entries.remove(key);
+                AUTH_CACHE_REQUESTS.with_label_values(&["miss"]).inc();
+                return None;
+            }
+            None => {
+                AUTH_CACHE_REQUESTS.with_label_values(&["miss"]).inc();
+                return None;
+            }
+        };
+
+        match result {
+            CachedResult::Authenticated(user) => {
+                AUTH_CACHE_REQUESTS.with_label_values(&["hit"]).inc();
+                Some(Ok(user))
+            }
+            CachedResult::Rejected {
+                code,
+                message,
+                details,
+            } => {
+                AUTH_CACHE_REQUESTS
+                    .with_label_values(&["negative_hit"])
+                    .inc();
+                Some(Err(Status::with_details(code, message, details.into())))
+            }
+        }
+    }
+
+    pub fn insert_authenticated(&self, key: CacheKey, user: U) {
+        self.insert(key, CachedResult::Authenticated(user), self.settings.ttl())
+    }
+
+    pub fn insert_rejected(&self, key: CacheKey, status: &Status) {
+        let result = CachedResult::Rejected {
+            code: status.code(),
+            message: status.message().to_string(),
+            details: status.details().to_vec(),
+        };
+        self.insert(key, result, self.settings.negative_ttl())
+    }
+
+    fn insert(&self, key: CacheKey, result: CachedResult<U>, ttl: Duration) {
+        if !self.settings.enabled {
+            return;
+        }
+
+        let now = Instant::now();
+        let mut entries = self.entries.lock().expect("auth cache lock is poisoned");
+        if entries.len() >= self.settings.max_entries && !entries.contains_key(&key) {
+            entries.retain(|_, entry| entry.expires_at > now);
+            if entries.len() >= self.settings.max_entries {
+                tracing::warn!(
+                    max_entries = self.settings.max_entries,
+                    "auth cache is full; result is not cached"
+                );
+                return;
+            }
+        }
+        entries.insert(
+            key,
+            CacheEntry {
+                result,
+                expires_at: now + ttl,
+            },
+        );
+    }
+}
+
+/// Authenticates the user against the blockscout instance at `endpoint`.
+/// Results are looked up in the cache first. `map_error` converts blockscout
+/// errors into the statuses returned by the service.
+pub async fn authenticate<U, F>(
+    cache: &AuthCache<U>,
+    metadata: &MetadataMap,
+    is_method_safe: bool,
+    endpoint: &Url,
+    api_key: Option<&str>,
+    map_error: F,
+) -> Result<U, Status>
+where
+    U: From<blockscout_auth::AuthSuccess> + Clone,
+    F: FnOnce(blockscout_auth::Error) -> Status,
+{
+    let cache_key = CacheKey::new(metadata, is_method_safe, endpoint);
+    if let Some(result) = cache_key.as_ref().and_then(|key| cache.get(key)) {
+        return result;
+    }
+
+    match blockscout_auth::auth_from_metadata(metadata, is_method_safe, endpoint, api_key).await {
+        Ok(success) => {
+            let user = U::from(success);
+            if let Some(key) = cache_key {
+                cache.insert_authenticated(key, user.clone());
+            }
+            Ok(user)
+        }
+        Err(err) => {
+            // Blockscout api and internal errors may be transient, so only
+            // explicitly rejected credentials are cached
+            let is_cacheable = matches!(
+                err,
+                blockscout_auth::Error::Unauthorized(_) | blockscout_auth::Error::InvalidJwt(_)
+            );
+            let status = map_error(err);
+            if let (true, Some(key)) = (is_cacheable, cache_key) {
+                cache.insert_rejected(key, &status);
+            }
+            Err(status)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone)]
+    struct User {
+        id: String,
+    }
+
+    fn metadata(cookie: &str) -> MetadataMap {
+        let mut metadata = MetadataMap::new();
+        metadata.insert("cookie", cookie.parse().unwrap());
+        metadata
+    }
+
+    fn user(id: &str) -> User {
+        User { id: id.to_string() }
+    }
+
+    fn settings() -> AuthCacheSettings {
+        AuthCacheSettings {
+            enabled: true,
+            ttl_secs: 60,
+            negative_ttl_secs: 10,
+            max_entries: 2,
+        }
+    }
+
+    #[test]
+    fn key_depends_on_credentials() {
+        let endpoint = Url::parse("http://localhost:8080").unwrap();
+        let other_endpoint = Url::parse("http://localhost:8081").unwrap();
+
+        let key = CacheKey::new(&metadata("_explorer_key=1"), true, &endpoint);
+        assert!(key.is_some());
+        assert_eq!(
+            key,
+            CacheKey::new(&metadata("_explorer_key=1"), true, &endpoint)
+        );
+        assert_ne!(
+            key,
+            CacheKey::new(&metadata("_explorer_key=2"), true, &endpoint)
+        );
+        assert_ne!(
+            key,
+            CacheKey::new(&metadata("_explorer_key=1"), false, &endpoint)
+        );
+        assert_ne!(
+            key,
+            CacheKey::new(&metadata("_explorer_key=1"), true, &other_endpoint)
+        );
+        assert_eq!(None, CacheKey::new(&MetadataMap::new(), true, &endpoint));
+    }
+
+    #[test]
+    fn stores_results() {
+        let endpoint = Url::parse("http://localhost:8080").unwrap();
+        let cache = AuthCache::new(settings());
+
+        let key_1 = CacheKey::new(&metadata("_explorer_key=1"), true, &endpoint).unwrap();
+        let key_2 = CacheKey::new(&metadata("_explorer_key=2"), true, &endpoint).unwrap();
+        assert!(cache.get(&key_1).is_none());
+
+        cache.insert_authenticated(key_1, user("1"));
+        cache.insert_rejected(
+            key_2,
+            &Status::with_details(Code::Unauthenticated, "invalid token", vec![1, 2, 3].into()),
+        );
+
+        let cached = cache.get(&key_1).unwrap().expect("user expected");
+        assert_eq!("1", cached.id);
+        let status = cache.get(&key_2).unwrap().expect_err("error expected");
+        assert_eq!(Code::Unauthenticated, status.code());
+        assert_eq!("invalid token", status.message());
+        assert_eq!(&[1, 2, 3], status.details());
+    }
+
+    #[test]
+    fn expired_entries_are_ignored() {
+        let endpoint = Url::parse("http://localhost:8080").unwrap();
+        let cache = AuthCache::new(AuthCacheSettings {
+            ttl_secs: 0,
+            ..settings()
+        });
+
+        let key = CacheKey::new(&metadata("_explorer_key=1"), true, &endpoint).unwrap();
+        cache.insert_authenticated(key, user("1"));
+        assert!(cache.get(&key).is_none());
+    }
+
+    #[test]
+    fn does_not_exceed_max_entries() {
+        let endpoint = Url::parse("http://localhost:8080").unwrap();
+        let cache = AuthCache::new(settings());
+
+        let keys = ["1", "2", "3"].map(|id| {
+            let key =
+                CacheKey::new(&metadata(&format!("_explorer_key={id}")), true, &endpoint).unwrap();
+            cache.insert_authenticated(key, user(id));
+            key
+        });
+
+        assert!(cache.get(&keys[0]).is_some());
+        assert!(cache.get(&keys[1]).is_some());
+        assert!(cache.get(&keys[2]).is_none());
+    }
+
+    #[test]
+    fn disabled_cache_stores_nothing() {
+        let endpoint = Url::parse("http://localhost:8080").unwrap();
+        let cache = AuthCache::new(AuthCacheSettings {
+            enabled: false,
+            ..settings()
+        });
+
+        let key = CacheKey::new(&metadata("_explorer_key=1"), true, &endpoint).unwrap();
+        cache.insert_authenticated(key, user("1"));
+        assert!(cache.get(&key).is_none());
+    }
+}
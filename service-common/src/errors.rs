@@ -0,0 +1,193 @@
+use std::{collections::HashMap, time::Duration};
+use tonic::{Code, Status};
+use tonic_types::{ErrorDetails, StatusExt};
+
+/// How long clients are advised to wait before retrying
+/// requests failed because of unavailable dependencies.
+pub const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Stable machine readable reasons attached to every returned error
+/// as `google.rpc.ErrorInfo`, so that clients do not have to parse messages.
+/// Each service defines its own reasons.
+pub trait Reason: Copy {
+    /// Domain of the `ErrorInfo` reasons returned by the service.
+    const DOMAIN: &'static str;
+    /// Reason of requests with invalid fields.
+    const INVALID_ARGUMENT: Self;
+    /// Reason of errors which are not expected to be handled by clients.
+    const INTERNAL: Self;
+
+    fn as_str(&self) -> &'static str;
+}
+
+/// Builds [`Status`] with `google.rpc` error details attached.
+pub struct ErrorBuilder<R> {
+    code: Code,
+    reason: R,
+    message: String,
+    metadata: HashMap<String, String>,
+    details: ErrorDetails,
+}
+
+impl<R: Reason> ErrorBuilder<R> {
+    pub fn new(code: Code, reason: R, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            reason,
+            message: message.into(),
+            metadata: HashMap::new(),
+            details: ErrorDetails::new(),
+        }
+    }
+
+    /// Adds a value to the `ErrorInfo` metadata.
+    pub fn metadata(mut self, key: impl Into<String>, value: impl ToString) -> Self {
+        self.metadata.insert(key.into(), value.to_string());
+        self
+    }
+
+    /// Adds a `BadRequest` field violation.
+    pub fn field_violation(
+        mut self,
+        field: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        self.details.add_bad_request_violation(field, description);
+        self
+    }
+
+    /// Adds a `PreconditionFailure` violation.
+    pub fn precondition_violation(
+        mut self,
+        violation_type: impl Into<String>,
+        subject: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        self.details
+            .add_precondition_failure_violation(violation_type, subject, description);
+        self
+    }
+
+    /// Attaches `RetryInfo`, advising clients to retry the request later.
+    pub fn retryable(mut self) -> Self {
+        self.details.set_retry_info(Some(RETRY_DELAY));
+        self
+    }
+
+    pub fn build(mut self) -> Status {
+        self.details
+            .set_error_info(self.reason.as_str(), R::DOMAIN, self.metadata);
+        Status::with_error_details(self.code, self.message, self.details)
+    }
+}
+
+/// Returns `INVALID_ARGUMENT` status with the field violation attached.
+pub fn invalid_argument<R: Reason>(field: &str, description: impl Into<String>) -> Status {
+    let description = description.into();
+    ErrorBuilder::new(
+        Code::InvalidArgument,
+        R::INVALID_ARGUMENT,
+        format!("invalid {field}: {description}"),
+    )
+    .field_violation(field, description)
+    .build()
+}
+
+pub fn internal<R: Reason>(message: impl Into<String>) -> Status {
+    ErrorBuilder::new(Code::Internal, R::INTERNAL, message).build()
+}
+
+/// Returns `UNAVAILABLE` status advising clients to retry the request later.
+pub fn unavailable<R: Reason>(reason: R, message: impl Into<String>) -> Status {
+    ErrorBuilder::new(Code::Unavailable, reason, message)
+        .retryable()
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[derive(Debug, Clone, Copy)]
+    enum TestReason {
+        InvalidArgument,
+        LimitReached,
+        Unavailable,
+        Internal,
+    }
+
+    impl Reason for TestReason {
+        const DOMAIN: &'static str = "test.blockscout.com";
+        const INVALID_ARGUMENT: Self = Self::InvalidArgument;
+        const INTERNAL: Self = Self::Internal;
+
+        fn as_str(&self) -> &'static str {
+            match self {
+                Self::InvalidArgument => "INVALID_ARGUMENT",
+                Self::LimitReached => "LIMIT_REACHED",
+                Self::Unavailable => "UNAVAILABLE",
+                Self::Internal => "INTERNAL",
+            }
+        }
+    }
+
+    #[test]
+    fn details_are_attached() {
+        let status = ErrorBuilder::new(
+            Code::FailedPrecondition,
+            TestReason::LimitReached,
+            "limit reached",
+        )
+        .metadata("limit", 10)
+        .precondition_violation("LIMIT", "verified_addresses", "limit reached")
+        .build();
+        assert_eq!(Code::FailedPrecondition, status.code());
+        assert_eq!("limit reached", status.message());
+
+        let details = status.get_error_details();
+        let error_info = details.error_info().expect("error info should be attached");
+        assert_eq!("LIMIT_REACHED", error_info.reason);
+        assert_eq!(TestReason::DOMAIN, error_info.domain);
+        assert_eq!(Some(&"10".to_string()), error_info.metadata.get("limit"));
+        let precondition_failure = details
+            .precondition_failure()
+            .expect("precondition failure should be attached");
+        assert_eq!(1, precondition_failure.violations.len());
+        assert!(details.retry_info().is_none());
+    }
+
+    #[test]
+    fn invalid_argument_contains_field() {
+        let status = invalid_argument::<TestReason>("chain_id", "value is too large");
+        assert_eq!(Code::InvalidArgument, status.code());
+
+        let details = status.get_error_details();
+        assert_eq!(
+            "INVALID_ARGUMENT",
+            details
+                .error_info()
+                .expect("error info should be attached")
+                .reason
+        );
+        let bad_request = details
+            .bad_request()
+            .expect("bad request should be attached");
+        assert_eq!("chain_id", bad_request.field_violations[0].field);
+        assert_eq!(
+            "value is too large",
+            bad_request.field_violations[0].description
+        );
+    }
+
+    #[test]
+    fn unavailable_is_retryable() {
+        let status = unavailable(TestReason::Unavailable, "blockscout is down");
+        assert_eq!(Code::Unavailable, status.code());
+        let retry_info = status
+            .get_error_details()
+            .retry_info()
+            .expect("retry info should be attached");
+        assert_eq!(Some(RETRY_DELAY), retry_info.retry_delay);
+    }
+}
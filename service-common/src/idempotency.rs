@@ -0,0 +1,472 @@
+use chrono::{NaiveDateTime, SubsecRound, Utc};
+use sea_orm::{
+    prelude::*,
+    sea_query::{Expr, OnConflict},
+    Condition, DatabaseConnection, Set,
+};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::{future::Future, sync::Arc, time::Duration};
+use thiserror::Error;
+use tonic::{metadata::MetadataMap, Status};
+
+pub const IDEMPOTENCY_KEY_NAME: &str = "idempotency-key";
+
+const MAX_KEY_LENGTH: usize = 255;
+
+/// The `idempotency_keys` table, created by the migrations of both services.
+mod idempotency_keys {
+    use sea_orm::entity::prelude::*;
+
+    #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+    #[sea_orm(table_name = "idempotency_keys")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i64,
+        pub created_at: DateTime,
+        pub caller: String,
+        pub key: String,
+        pub request_hash: String,
+        #[sea_orm(column_type = "Binary(BlobSize::Blob(None))", nullable)]
+        pub response: Option<Vec<u8>>,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields, default)]
+pub struct IdempotencySettings {
+    pub enabled: bool,
+    // How long responses are stored for the idempotency keys they were returned for
+    pub ttl_secs: u64,
+    // How long a key is held by the request still being processed. Keys of requests
+    // that have been interrupted (e.g. by a restart) can be taken over by retries after it
+    pub lease_secs: u64,
+}
+
+impl Default for IdempotencySettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl_secs: 24 * 60 * 60,
+            lease_secs: 2 * 60,
+        }
+    }
+}
+
+impl IdempotencySettings {
+    pub fn ttl(&self) -> Duration {
+        Duration::from_secs(self.ttl_secs)
+    }
+
+    pub fn lease(&self) -> Duration {
+        Duration::from_secs(self.lease_secs)
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("database error: {0}")]
+    Db(#[from] DbErr),
+    #[error("idempotency key has already been used for a different request")]
+    KeyReused,
+    #[error("request with the same idempotency key is still in progress")]
+    InProgress,
+    #[error("{0}")]
+    InvalidKey(String),
+    #[error("failed to decode stored response: {0}")]
+    Decode(#[from] prost::DecodeError),
+}
+
+/// Makes retries of mutating requests safe. The first successful response
+/// to a request with the `idempotency-key` header is stored for the caller,
+/// and replayed for repeated requests with the same key and payload.
+pub struct Idempotency {
+    db: Arc<DatabaseConnection>,
+    settings: IdempotencySettings,
+    map_error: fn(Error) -> Status,
+}
+
+impl Idempotency {
+    /// `map_error` converts the errors into the statuses returned by the service.
+    pub fn new(
+        db: Arc<DatabaseConnection>,
+        settings: IdempotencySettings,
+        map_error: fn(Error) -> Status,
+    ) -> Self {
+        Self {
+            db,
+            settings,
+            map_error,
+        }
+    }
+
+    /// Runs `handler` unless the response for the idempotency key of the request
+    /// has already been stored. Requests without the key are always processed.
+    pub async fn run<Req, Res, F, Fut>(
+        &self,
+        metadata: &MetadataMap,
+        caller: &str,
+        method: &str,
+        request: Req,
+        handler: F,
+    ) -> Result<Res, Status>
+    where
+        Req: prost::Message,
+        Res: prost::Message + Default,
+        F: FnOnce(Req) -> Fut,
+        Fut: Future<Output = Result<Res, Status>>,
+    {
+        let key = match idempotency_key(metadata).map_err(self.map_error)? {
+            Some(key) if self.settings.enabled => key,
+            _ => return handler(request).await,
+        };
+        let request_hash = request_hash(method, &request);
+        let reservation = reserve_key(&self.db, caller, key, &request_hash, &self.settings)
+            .await
+            .map_err(self.map_error)?;
+        let reserved_at = match reservation {
+            Reservation::New { reserved_at } => reserved_at,
+            Reservation::Completed(response) => {
+                return Res::decode(response.as_slice()).map_err(|err| (self.map_error)(err.into()))
+            }
+        };
+
+        match handler(request).await {
+            Ok(response) => {
+                complete_key(&self.db, caller, key, reserved_at, response.encode_to_vec())
+                    .await
+                    .map_err(self.map_error)?;
+                Ok(response)
+            }
+            Err(status) => {
+                // Failed requests are not stored, so that they can be retried with the same key
+                if let Err(err) = release_key(&self.db, caller, key, reserved_at).await {
+                    tracing::error!(err = ?err, "failed to release idempotency key");
+                }
+                Err(status)
+            }
+        }
+    }
+}
+
+fn idempotency_key(metadata: &MetadataMap) -> Result<Option<&str>, Error> {
+    let value = match metadata.get(IDEMPOTENCY_KEY_NAME) {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+    let key = value
+        .to_str()
+        .map_err(|_| Error::InvalidKey("must be ascii".to_string()))?;
+    if key.is_empty() || key.len() > MAX_KEY_LENGTH {
+        return Err(Error::InvalidKey(format!(
+            "must be from 1 to {MAX_KEY_LENGTH} characters long"
+        )));
+    }
+    Ok(Some(key))
+}
+
+fn request_hash<Req: prost::Message>(method: &str, request: &Req) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method);
+    // Separator, so that the method name cannot be mixed up with the payload
+    hasher.update([0u8]);
+    hasher.update(request.encode_to_vec());
+    format!("{:x}", hasher.finalize())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Reservation {
+    /// The key is free, the request has to be processed. The reservation time
+    /// identifies the reservation, so that it is not completed or released
+    /// by the request whose lease has been taken over.
+    New { reserved_at: NaiveDateTime },
+    /// The request has already been processed, its stored response has to be returned.
+    Completed(Vec<u8>),
+}
+
+fn before(now: NaiveDateTime, duration: Duration) -> NaiveDateTime {
+    chrono::Duration::from_std(duration)
+        .ok()
+        .and_then(|duration| now.checked_sub_signed(duration))
+        .unwrap_or(NaiveDateTime::MIN)
+}
+
+/// Reserves the idempotency key of the caller for the request with the given hash.
+/// Keys expire after the ttl, after which they can be reused for any request.
+/// Reservations of requests not completed within the lease are taken over.
+async fn reserve_key(
+    db: &DatabaseConnection,
+    caller: &str,
+    key: &str,
+    request_hash: &str,
+    settings: &IdempotencySettings,
+) -> Result<Reservation, Error> {
+    // Postgres stores timestamps with microsecond precision, while the reservation
+    // time has to match the stored one exactly
+    let now = Utc::now().naive_utc().trunc_subsecs(6);
+    idempotency_keys::Entity::delete_many()
+        .filter(idempotency_keys::Column::CreatedAt.lt(before(now, settings.ttl())))
+        .exec(db)
+        .await?;
+
+    let existing = idempotency_keys::Entity::find()
+        .filter(idempotency_keys::Column::Caller.eq(caller))
+        .filter(idempotency_keys::Column::Key.eq(key))
+        .one(db)
+        .await?;
+    if let Some(existing) = existing {
+        if existing.request_hash != request_hash {
+            return Err(Error::KeyReused);
+        }
+        if let Some(response) = existing.response {
+            return Ok(Reservation::Completed(response));
+        }
+        if existing.created_at >= before(now, settings.lease()) {
+            return Err(Error::InProgress);
+        }
+        // The request holding the key has been interrupted. Only one of the
+        // concurrent retries takes the reservation over
+        let updated = idempotency_keys::Entity::update_many()
+            .col_expr(idempotency_keys::Column::CreatedAt, Expr::value(now))
+            .filter(idempotency_keys::Column::Id.eq(existing.id))
+            .filter(idempotency_keys::Column::CreatedAt.eq(existing.created_at))
+            .filter(idempotency_keys::Column::Response.is_null())
+            .exec(db)
+            .await?;
+        if updated.rows_affected == 0 {
+            return Err(Error::InProgress);
+        }
+        return Ok(Reservation::New { reserved_at: now });
+    }
+
+    // The key could have been reserved by a concurrent request since the check above
+    let inserted = idempotency_keys::Entity::insert(idempotency_keys::ActiveModel {
+        created_at: Set(now),
+        caller: Set(caller.to_string()),
+        key: Set(key.to_string()),
+        request_hash: Set(request_hash.to_string()),
+        ..Default::default()
+    })
+    .on_conflict(
+        OnConflict::columns([
+            idempotency_keys::Column::Caller,
+            idempotency_keys::Column::Key,
+        ])
+        .do_nothing()
+        .to_owned(),
+    )
+    .exec_without_returning(db)
+    .await?;
+    if inserted == 0 {
+        return Err(Error::InProgress);
+    }
+    Ok(Reservation::New { reserved_at: now })
+}
+
+fn reservation_condition(caller: &str, key: &str, reserved_at: NaiveDateTime) -> Condition {
+    Condition::all()
+        .add(idempotency_keys::Column::Caller.eq(caller))
+        .add(idempotency_keys::Column::Key.eq(key))
+        .add(idempotency_keys::Column::CreatedAt.eq(reserved_at))
+        .add(idempotency_keys::Column::Response.is_null())
+}
+
+/// Stores the response of the request processed under the reserved key.
+async fn complete_key(
+    db: &DatabaseConnection,
+    caller: &str,
+    key: &str,
+    reserved_at: NaiveDateTime,
+    response: Vec<u8>,
+) -> Result<(), Error> {
+    idempotency_keys::Entity::update_many()
+        .col_expr(idempotency_keys::Column::Response, Expr::value(response))
+        .filter(reservation_condition(caller, key, reserved_at))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// Releases the reserved key if the request has failed, so that it can be retried.
+async fn release_key(
+    db: &DatabaseConnection,
+    caller: &str,
+    key: &str,
+    reserved_at: NaiveDateTime,
+) -> Result<(), Error> {
+    idempotency_keys::Entity::delete_many()
+        .filter(reservation_condition(caller, key, reserved_at))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use sea_orm::{ConnectionTrait, Database, DbBackend, Statement};
+
+    async fn init_db() -> DatabaseConnection {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+        for sql in [
+            "CREATE TABLE idempotency_keys (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                caller TEXT NOT NULL,
+                key TEXT NOT NULL,
+                request_hash TEXT NOT NULL,
+                response BLOB
+            )",
+            "CREATE UNIQUE INDEX idempotency_keys_caller_key_index
+                ON idempotency_keys (caller, key)",
+        ] {
+            db.execute(Statement::from_string(DbBackend::Sqlite, sql.to_string()))
+                .await
+                .unwrap();
+        }
+        db
+    }
+
+    fn settings() -> IdempotencySettings {
+        IdempotencySettings {
+            enabled: true,
+            ttl_secs: 60 * 60,
+            lease_secs: 60,
+        }
+    }
+
+    async fn set_created_at(db: &DatabaseConnection, created_at: NaiveDateTime) {
+        idempotency_keys::Entity::update_many()
+            .col_expr(idempotency_keys::Column::CreatedAt, Expr::value(created_at))
+            .exec(db)
+            .await
+            .unwrap();
+    }
+
+    async fn reserve_new(db: &DatabaseConnection, caller: &str, hash: &str) -> NaiveDateTime {
+        match reserve_key(db, caller, "key", hash, &settings())
+            .await
+            .unwrap()
+        {
+            Reservation::New { reserved_at } => reserved_at,
+            reservation => panic!("expected new reservation, got {reservation:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn replays_completed_response() {
+        let db = init_db().await;
+
+        let reserved_at = reserve_new(&db, "user:1", "hash").await;
+        assert!(matches!(
+            reserve_key(&db, "user:1", "key", "hash", &settings()).await,
+            Err(Error::InProgress)
+        ));
+
+        complete_key(&db, "user:1", "key", reserved_at, vec![1, 2, 3])
+            .await
+            .unwrap();
+        let reservation = reserve_key(&db, "user:1", "key", "hash", &settings())
+            .await
+            .unwrap();
+        assert_eq!(Reservation::Completed(vec![1, 2, 3]), reservation);
+
+        // Keys are scoped by callers
+        reserve_new(&db, "user:2", "other").await;
+    }
+
+    #[tokio::test]
+    async fn rejects_reused_key() {
+        let db = init_db().await;
+
+        let reserved_at = reserve_new(&db, "user:1", "hash").await;
+        complete_key(&db, "user:1", "key", reserved_at, vec![])
+            .await
+            .unwrap();
+        assert!(matches!(
+            reserve_key(&db, "user:1", "key", "other", &settings()).await,
+            Err(Error::KeyReused)
+        ));
+    }
+
+    #[tokio::test]
+    async fn released_and_expired_keys_can_be_reused() {
+        let db = init_db().await;
+
+        let reserved_at = reserve_new(&db, "user:1", "hash").await;
+        release_key(&db, "user:1", "key", reserved_at)
+            .await
+            .unwrap();
+        let reserved_at = reserve_new(&db, "user:1", "other").await;
+        complete_key(&db, "user:1", "key", reserved_at, vec![1])
+            .await
+            .unwrap();
+
+        // Completed keys are not released
+        release_key(&db, "user:1", "key", reserved_at)
+            .await
+            .unwrap();
+        let reservation = reserve_key(&db, "user:1", "key", "other", &settings())
+            .await
+            .unwrap();
+        assert_eq!(Reservation::Completed(vec![1]), reservation);
+
+        set_created_at(&db, Utc::now().naive_utc() - chrono::Duration::hours(2)).await;
+        reserve_new(&db, "user:1", "hash").await;
+    }
+
+    #[tokio::test]
+    async fn abandoned_reservations_are_taken_over_after_lease() {
+        let db = init_db().await;
+
+        reserve_new(&db, "user:1", "hash").await;
+        // The request holding the key has been interrupted half a lease ago
+        let interrupted_at =
+            Utc::now().naive_utc().trunc_subsecs(6) - chrono::Duration::seconds(30);
+        set_created_at(&db, interrupted_at).await;
+        assert!(matches!(
+            reserve_key(&db, "user:1", "key", "hash", &settings()).await,
+            Err(Error::InProgress)
+        ));
+
+        // The lease has expired
+        let interrupted_at = interrupted_at - chrono::Duration::minutes(1);
+        set_created_at(&db, interrupted_at).await;
+        // Different requests still cannot reuse the key
+        assert!(matches!(
+            reserve_key(&db, "user:1", "key", "other", &settings()).await,
+            Err(Error::KeyReused)
+        ));
+        let reserved_at = reserve_new(&db, "user:1", "hash").await;
+        assert!(matches!(
+            reserve_key(&db, "user:1", "key", "hash", &settings()).await,
+            Err(Error::InProgress)
+        ));
+
+        // The interrupted request neither releases nor completes the taken over key
+        release_key(&db, "user:1", "key", interrupted_at)
+            .await
+            .unwrap();
+        complete_key(&db, "user:1", "key", interrupted_at, vec![1])
+            .await
+            .unwrap();
+        assert!(matches!(
+            reserve_key(&db, "user:1", "key", "hash", &settings()).await,
+            Err(Error::InProgress)
+        ));
+
+        complete_key(&db, "user:1", "key", reserved_at, vec![2])
+            .await
+            .unwrap();
+        let reservation = reserve_key(&db, "user:1", "key", "hash", &settings())
+            .await
+            .unwrap();
+        assert_eq!(Reservation::Completed(vec![2]), reservation);
+    }
+}
@@ -0,0 +1,9 @@
+//! Functionality shared by `admin-server` and `contracts-info-server`.
+
+#[cfg(feature = "auth")]
+pub mod auth;
+#[cfg(feature = "errors")]
+pub mod errors;
+#[cfg(feature = "idempotency")]
+pub mod idempotency;
+pub mod user;
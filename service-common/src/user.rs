@@ -0,0 +1,60 @@
+/// Value personal emails are replaced with on the user data erasure.
+/// Uses a reserved domain, so that it could never match an email of a real account.
+pub const ANONYMIZED_EMAIL: &str = "anonymized@erased.invalid";
+
+/// Blockscout account the request is made on behalf of.
+/// Ids are assigned by the Blockscout instance of the chain,
+/// so the same person has different ids at different chains.
+#[derive(Clone, Debug, PartialOrd, PartialEq, Eq, Hash)]
+pub struct User {
+    pub id: String,
+    pub email: String,
+}
+
+impl User {
+    pub fn new(id: impl Into<String>, email: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            email: email.into(),
+        }
+    }
+
+    /// Records created before user ids were stored have no owner id,
+    /// so the owner is identified by the email for them.
+    pub fn is_owner(&self, owner_id: Option<&str>, owner_email: &str) -> bool {
+        match owner_id {
+            Some(owner_id) => owner_id == self.id,
+            None => owner_email == self.email,
+        }
+    }
+}
+
+/// The user whose personal data is exported or erased.
+/// Admin requests may identify the user by either the id or the email only.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DataSubject {
+    User(User),
+    Id(String),
+    Email(String),
+}
+
+impl From<User> for DataSubject {
+    fn from(user: User) -> Self {
+        Self::User(user)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_owner() {
+        let user = User::new("1", "user@gmail.com");
+        assert!(user.is_owner(Some("1"), "user@gmail.com"));
+        assert!(user.is_owner(Some("1"), "old_email@gmail.com"));
+        assert!(user.is_owner(None, "user@gmail.com"));
+        assert!(!user.is_owner(Some("2"), "user@gmail.com"));
+        assert!(!user.is_owner(None, "another@gmail.com"));
+    }
+}